@@ -0,0 +1,186 @@
+//! Benchmarks for the functions of `AssetsExtension`.
+//!
+//! Chain extensions are not pallets, so the benchmarks hang off the stand-in [`Pallet`] below,
+//! which the runtime registers as `[nativex_assets_extension, AssetsExtensionBench::<Runtime, I>]`
+//! for the assets instance `I` it gives to contracts.
+//!
+//! Each benchmark calls a contract that calls the function `r` times, see
+//! [`nativex_chain_extensions::benchmarking`]. The runtime's chain extension must route extension
+//! id 1 to `AssetsExtension<I>`. Every call of `transfer` and `transfer_from` pays the same
+//! recipient, so only the first one creates its account.
+
+use crate::{func, AssetsExtension};
+use frame_benchmarking::v2::*;
+use frame_support::traits::{
+	fungibles::{approvals, metadata, Create, Mutate},
+	Currency, Get,
+};
+use nativex_chain_extensions::{
+	benchmarking::{call_fixture, deploy_fixture, MAX_CALLS},
+	SUCCESS,
+};
+use pallet_assets::BenchmarkHelper;
+use sp_std::{marker::PhantomData, vec};
+
+type AssetIdOf<T, I> = <T as pallet_assets::Config<I>>::AssetId;
+type BalanceOf<T, I> = <T as pallet_assets::Config<I>>::Balance;
+
+pub struct Pallet<T: Config<I>, I: 'static = ()>(frame_system::Pallet<T>, PhantomData<I>);
+pub trait Config<I: 'static = ()>: pallet_assets::Config<I> + pallet_contracts::Config {}
+
+/// The caller, the deployed fixture and a sufficient asset both of them hold.
+///
+/// The asset has metadata of the maximum length, and the fixture has the native balance to pay
+/// approval deposits.
+fn setup<T: Config<I>, I: 'static>(
+) -> Result<(T::AccountId, T::AccountId, AssetIdOf<T, I>), BenchmarkError> {
+	let caller: T::AccountId = whitelisted_caller();
+	let contract = deploy_fixture::<T>(&caller)?;
+	let owner: T::AccountId = account("owner", 0, 0);
+	let native = <T as pallet_assets::Config<I>>::Currency::minimum_balance() * 1_000_000u32.into();
+	<T as pallet_assets::Config<I>>::Currency::make_free_balance_be(&owner, native);
+	let _ = <T as pallet_assets::Config<I>>::Currency::deposit_creating(&contract, native);
+
+	let id: AssetIdOf<T, I> =
+		<T as pallet_assets::Config<I>>::BenchmarkHelper::create_asset_id_parameter(0).into();
+	let limit = T::StringLimit::get() as usize;
+	<pallet_assets::Pallet<T, I> as Create<_>>::create(id, owner.clone(), true, 1u32.into())?;
+	<pallet_assets::Pallet<T, I> as metadata::Mutate<_>>::set(
+		id,
+		&owner,
+		vec![0; limit],
+		vec![0; limit],
+		18,
+	)?;
+	<pallet_assets::Pallet<T, I> as Mutate<_>>::mint_into(id, &caller, 1_000_000u32.into())?;
+	<pallet_assets::Pallet<T, I> as Mutate<_>>::mint_into(id, &contract, 1_000_000u32.into())?;
+	Ok((caller, contract, id))
+}
+
+/// Make the fixture call function `func_id` `r` times with `input` and check it succeeded.
+fn call<T: Config<I>, I: 'static>(
+	caller: T::AccountId,
+	contract: T::AccountId,
+	r: u32,
+	func_id: u16,
+	input: impl parity_scale_codec::Encode,
+) {
+	let (status, _) = call_fixture::<T, AssetsExtension<I>>(caller, contract, r, func_id, input);
+	assert_eq!(status, SUCCESS);
+}
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn total_supply(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::TOTAL_SUPPLY, id);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn balance_of(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+		let input = (id, caller.clone());
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::BALANCE_OF, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn allowance(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+		<pallet_assets::Pallet<T, I> as approvals::Mutate<_>>::approve(
+			id,
+			&contract,
+			&caller,
+			1u32.into(),
+		)?;
+		let input = (id, contract.clone(), caller.clone());
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::ALLOWANCE, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let input = (id, recipient, BalanceOf::<T, I>::from(1u32));
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::TRANSFER, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer_from(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+		// The last call uses up the approval, which also removes it and unreserves its deposit.
+		<pallet_assets::Pallet<T, I> as approvals::Mutate<_>>::approve(
+			id,
+			&caller,
+			&contract,
+			r.max(1).into(),
+		)?;
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let input = (id, caller.clone(), recipient, BalanceOf::<T, I>::from(1u32));
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::TRANSFER_FROM, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn approve(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+		// Every call replaces an existing approval: it is cancelled, then approved again.
+		<pallet_assets::Pallet<T, I> as approvals::Mutate<_>>::approve(
+			id,
+			&contract,
+			&caller,
+			1u32.into(),
+		)?;
+		let input = (id, caller.clone(), BalanceOf::<T, I>::from(1u32));
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::APPROVE, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn metadata(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, id) = setup::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(caller, contract, r, func::NAME, id);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,218 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Chain extension giving ink! contracts access to `pallet_assets`.
+//!
+//! The functions mirror PSP22, so a contract can wrap any asset as a PSP22 token. Transfers and
+//! approvals act as the calling contract: it is the sender of `transfer`, the owner in
+//! `approve` and the spender in `transfer_from`.
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	log::trace,
+	storage::with_storage_layer,
+	traits::tokens::fungibles::{approvals, metadata},
+};
+use frame_system::RawOrigin;
+use nativex_chain_extensions::{
+	per_call, read_input, write_output, Extension, ExtensionError, ExtensionResult, Failure,
+};
+use pallet_contracts::chain_extension::{Environment, Ext, InitState, SysConfig};
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{
+	traits::{StaticLookup, Zero},
+	DispatchError, TokenError,
+};
+use sp_std::marker::PhantomData;
+
+/// `func_id`s of the functions, the lower 16 bits of the contract's `func_id`.
+pub mod func {
+	pub const TOTAL_SUPPLY: u16 = 1;
+	pub const BALANCE_OF: u16 = 2;
+	pub const ALLOWANCE: u16 = 3;
+	pub const TRANSFER: u16 = 4;
+	pub const TRANSFER_FROM: u16 = 5;
+	pub const APPROVE: u16 = 6;
+	pub const NAME: u16 = 7;
+	pub const SYMBOL: u16 = 8;
+	pub const DECIMALS: u16 = 9;
+}
+
+type AssetIdOf<T, I> = <T as pallet_assets::Config<I>>::AssetId;
+type BalanceOf<T, I> = <T as pallet_assets::Config<I>>::Balance;
+type AccountIdOf<T> = <T as SysConfig>::AccountId;
+
+/// Contract extension for `pallet_assets` instance `I`, charging weights `W`.
+pub struct AssetsExtension<I = (), W = ()>(PhantomData<(I, W)>);
+
+impl<I, W> Default for AssetsExtension<I, W> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+/// Report the errors a contract can handle as status codes; any other dispatch error becomes
+/// `DispatchFailed`.
+fn dispatch_failure<T: pallet_assets::Config<I>, I: 'static>(error: DispatchError) -> Failure {
+	let status = if error == pallet_assets::Error::<T, I>::BalanceLow.into() ||
+		error == TokenError::FundsUnavailable.into()
+	{
+		ExtensionError::InsufficientBalance
+	} else if error == pallet_assets::Error::<T, I>::Unapproved.into() {
+		ExtensionError::InsufficientAllowance
+	} else {
+		ExtensionError::DispatchFailed
+	};
+	status.into()
+}
+
+impl<T, I, W> Extension<T> for AssetsExtension<I, W>
+where
+	T: pallet_contracts::Config + pallet_assets::Config<I>,
+	I: 'static,
+	W: WeightInfo,
+{
+	const ID: u16 = 1;
+
+	fn call<E>(&mut self, func_id: u16, env: Environment<E, InitState>) -> ExtensionResult
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let contract = env.ext().address().clone();
+		let origin = || RawOrigin::Signed(contract.clone()).into();
+		trace!(
+			target: "runtime",
+			"[ChainExtension]|call|ext_id:{:}|func_id:{:}",
+			<Self as Extension<T>>::ID,
+			func_id
+		);
+
+		match func_id {
+			func::TOTAL_SUPPLY => {
+				env.charge_weight(per_call(W::total_supply))?;
+				let id: AssetIdOf<T, I> = read_input(&mut env)?;
+				write_output(&mut env, &pallet_assets::Pallet::<T, I>::total_supply(id))
+			},
+
+			func::BALANCE_OF => {
+				env.charge_weight(per_call(W::balance_of))?;
+				let (id, owner): (AssetIdOf<T, I>, AccountIdOf<T>) = read_input(&mut env)?;
+				write_output(&mut env, &pallet_assets::Pallet::<T, I>::balance(id, owner))
+			},
+
+			func::ALLOWANCE => {
+				env.charge_weight(per_call(W::allowance))?;
+				let (id, owner, spender): (AssetIdOf<T, I>, AccountIdOf<T>, AccountIdOf<T>) =
+					read_input(&mut env)?;
+				let allowance = <pallet_assets::Pallet<T, I> as approvals::Inspect<_>>::allowance(
+					id, &owner, &spender,
+				);
+				write_output(&mut env, &allowance)
+			},
+
+			func::TRANSFER => {
+				env.charge_weight(per_call(W::transfer))?;
+				let (id, to, amount): (AssetIdOf<T, I>, AccountIdOf<T>, BalanceOf<T, I>) =
+					read_input(&mut env)?;
+				with_storage_layer(|| {
+					pallet_assets::Pallet::<T, I>::transfer(
+						origin(),
+						id.into(),
+						T::Lookup::unlookup(to),
+						amount,
+					)
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::TRANSFER_FROM => {
+				env.charge_weight(per_call(W::transfer_from))?;
+				let (id, from, to, amount): (
+					AssetIdOf<T, I>,
+					AccountIdOf<T>,
+					AccountIdOf<T>,
+					BalanceOf<T, I>,
+				) = read_input(&mut env)?;
+				with_storage_layer(|| {
+					pallet_assets::Pallet::<T, I>::transfer_approved(
+						origin(),
+						id.into(),
+						T::Lookup::unlookup(from),
+						T::Lookup::unlookup(to),
+						amount,
+					)
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::APPROVE => {
+				env.charge_weight(per_call(W::approve))?;
+				let (id, spender, amount): (AssetIdOf<T, I>, AccountIdOf<T>, BalanceOf<T, I>) =
+					read_input(&mut env)?;
+				// `approve_transfer` adds to an existing approval, PSP22 `approve` replaces it.
+				with_storage_layer(|| {
+					let current = <pallet_assets::Pallet<T, I> as approvals::Inspect<_>>::allowance(
+						id, &contract, &spender,
+					);
+					if !current.is_zero() {
+						pallet_assets::Pallet::<T, I>::cancel_approval(
+							origin(),
+							id.into(),
+							T::Lookup::unlookup(spender.clone()),
+						)?;
+					}
+					if !amount.is_zero() {
+						pallet_assets::Pallet::<T, I>::approve_transfer(
+							origin(),
+							id.into(),
+							T::Lookup::unlookup(spender),
+							amount,
+						)?;
+					}
+					Ok(())
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::NAME => {
+				env.charge_weight(per_call(W::metadata))?;
+				let id: AssetIdOf<T, I> = read_input(&mut env)?;
+				write_output(
+					&mut env,
+					&<pallet_assets::Pallet<T, I> as metadata::Inspect<_>>::name(id),
+				)
+			},
+
+			func::SYMBOL => {
+				env.charge_weight(per_call(W::metadata))?;
+				let id: AssetIdOf<T, I> = read_input(&mut env)?;
+				write_output(
+					&mut env,
+					&<pallet_assets::Pallet<T, I> as metadata::Inspect<_>>::symbol(id),
+				)
+			},
+
+			func::DECIMALS => {
+				env.charge_weight(per_call(W::metadata))?;
+				let id: AssetIdOf<T, I> = read_input(&mut env)?;
+				write_output(
+					&mut env,
+					&<pallet_assets::Pallet<T, I> as metadata::Inspect<_>>::decimals(id),
+				)
+			},
+
+			_ => Err(ExtensionError::UnknownFunction.into()),
+		}
+	}
+}
@@ -0,0 +1,58 @@
+use crate::AssetsExtension;
+use frame_support::traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, GenesisBuild};
+use frame_system::{EnsureRoot, EnsureSigned};
+use nativex_chain_extensions::Registry;
+pub use nativex_chain_extensions::test_utils::{ALICE, BOB};
+use sp_runtime::AccountId32;
+
+// Configure a mock runtime with contracts and assets to test the extension.
+nativex_chain_extensions::impl_mock_runtime!(
+	extension = Registry<(AssetsExtension,)>,
+	pallets { Assets: pallet_assets }
+);
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId32>>;
+	type ForceOrigin = EnsureRoot<AccountId32>;
+	type AssetDeposit = ConstU64<1>;
+	type AssetAccountDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type MetadataDepositPerByte = ConstU64<1>;
+	type ApprovalDeposit = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// Asset created at genesis, owned by `ALICE`, with metadata.
+pub const ASSET: u32 = 1;
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000_000), (BOB, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(ASSET, ALICE, true, 1)],
+		metadata: vec![(ASSET, b"Token".to_vec(), b"TKN".to_vec(), 10)],
+		accounts: vec![(ASSET, ALICE, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
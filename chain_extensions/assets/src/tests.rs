@@ -0,0 +1,124 @@
+use crate::{func, mock::*, AssetsExtension, WeightInfo};
+use frame_support::{
+	assert_ok,
+	traits::tokens::fungibles::approvals::Inspect,
+	weights::{constants::RocksDbWeight, Weight},
+};
+use nativex_chain_extensions::{
+	per_call,
+	test_utils::{self, call_extension, decode, deploy_proxy},
+	ExtensionError, SUCCESS,
+};
+use parity_scale_codec::Encode;
+use sp_runtime::AccountId32;
+
+/// Deploy the proxy contract, which forwards its input to the chain extension, and fund it.
+fn deploy() -> AccountId32 {
+	let contract = deploy_proxy::<Test>(1_000);
+	assert_ok!(Assets::mint(RuntimeOrigin::signed(ALICE), ASSET, contract.clone(), 100));
+	contract
+}
+
+/// Call function `func_id` of the assets extension through `contract`; returns the status code
+/// and the output.
+fn call(contract: &AccountId32, func_id: u16, input: impl Encode) -> (u32, Vec<u8>) {
+	test_utils::call::<Test, AssetsExtension>(contract, func_id, input)
+}
+
+#[test]
+fn reads_balances_and_metadata() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(decode::<u64>(call(&contract, func::TOTAL_SUPPLY, ASSET)), 1_100);
+		assert_eq!(decode::<u64>(call(&contract, func::BALANCE_OF, (ASSET, ALICE))), 1_000);
+		assert_eq!(decode::<u64>(call(&contract, func::BALANCE_OF, (ASSET, &contract))), 100);
+		assert_eq!(decode::<Vec<u8>>(call(&contract, func::NAME, ASSET)), b"Token".to_vec());
+		assert_eq!(decode::<Vec<u8>>(call(&contract, func::SYMBOL, ASSET)), b"TKN".to_vec());
+		assert_eq!(decode::<u8>(call(&contract, func::DECIMALS, ASSET)), 10);
+	});
+}
+
+#[test]
+fn transfer_acts_as_contract() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(call(&contract, func::TRANSFER, (ASSET, BOB, 40u64)).0, SUCCESS);
+		assert_eq!(Assets::balance(ASSET, &contract), 60);
+		assert_eq!(Assets::balance(ASSET, BOB), 40);
+	});
+}
+
+#[test]
+fn transfer_reports_insufficient_balance() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(
+			call(&contract, func::TRANSFER, (ASSET, BOB, 101u64)).0,
+			ExtensionError::InsufficientBalance as u32
+		);
+		assert_eq!(Assets::balance(ASSET, &contract), 100);
+		assert_eq!(Assets::balance(ASSET, BOB), 0);
+	});
+}
+
+#[test]
+fn approve_replaces_allowance() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(call(&contract, func::APPROVE, (ASSET, BOB, 50u64)).0, SUCCESS);
+		assert_eq!(decode::<u64>(call(&contract, func::ALLOWANCE, (ASSET, &contract, BOB))), 50);
+
+		assert_eq!(call(&contract, func::APPROVE, (ASSET, BOB, 20u64)).0, SUCCESS);
+		assert_eq!(Assets::allowance(ASSET, &contract, &BOB), 20);
+
+		assert_eq!(call(&contract, func::APPROVE, (ASSET, BOB, 0u64)).0, SUCCESS);
+		assert_eq!(Assets::allowance(ASSET, &contract, &BOB), 0);
+	});
+}
+
+#[test]
+fn transfer_from_spends_contract_allowance() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		assert_ok!(Assets::approve_transfer(
+			RuntimeOrigin::signed(ALICE),
+			ASSET,
+			contract.clone(),
+			30
+		));
+
+		assert_eq!(call(&contract, func::TRANSFER_FROM, (ASSET, ALICE, BOB, 20u64)).0, SUCCESS);
+		assert_eq!(Assets::balance(ASSET, ALICE), 980);
+		assert_eq!(Assets::balance(ASSET, BOB), 20);
+
+		assert_eq!(
+			call(&contract, func::TRANSFER_FROM, (ASSET, ALICE, BOB, 20u64)).0,
+			ExtensionError::InsufficientAllowance as u32
+		);
+		assert_eq!(Assets::balance(ASSET, BOB), 20);
+	});
+}
+
+#[test]
+fn unknown_ids_return_status_codes() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(call(&contract, 100, ASSET).0, ExtensionError::UnknownFunction as u32);
+		assert_eq!(
+			call_extension::<Test>(&contract, 7 << 16 | func::TOTAL_SUPPLY as u32, ASSET).0,
+			ExtensionError::UnknownExtension as u32
+		);
+	});
+}
+
+#[test]
+fn charges_the_cost_of_one_call() {
+	let one_transfer =
+		Weight::from_parts(60_000_000, 10_506) + RocksDbWeight::get().reads_writes(4, 4);
+	assert_eq!(per_call(<() as WeightInfo>::transfer), one_transfer);
+}
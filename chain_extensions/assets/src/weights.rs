@@ -0,0 +1,213 @@
+//! Weights for nativex_assets_extension
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=nativex_assets_extension` before relying on them.
+//!
+//! Each function `f(r)` is the cost of a contract call making `r` calls of the function, and the
+//! extension charges its slope, see [`nativex_chain_extensions::per_call`]:
+//! - The base is a `pallet_contracts` call into a small contract. It cancels out of the charge.
+//! - Each call counts the `pallet_assets` storage accesses listed above the function. The proof
+//!   size adds up, for every item read, its `MaxEncodedLen` in the runtime plus the 2475 bytes of
+//!   trie proof the benchmarks budget for a map entry. The execution time is given with its
+//!   justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for nativex_assets_extension.
+pub trait WeightInfo {
+	fn total_supply(r: u32, ) -> Weight;
+	fn balance_of(r: u32, ) -> Weight;
+	fn allowance(r: u32, ) -> Weight;
+	fn transfer(r: u32, ) -> Weight;
+	fn transfer_from(r: u32, ) -> Weight;
+	fn approve(r: u32, ) -> Weight;
+	fn metadata(r: u32, ) -> Weight;
+}
+
+/// Weights for nativex_assets_extension using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:0)
+	/// Proof: Assets Asset (max_size: 210, added: 2685)
+	/// Execution: one read and the copy of a `u128`, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn total_supply(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_685).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Account (r:1 w:0)
+	/// Proof: Assets Account (max_size: 134, added: 2609)
+	/// Execution: one read and the copy of a `u128`, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn balance_of(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_609).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Approvals (r:1 w:0)
+	/// Proof: Assets Approvals (max_size: 148, added: 2623)
+	/// Execution: one read and the copy of a `u128`, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn allowance(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_623).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_size: 210, added: 2685)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_size: 134, added: 2609)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a `pallet_assets` transfer between two accounts, 60 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn transfer(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 10_506).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Approvals (r:1 w:1)
+	/// Proof: Assets Approvals (max_size: 148, added: 2623)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_size: 210, added: 2685)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_size: 134, added: 2609)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: `transfer` plus the approval update and the deposit unreserve, 80 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn transfer_from(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(80_000_000, 15_732).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_size: 210, added: 2685)
+	/// Storage: Assets Approvals (r:1 w:1)
+	/// Proof: Assets Approvals (max_size: 148, added: 2623)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: replacing an approval, which unreserves and reserves its deposit, 70 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn approve(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(70_000_000, 7_911).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Assets Metadata (r:1 w:0)
+	/// Proof: Assets Metadata (max_size: 140, added: 2615)
+	/// Execution: one read and the copy of at most 50 bytes, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn metadata(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_615).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn total_supply(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_685).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn balance_of(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_609).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn allowance(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_623).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn transfer(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 10_506).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(r.into())))
+	}
+	fn transfer_from(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(80_000_000, 15_732).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(r.into())))
+	}
+	fn approve(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(70_000_000, 7_911).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+	}
+	fn metadata(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_615).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
@@ -0,0 +1,53 @@
+;; Calls the chain extension `r` times with the same input and returns the last result, so the
+;; benchmarks can tell the cost of one call from the cost of the contract around it.
+;;
+;; Input:  r (u32, little endian) ++ func_id (u32, little endian) ++ extension input
+;; Output: status code (u32, little endian) ++ extension output
+;;
+;; `call_repeatedly.wasm` is this module compiled with `wat`; the crate's tests check they match.
+(module
+	(import "seal0" "seal_input" (func $seal_input (param i32 i32)))
+	(import "seal0" "seal_call_chain_extension"
+		(func $seal_call_chain_extension (param i32 i32 i32 i32 i32) (result i32)))
+	(import "seal0" "seal_return" (func $seal_return (param i32 i32 i32)))
+	(import "env" "memory" (memory 1 1))
+
+	;; [0, 4) capacity, then length, of the input buffer
+	(data (i32.const 0) "\00\04")
+	;; [4, 8) capacity, then length, of the output buffer
+	(data (i32.const 4) "\00\04")
+	;; [8, 1032) input buffer
+	;; [2048, 2052) status code, followed by the output buffer at [2052, 3076)
+
+	(func (export "deploy"))
+
+	(func (export "call")
+		(local $r i32)
+		(call $seal_input (i32.const 8) (i32.const 0))
+		(local.set $r (i32.load (i32.const 8)))
+		(block $done
+			(loop $next
+				(br_if $done (i32.eqz (local.get $r)))
+				;; Each call shrinks the output length to what it wrote.
+				(i32.store (i32.const 4) (i32.const 1024))
+				(i32.store
+					(i32.const 2048)
+					(call $seal_call_chain_extension
+						(i32.load (i32.const 12))
+						(i32.const 16)
+						(i32.sub (i32.load (i32.const 0)) (i32.const 8))
+						(i32.const 2052)
+						(i32.const 4)
+					)
+				)
+				(local.set $r (i32.sub (local.get $r) (i32.const 1)))
+				(br $next)
+			)
+		)
+		(call $seal_return
+			(i32.const 0)
+			(i32.const 2048)
+			(i32.add (i32.load (i32.const 4)) (i32.const 4))
+		)
+	)
+)
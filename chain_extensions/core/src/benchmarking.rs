@@ -0,0 +1,77 @@
+//! Helpers to benchmark an extension through a real contract call.
+//!
+//! [`deploy_fixture`] deploys `fixtures/call_repeatedly.wat`, a contract calling the chain
+//! extension `r` times with the same input, and [`call_fixture`] calls it. An extension benchmarks
+//! each function for `r` in `[0, MAX_CALLS]` and charges the slope with
+//! [`per_call`](crate::per_call): the cost of one call along the real path, through the host
+//! function, the runtime's [`Registry`](crate::Registry) and the extension.
+
+use crate::Extension;
+use frame_benchmarking::BenchmarkError;
+use frame_support::{traits::Currency, weights::Weight};
+use pallet_contracts::{chain_extension::SysConfig, Determinism};
+use pallet_contracts_primitives::Code;
+use parity_scale_codec::{Decode, Encode};
+use sp_std::{vec, vec::Vec};
+
+/// Upper bound of the number of extension calls in one contract call.
+pub const MAX_CALLS: u32 = 20;
+
+/// A contract calling the chain extension `r` times, see `fixtures/call_repeatedly.wat`.
+const FIXTURE: &[u8] = include_bytes!("../fixtures/call_repeatedly.wasm");
+
+type AccountIdOf<T> = <T as SysConfig>::AccountId;
+
+/// Deploy the fixture from `caller`, funding `caller` first.
+pub fn deploy_fixture<T: pallet_contracts::Config>(
+	caller: &AccountIdOf<T>,
+) -> Result<AccountIdOf<T>, BenchmarkError> {
+	let min_balance = T::Currency::minimum_balance();
+	T::Currency::make_free_balance_be(caller, min_balance * 1_000_000u32.into());
+	let result = pallet_contracts::Pallet::<T>::bare_instantiate(
+		caller.clone(),
+		min_balance,
+		Weight::MAX,
+		None,
+		Code::Upload(FIXTURE.to_vec()),
+		vec![],
+		vec![],
+		false,
+	);
+	let contract = result
+		.result
+		.map_err(|_| BenchmarkError::Stop("fixture instantiation failed"))?;
+	Ok(contract.account_id)
+}
+
+/// Make the fixture `contract` call function `func_id` of extension `X` `r` times with `input`.
+///
+/// Returns the status code and the output of the last call; both are zero when `r` is 0.
+pub fn call_fixture<T, X>(
+	caller: AccountIdOf<T>,
+	contract: AccountIdOf<T>,
+	r: u32,
+	func_id: u16,
+	input: impl Encode,
+) -> (u32, Vec<u8>)
+where
+	T: pallet_contracts::Config,
+	X: Extension<T>,
+{
+	let func_id = (X::ID as u32) << 16 | func_id as u32;
+	let data = pallet_contracts::Pallet::<T>::bare_call(
+		caller,
+		contract,
+		0u32.into(),
+		Weight::MAX,
+		None,
+		(r, func_id, input).encode(),
+		false,
+		Determinism::Enforced,
+	)
+	.result
+	.expect("the fixture never traps")
+	.data;
+	let status = u32::decode(&mut &data[..4]).expect("the fixture returns a status code");
+	(status, data[4..].to_vec())
+}
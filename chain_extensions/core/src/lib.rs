@@ -0,0 +1,256 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Building blocks shared by the nativex chain extensions.
+//!
+//! - [`Extension`] is implemented by every extension and carries its extension id, the upper 16
+//!   bits of the `func_id` a contract calls with.
+//! - [`Registry`] composes a tuple of extensions into the runtime's
+//!   `pallet_contracts::Config::ChainExtension`, dispatching on the extension id.
+//! - [`ExtensionError`] is the status code returned to the contract as `RetVal::Converging(code)`.
+//!   Only failures a contract cannot handle, such as running out of gas, trap the call.
+//! - [`read_input`] and [`write_output`] move SCALE-typed values in and out of the contract,
+//!   reporting malformed input and short output buffers as status codes.
+//! - `test_utils`, behind the `test-utils` feature, holds the mock runtime and contract helpers
+//!   the extensions are tested with.
+//! - `benchmarking`, behind the `runtime-benchmarks` feature, deploys and calls the contract the
+//!   extensions are benchmarked with; [`per_call`] turns those benchmarks into the weight charged
+//!   for one call.
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(feature = "test-utils")]
+pub mod test_utils;
+
+use frame_support::{log::trace, weights::Weight};
+use pallet_contracts::chain_extension::{
+	BufInBufOutState, ChainExtension, Environment, Ext, InitState, RetVal, SysConfig,
+};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{DispatchError, RuntimeDebug};
+
+/// Status code returned to the contract by every extension.
+///
+/// The codes are part of the contract ABI: never renumber a variant, only append new ones.
+#[derive(Clone, Copy, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[repr(u32)]
+pub enum ExtensionError {
+	/// No extension is registered under the extension id.
+	UnknownExtension = 1,
+	/// The extension has no function with this id.
+	UnknownFunction = 2,
+	/// The input could not be decoded as the function's argument.
+	DecodingFailed = 3,
+	/// The output buffer is too small to hold the result; nothing was written.
+	OutputBufferTooSmall = 4,
+	/// The dispatched call failed.
+	DispatchFailed = 5,
+	/// The account does not hold enough of the asset.
+	InsufficientBalance = 6,
+	/// The spender is not approved for enough of the owner's asset.
+	InsufficientAllowance = 7,
+	/// The calling contract lacks the role or ownership the function requires.
+	NoPermission = 8,
+	/// The collection or item does not exist.
+	UnknownItem = 9,
+	/// The collection or item already exists.
+	AlreadyExists = 10,
+}
+
+/// Status code of a successful call.
+pub const SUCCESS: u32 = 0;
+
+impl From<ExtensionError> for u32 {
+	fn from(error: ExtensionError) -> u32 {
+		error as u32
+	}
+}
+
+/// Why an extension call did not succeed.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum Failure {
+	/// Report `ExtensionError` to the contract, which can handle it.
+	Status(ExtensionError),
+	/// Trap the contract with `DispatchError`.
+	Trap(DispatchError),
+}
+
+impl From<ExtensionError> for Failure {
+	fn from(error: ExtensionError) -> Self {
+		Failure::Status(error)
+	}
+}
+
+impl From<DispatchError> for Failure {
+	fn from(error: DispatchError) -> Self {
+		Failure::Trap(error)
+	}
+}
+
+impl Failure {
+	/// Report the errors `pallet_contracts` raises for bad contract input as status codes and trap
+	/// on anything else.
+	pub fn from_contracts<T: pallet_contracts::Config>(error: DispatchError) -> Self {
+		if error == pallet_contracts::Error::<T>::DecodingFailed.into() {
+			ExtensionError::DecodingFailed.into()
+		} else if error == pallet_contracts::Error::<T>::OutputBufferTooSmall.into() {
+			ExtensionError::OutputBufferTooSmall.into()
+		} else {
+			Failure::Trap(error)
+		}
+	}
+}
+
+/// Result of an extension call.
+pub type ExtensionResult<R = ()> = Result<R, Failure>;
+
+/// A chain extension registered in a [`Registry`].
+pub trait Extension<T: pallet_contracts::Config>: Default {
+	/// Extension id, matched against the upper 16 bits of the contract's `func_id`.
+	const ID: u16;
+
+	/// Handle function `func_id`, the lower 16 bits of the contract's `func_id`.
+	fn call<E>(&mut self, func_id: u16, env: Environment<E, InitState>) -> ExtensionResult
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>;
+}
+
+/// A tuple of [`Extension`]s with distinct ids.
+pub trait Extensions<T: pallet_contracts::Config>: Default {
+	/// Forward the call to the extension registered under `ext_id`.
+	fn dispatch<E>(
+		&mut self,
+		ext_id: u16,
+		func_id: u16,
+		env: Environment<E, InitState>,
+	) -> ExtensionResult
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>;
+}
+
+macro_rules! impl_extensions_for_tuple {
+	( $( $ext:ident $var:ident ),+ ) => {
+		impl<T: pallet_contracts::Config, $( $ext: Extension<T> ),+> Extensions<T> for ( $( $ext, )+ ) {
+			fn dispatch<E>(
+				&mut self,
+				ext_id: u16,
+				func_id: u16,
+				env: Environment<E, InitState>,
+			) -> ExtensionResult
+			where
+				E: Ext<T = T>,
+				<E::T as SysConfig>::AccountId:
+					UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+			{
+				let ( $( $var, )+ ) = self;
+				$(
+					if ext_id == $ext::ID {
+						return $var.call(func_id, env)
+					}
+				)+
+				Err(ExtensionError::UnknownExtension.into())
+			}
+		}
+	};
+}
+
+impl_extensions_for_tuple!(A a);
+impl_extensions_for_tuple!(A a, B b);
+impl_extensions_for_tuple!(A a, B b, C c);
+impl_extensions_for_tuple!(A a, B b, C c, D d);
+impl_extensions_for_tuple!(A a, B b, C c, D d, F f);
+impl_extensions_for_tuple!(A a, B b, C c, D d, F f, G g);
+impl_extensions_for_tuple!(A a, B b, C c, D d, F f, G g, H h);
+impl_extensions_for_tuple!(A a, B b, C c, D d, F f, G g, H h, I i);
+
+/// The runtime's chain extension: dispatches to the extension in `X` whose id matches the upper
+/// 16 bits of `func_id` and returns its status code as `RetVal::Converging`.
+#[derive(Default)]
+pub struct Registry<X>(X);
+
+impl<T, X> ChainExtension<T> for Registry<X>
+where
+	T: pallet_contracts::Config,
+	X: Extensions<T>,
+	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let (ext_id, func_id) = (env.ext_id(), env.func_id());
+		match self.0.dispatch(ext_id, func_id, env) {
+			Ok(()) => Ok(RetVal::Converging(SUCCESS)),
+			Err(Failure::Status(error)) => {
+				trace!(
+					target: "runtime",
+					"[ChainExtension]|call|ext_id:{:}|func_id:{:}|status:{:?}",
+					ext_id,
+					func_id,
+					error
+				);
+				Ok(RetVal::Converging(error.into()))
+			},
+			Err(Failure::Trap(error)) => Err(error),
+		}
+	}
+
+	fn enabled() -> bool {
+		true
+	}
+}
+
+/// The cost of one call of a function benchmarked as a contract calling it `r` times: the cost
+/// with one call less the cost of the same contract with none.
+pub fn per_call(weight: fn(u32) -> Weight) -> Weight {
+	weight(1).saturating_sub(weight(0))
+}
+
+/// Decode the contract's input as `I`.
+pub fn read_input<E, I>(env: &mut Environment<E, BufInBufOutState>) -> ExtensionResult<I>
+where
+	E: Ext,
+	I: Decode + MaxEncodedLen,
+	<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	env.read_as().map_err(Failure::from_contracts::<E::T>)
+}
+
+/// Write the SCALE encoding of `output` to the contract's output buffer.
+pub fn write_output<E, O>(env: &mut Environment<E, BufInBufOutState>, output: &O) -> ExtensionResult
+where
+	E: Ext,
+	O: Encode,
+	<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+{
+	env.write(&output.encode(), false, None).map_err(Failure::from_contracts::<E::T>)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn status_codes_are_stable() {
+		assert_eq!(u32::from(ExtensionError::UnknownExtension), 1);
+		assert_eq!(u32::from(ExtensionError::UnknownFunction), 2);
+		assert_eq!(u32::from(ExtensionError::DecodingFailed), 3);
+		assert_eq!(u32::from(ExtensionError::OutputBufferTooSmall), 4);
+		assert_eq!(u32::from(ExtensionError::DispatchFailed), 5);
+		assert_eq!(u32::from(ExtensionError::InsufficientBalance), 6);
+		assert_eq!(u32::from(ExtensionError::InsufficientAllowance), 7);
+		assert_eq!(u32::from(ExtensionError::NoPermission), 8);
+		assert_eq!(u32::from(ExtensionError::UnknownItem), 9);
+		assert_eq!(u32::from(ExtensionError::AlreadyExists), 10);
+	}
+
+	#[test]
+	fn benchmark_fixture_is_compiled_from_its_source() {
+		let wasm = wat::parse_str(include_str!("../fixtures/call_repeatedly.wat")).unwrap();
+		assert_eq!(wasm, include_bytes!("../fixtures/call_repeatedly.wasm"));
+	}
+}
@@ -0,0 +1,201 @@
+//! A `pallet_contracts` mock runtime and helpers to test extensions through a real contract.
+//!
+//! [`impl_mock_runtime!`](crate::impl_mock_runtime) declares the runtime; the extension's tests
+//! deploy the proxy contract with [`deploy_proxy`] and call the extension through it with
+//! [`call_extension`].
+
+use crate::{Extension, SUCCESS};
+use frame_support::{traits::Currency, weights::Weight};
+use pallet_contracts::{chain_extension::SysConfig, Determinism};
+use pallet_contracts_primitives::Code;
+use parity_scale_codec::{Decode, Encode};
+use sp_core::H256;
+use sp_runtime::AccountId32;
+
+pub const ALICE: AccountId32 = AccountId32::new([1u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([2u8; 32]);
+
+/// Gas limit of the contract calls made by the helpers.
+pub const GAS_LIMIT: Weight = Weight::from_parts(100_000_000_000, 3 * 1024 * 1024);
+
+type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+	<T as SysConfig>::AccountId,
+>>::Balance;
+
+/// Declare `Test`, a runtime with `System`, `Balances`, `Timestamp`, `RandomnessCollectiveFlip`,
+/// `Contracts` and the given pallets, whose chain extension is `extension`.
+///
+/// Accounts are `AccountId32` and balances `u64`; weight is free and every deposit costs 1. The
+/// calling crate implements the `Config` of the given pallets, and depends on `frame-system`,
+/// `pallet-balances`, `pallet-timestamp`, `pallet-insecure-randomness-collective-flip` and
+/// `pallet-contracts`.
+#[macro_export]
+macro_rules! impl_mock_runtime {
+	(extension = $extension:ty, pallets { $( $name:ident: $pallet:ident ),* $(,)? }) => {
+		type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+		type Block = frame_system::mocking::MockBlock<Test>;
+
+		frame_support::construct_runtime!(
+			pub enum Test where
+				Block = Block,
+				NodeBlock = Block,
+				UncheckedExtrinsic = UncheckedExtrinsic,
+			{
+				System: frame_system,
+				Balances: pallet_balances,
+				Timestamp: pallet_timestamp,
+				RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
+				$( $name: $pallet, )*
+				Contracts: pallet_contracts,
+			}
+		);
+
+		impl frame_system::Config for Test {
+			type BaseCallFilter = frame_support::traits::Everything;
+			type BlockWeights = ();
+			type BlockLength = ();
+			type DbWeight = ();
+			type RuntimeOrigin = RuntimeOrigin;
+			type RuntimeCall = RuntimeCall;
+			type Index = u64;
+			type BlockNumber = u64;
+			type Hash = sp_core::H256;
+			type Hashing = sp_runtime::traits::BlakeTwo256;
+			type AccountId = sp_runtime::AccountId32;
+			type Lookup = sp_runtime::traits::IdentityLookup<Self::AccountId>;
+			type Header = sp_runtime::testing::Header;
+			type RuntimeEvent = RuntimeEvent;
+			type BlockHashCount = frame_support::traits::ConstU64<250>;
+			type Version = ();
+			type PalletInfo = PalletInfo;
+			type AccountData = pallet_balances::AccountData<u64>;
+			type OnNewAccount = ();
+			type OnKilledAccount = ();
+			type SystemWeightInfo = ();
+			type SS58Prefix = frame_support::traits::ConstU16<42>;
+			type OnSetCode = ();
+			type MaxConsumers = frame_support::traits::ConstU32<16>;
+		}
+
+		impl pallet_balances::Config for Test {
+			type MaxLocks = ();
+			type MaxReserves = ();
+			type ReserveIdentifier = [u8; 8];
+			type Balance = u64;
+			type RuntimeEvent = RuntimeEvent;
+			type DustRemoval = ();
+			type ExistentialDeposit = frame_support::traits::ConstU64<1>;
+			type AccountStore = System;
+			type WeightInfo = ();
+			type FreezeIdentifier = ();
+			type MaxFreezes = ();
+			type HoldIdentifier = ();
+			type MaxHolds = ();
+		}
+
+		impl pallet_timestamp::Config for Test {
+			type Moment = u64;
+			type OnTimestampSet = ();
+			type MinimumPeriod = frame_support::traits::ConstU64<1>;
+			type WeightInfo = ();
+		}
+
+		impl pallet_insecure_randomness_collective_flip::Config for Test {}
+
+		frame_support::parameter_types! {
+			pub MySchedule: pallet_contracts::Schedule<Test> = Default::default();
+		}
+
+		/// Weight is free in the mock; deposits are what contracts pay for.
+		pub struct WeightPrice;
+		impl sp_runtime::traits::Convert<frame_support::weights::Weight, u64> for WeightPrice {
+			fn convert(_: frame_support::weights::Weight) -> u64 {
+				0
+			}
+		}
+
+		impl pallet_contracts::Config for Test {
+			type Time = Timestamp;
+			type Randomness = RandomnessCollectiveFlip;
+			type Currency = Balances;
+			type RuntimeEvent = RuntimeEvent;
+			type RuntimeCall = RuntimeCall;
+			type CallFilter = frame_support::traits::Nothing;
+			type DepositPerItem = frame_support::traits::ConstU64<1>;
+			type DepositPerByte = frame_support::traits::ConstU64<1>;
+			type DefaultDepositLimit = frame_support::traits::ConstU64<{ 1024 * 1024 }>;
+			type CallStack = [pallet_contracts::Frame<Self>; 5];
+			type WeightPrice = WeightPrice;
+			type WeightInfo = ();
+			type ChainExtension = $extension;
+			type Schedule = MySchedule;
+			type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
+			type MaxCodeLen = frame_support::traits::ConstU32<{ 123 * 1024 }>;
+			type MaxStorageKeyLen = frame_support::traits::ConstU32<128>;
+			type UnsafeUnstableInterface = frame_support::traits::ConstBool<false>;
+			type MaxDebugBufferLen = frame_support::traits::ConstU32<{ 2 * 1024 * 1024 }>;
+		}
+	};
+}
+
+/// Deploy the proxy contract (`fixtures/proxy.wat`) from [`ALICE`], endowed with `value`.
+///
+/// The proxy forwards its input to the chain extension: a `u32` `func_id`, then the extension's
+/// input. It returns the status code as a `u32`, then the extension's output.
+pub fn deploy_proxy<T>(value: BalanceOf<T>) -> AccountId32
+where
+	T: pallet_contracts::Config + SysConfig<AccountId = AccountId32, Hash = H256>,
+{
+	let wasm = wat::parse_str(include_str!("../fixtures/proxy.wat")).unwrap();
+	pallet_contracts::Pallet::<T>::bare_instantiate(
+		ALICE,
+		value,
+		GAS_LIMIT,
+		None,
+		Code::Upload(wasm),
+		vec![],
+		vec![],
+		false,
+	)
+	.result
+	.unwrap()
+	.account_id
+}
+
+/// Call the raw `func_id` through the proxy `contract` as [`ALICE`]; returns the status code and
+/// the output.
+pub fn call_extension<T>(contract: &AccountId32, func_id: u32, input: impl Encode) -> (u32, Vec<u8>)
+where
+	T: pallet_contracts::Config + SysConfig<AccountId = AccountId32, Hash = H256>,
+{
+	let data = pallet_contracts::Pallet::<T>::bare_call(
+		ALICE,
+		contract.clone(),
+		0u32.into(),
+		GAS_LIMIT,
+		None,
+		(func_id, input).encode(),
+		false,
+		Determinism::Enforced,
+	)
+	.result
+	.unwrap()
+	.data;
+	let status = u32::decode(&mut &data[..4]).unwrap();
+	(status, data[4..].to_vec())
+}
+
+/// Call function `func_id` of extension `X` through the proxy `contract`.
+pub fn call<T, X>(contract: &AccountId32, func_id: u16, input: impl Encode) -> (u32, Vec<u8>)
+where
+	T: pallet_contracts::Config + SysConfig<AccountId = AccountId32, Hash = H256>,
+	X: Extension<T>,
+{
+	call_extension::<T>(contract, (X::ID as u32) << 16 | func_id as u32, input)
+}
+
+/// Decode the output of a successful call.
+pub fn decode<D: Decode>((status, output): (u32, Vec<u8>)) -> D {
+	assert_eq!(status, SUCCESS);
+	D::decode(&mut &output[..]).unwrap()
+}
@@ -0,0 +1,197 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! ink! definitions for contracts deployed on nativex.
+//!
+//! Use [`NativexEnvironment`] as the contract's environment to reach the runtime's chain
+//! extensions through `self.env().extension()`:
+//!
+//! ```ignore
+//! #[ink::contract(env = nativex_ink_env::NativexEnvironment)]
+//! mod my_contract {
+//!     // ...
+//!     #[ink(message)]
+//!     pub fn roll(&self) -> Result<[u8; 32], nativex_ink_env::NativexError> {
+//!         self.env().extension().fetch_random([0; 32])
+//!     }
+//! }
+//! ```
+
+use ink::{
+	env::{chain_extension::FromStatusCode, DefaultEnvironment, Environment},
+	prelude::vec::Vec,
+	primitives::AccountId,
+};
+
+/// Identifier of an asset in `pallet_assets`.
+pub type AssetId = u32;
+/// Identifier of a collection in `pallet_nfts`.
+pub type CollectionId = u32;
+/// Identifier of an item within a collection in `pallet_nfts`.
+pub type ItemId = u32;
+
+/// Status codes returned by the runtime's chain extensions.
+///
+/// Mirrors `nativex_chain_extensions::ExtensionError`; the codes never change meaning.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NativexError {
+	/// No extension is registered under the extension id.
+	UnknownExtension,
+	/// The extension has no function with this id.
+	UnknownFunction,
+	/// The input could not be decoded as the function's argument.
+	DecodingFailed,
+	/// The output buffer is too small to hold the result.
+	OutputBufferTooSmall,
+	/// The dispatched call failed.
+	DispatchFailed,
+	/// The account does not hold enough of the asset.
+	InsufficientBalance,
+	/// The spender is not approved for enough of the owner's asset.
+	InsufficientAllowance,
+	/// The calling contract lacks the role or ownership the function requires.
+	NoPermission,
+	/// The collection or item does not exist.
+	UnknownItem,
+	/// The collection or item already exists.
+	AlreadyExists,
+	/// A status code this version of the crate does not know.
+	Unknown(u32),
+}
+
+impl FromStatusCode for NativexError {
+	fn from_status_code(status_code: u32) -> Result<(), Self> {
+		match status_code {
+			0 => Ok(()),
+			1 => Err(Self::UnknownExtension),
+			2 => Err(Self::UnknownFunction),
+			3 => Err(Self::DecodingFailed),
+			4 => Err(Self::OutputBufferTooSmall),
+			5 => Err(Self::DispatchFailed),
+			6 => Err(Self::InsufficientBalance),
+			7 => Err(Self::InsufficientAllowance),
+			8 => Err(Self::NoPermission),
+			9 => Err(Self::UnknownItem),
+			10 => Err(Self::AlreadyExists),
+			code => Err(Self::Unknown(code)),
+		}
+	}
+}
+
+impl From<scale::Error> for NativexError {
+	fn from(_: scale::Error) -> Self {
+		Self::DecodingFailed
+	}
+}
+
+/// The chain extensions of the nativex runtime.
+///
+/// `extension` is the full `func_id`: the extension id in the upper 16 bits and the function id
+/// in the lower 16 bits. Extension 0 is randomness, extension 1 is `pallet_assets` and extension
+/// 2 is `pallet_nfts`.
+#[ink::chain_extension]
+pub trait NativexExtension {
+	type ErrorCode = NativexError;
+
+	/// Random seed mixed from `subject` and the recent block hashes. Not secure randomness.
+	#[ink(extension = 1101)]
+	fn fetch_random(subject: [u8; 32]) -> [u8; 32];
+
+	/// Total supply of `asset_id` in `pallet_assets`.
+	#[ink(extension = 65537)]
+	fn total_supply(asset_id: AssetId) -> u128;
+
+	/// Balance of `owner` in `asset_id`.
+	#[ink(extension = 65538)]
+	fn balance_of(asset_id: AssetId, owner: AccountId) -> u128;
+
+	/// Amount of `owner`'s `asset_id` that `spender` may transfer.
+	#[ink(extension = 65539)]
+	fn allowance(asset_id: AssetId, owner: AccountId, spender: AccountId) -> u128;
+
+	/// Transfer `amount` of `asset_id` from the calling contract to `to`.
+	#[ink(extension = 65540)]
+	fn transfer(asset_id: AssetId, to: AccountId, amount: u128);
+
+	/// Transfer `amount` of `asset_id` from `from` to `to`, spending the calling contract's
+	/// allowance.
+	#[ink(extension = 65541)]
+	fn transfer_from(asset_id: AssetId, from: AccountId, to: AccountId, amount: u128);
+
+	/// Set the allowance of `spender` over the calling contract's `asset_id` to `amount`.
+	#[ink(extension = 65542)]
+	fn approve(asset_id: AssetId, spender: AccountId, amount: u128);
+
+	/// Name of `asset_id`.
+	#[ink(extension = 65543)]
+	fn name(asset_id: AssetId) -> Vec<u8>;
+
+	/// Symbol of `asset_id`.
+	#[ink(extension = 65544)]
+	fn symbol(asset_id: AssetId) -> Vec<u8>;
+
+	/// Decimals of `asset_id`.
+	#[ink(extension = 65545)]
+	fn decimals(asset_id: AssetId) -> u8;
+
+	/// Owner of `collection` in `pallet_nfts`.
+	#[ink(extension = 131073)]
+	fn nft_collection_owner(collection: CollectionId) -> Option<AccountId>;
+
+	/// Owner of `item` in `collection`.
+	#[ink(extension = 131074)]
+	fn nft_owner_of(collection: CollectionId, item: ItemId) -> Option<AccountId>;
+
+	/// Attribute `key` set by the collection owner on `item`, or on `collection` if `item` is
+	/// `None`.
+	#[ink(extension = 131075)]
+	fn nft_attribute(collection: CollectionId, item: Option<ItemId>, key: Vec<u8>)
+		-> Option<Vec<u8>>;
+
+	/// Create a collection owned and administered by the calling contract.
+	#[ink(extension = 131076)]
+	fn nft_create_collection() -> CollectionId;
+
+	/// Mint `item` of `collection` to `to`; the calling contract must be the collection's issuer.
+	#[ink(extension = 131077)]
+	fn nft_mint(collection: CollectionId, item: ItemId, to: AccountId);
+
+	/// Transfer `item` of `collection` to `to`; the calling contract must own it or be approved.
+	#[ink(extension = 131078)]
+	fn nft_transfer(collection: CollectionId, item: ItemId, to: AccountId);
+
+	/// Approve or revoke `delegate` for transferring `item`, owned by the calling contract.
+	#[ink(extension = 131079)]
+	fn nft_approve(collection: CollectionId, item: ItemId, delegate: AccountId, approved: bool);
+
+	/// Burn `item` of `collection`, owned by the calling contract.
+	#[ink(extension = 131080)]
+	fn nft_burn(collection: CollectionId, item: ItemId);
+
+	/// Set attribute `key` of `item`, or of `collection` if `item` is `None`; the calling
+	/// contract must own the collection.
+	#[ink(extension = 131081)]
+	fn nft_set_attribute(
+		collection: CollectionId,
+		item: Option<ItemId>,
+		key: Vec<u8>,
+		value: Vec<u8>,
+	);
+}
+
+/// The ink! environment of the nativex runtime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum NativexEnvironment {}
+
+impl Environment for NativexEnvironment {
+	const MAX_EVENT_TOPICS: usize = <DefaultEnvironment as Environment>::MAX_EVENT_TOPICS;
+
+	type AccountId = <DefaultEnvironment as Environment>::AccountId;
+	type Balance = u128;
+	type Hash = <DefaultEnvironment as Environment>::Hash;
+	type Timestamp = u64;
+	type BlockNumber = u32;
+
+	type ChainExtension = NativexExtension;
+}
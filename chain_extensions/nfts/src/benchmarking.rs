@@ -0,0 +1,193 @@
+//! Benchmarks for the functions of `NftsExtension`.
+//!
+//! Chain extensions are not pallets, so the benchmarks hang off the stand-in [`Pallet`] below,
+//! which the runtime registers as `[nativex_nfts_extension, NftsExtensionBench::<Runtime>]`.
+//!
+//! Each benchmark calls a contract that calls the function `r` times, see
+//! [`nativex_chain_extensions::benchmarking`]. The runtime's chain extension must route extension
+//! id 2 to `NftsExtension<I>`. `mint`, `transfer` and `burn` can't be repeated with the same input,
+//! so their `r` is 0 or 1. The collection and items are set up through the extension too, so
+//! `CreateOrigin` must let contracts create collections.
+
+use crate::{func, NftsExtension};
+use frame_benchmarking::v2::*;
+use frame_support::traits::{Currency, Get};
+use nativex_chain_extensions::{
+	benchmarking::{call_fixture, deploy_fixture, MAX_CALLS},
+	SUCCESS,
+};
+use pallet_nfts::BenchmarkHelper;
+use parity_scale_codec::{Decode, Encode};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+type CollectionIdOf<T, I> = <T as pallet_nfts::Config<I>>::CollectionId;
+type ItemIdOf<T, I> = <T as pallet_nfts::Config<I>>::ItemId;
+
+pub struct Pallet<T: Config<I>, I: 'static = ()>(frame_system::Pallet<T>, PhantomData<I>);
+pub trait Config<I: 'static = ()>: pallet_nfts::Config<I> + pallet_contracts::Config {}
+
+/// Make the fixture call function `func_id` `r` times with `input`, check it succeeded and return
+/// the output of the last call.
+fn call<T: Config<I>, I: 'static>(
+	caller: &T::AccountId,
+	contract: &T::AccountId,
+	r: u32,
+	func_id: u16,
+	input: impl Encode,
+) -> Vec<u8> {
+	let (status, output) =
+		call_fixture::<T, NftsExtension<I>>(caller.clone(), contract.clone(), r, func_id, input);
+	assert_eq!(status, SUCCESS);
+	output
+}
+
+/// The caller, the deployed fixture and a collection the fixture created, with the native
+/// balance to pay deposits.
+fn setup<T: Config<I>, I: 'static>(
+) -> Result<(T::AccountId, T::AccountId, CollectionIdOf<T, I>), BenchmarkError> {
+	let caller: T::AccountId = whitelisted_caller();
+	let contract = deploy_fixture::<T>(&caller)?;
+	let native = <T as pallet_nfts::Config<I>>::Currency::minimum_balance() * 1_000_000u32.into();
+	let _ = <T as pallet_nfts::Config<I>>::Currency::deposit_creating(&contract, native);
+
+	let output = call::<T, I>(&caller, &contract, 1, func::CREATE_COLLECTION, ());
+	let collection = CollectionIdOf::<T, I>::decode(&mut &output[..])
+		.map_err(|_| BenchmarkError::Stop("undecodable collection id"))?;
+	Ok((caller, contract, collection))
+}
+
+/// [`setup`], plus an item of the collection owned by the fixture.
+fn setup_item<T: Config<I>, I: 'static>(
+) -> Result<(T::AccountId, T::AccountId, CollectionIdOf<T, I>, ItemIdOf<T, I>), BenchmarkError> {
+	let (caller, contract, collection) = setup::<T, I>()?;
+	let item = T::Helper::item(0);
+	call::<T, I>(&caller, &contract, 1, func::MINT, (collection, item, contract.clone()));
+	Ok((caller, contract, collection, item))
+}
+
+/// A key and a value of the maximum length.
+fn attribute<T: Config<I>, I: 'static>() -> (Vec<u8>, Vec<u8>) {
+	(vec![0; T::KeyLimit::get() as usize], vec![0; T::ValueLimit::get() as usize])
+}
+
+#[instance_benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn collection_owner(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection) = setup::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::COLLECTION_OWNER, collection);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn owner_of(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::OWNER_OF, (collection, item));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn attribute(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+		let (key, value) = super::attribute::<T, I>();
+		let input = (collection, Some(item), &key, value);
+		call::<T, I>(&caller, &contract, 1, func::SET_ATTRIBUTE, input);
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::ATTRIBUTE, (collection, Some(item), key));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_collection(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, _) = setup::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::CREATE_COLLECTION, ());
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn mint(r: Linear<0, 1>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection) = setup::<T, I>()?;
+		let recipient: T::AccountId = account("recipient", 0, 0);
+		let input = (collection, T::Helper::item(0), recipient);
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::MINT, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn transfer(r: Linear<0, 1>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+		let recipient: T::AccountId = account("recipient", 0, 0);
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::TRANSFER, (collection, item, recipient));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn approve(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+		let input = (collection, item, caller.clone(), true);
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::APPROVE, input);
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn burn(r: Linear<0, 1>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::BURN, (collection, item));
+		}
+
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_attribute(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		let (caller, contract, collection, item) = setup_item::<T, I>()?;
+		let (key, value) = super::attribute::<T, I>();
+		let input = (collection, Some(item), key, value);
+
+		#[block]
+		{
+			call::<T, I>(&caller, &contract, r, func::SET_ATTRIBUTE, input);
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,263 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! Chain extension giving ink! contracts access to `pallet_nfts`.
+//!
+//! The functions follow PSP34 on top of native collections. Calls act as the calling contract:
+//! a collection it creates is owned and administered by it, so it can mint, set attributes and
+//! manage items the way an account would through the pallet's extrinsics.
+
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::{
+	log::trace, pallet_prelude::OptionQuery, storage::with_storage_layer,
+	traits::tokens::nonfungibles_v2::Inspect, BoundedVec,
+};
+use frame_system::RawOrigin;
+use nativex_chain_extensions::{
+	per_call, read_input, write_output, Extension, ExtensionError, ExtensionResult, Failure,
+};
+use pallet_contracts::chain_extension::{Environment, Ext, InitState, SysConfig};
+use pallet_nfts::{AttributeNamespace, CollectionConfig, CollectionSettings, Incrementable};
+use sp_core::crypto::UncheckedFrom;
+use sp_runtime::{traits::StaticLookup, DispatchError};
+use sp_std::marker::PhantomData;
+
+/// `func_id`s of the functions, the lower 16 bits of the contract's `func_id`.
+pub mod func {
+	pub const COLLECTION_OWNER: u16 = 1;
+	pub const OWNER_OF: u16 = 2;
+	pub const ATTRIBUTE: u16 = 3;
+	pub const CREATE_COLLECTION: u16 = 4;
+	pub const MINT: u16 = 5;
+	pub const TRANSFER: u16 = 6;
+	pub const APPROVE: u16 = 7;
+	pub const BURN: u16 = 8;
+	pub const SET_ATTRIBUTE: u16 = 9;
+}
+
+type CollectionIdOf<T, I> = <T as pallet_nfts::Config<I>>::CollectionId;
+type ItemIdOf<T, I> = <T as pallet_nfts::Config<I>>::ItemId;
+type KeyOf<T, I> = BoundedVec<u8, <T as pallet_nfts::Config<I>>::KeyLimit>;
+type ValueOf<T, I> = BoundedVec<u8, <T as pallet_nfts::Config<I>>::ValueLimit>;
+type AccountIdOf<T> = <T as SysConfig>::AccountId;
+
+/// `pallet_nfts::NextCollectionId`, the id `create` gives the next collection.
+#[frame_support::storage_alias]
+type NextCollectionId<T: pallet_nfts::Config<I>, I: 'static> =
+	StorageValue<pallet_nfts::Pallet<T, I>, CollectionIdOf<T, I>, OptionQuery>;
+
+/// Contract extension for `pallet_nfts` instance `I`, charging weights `W`.
+pub struct NftsExtension<I = (), W = ()>(PhantomData<(I, W)>);
+
+impl<I, W> Default for NftsExtension<I, W> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
+
+/// Report the errors a contract can handle as status codes; any other dispatch error becomes
+/// `DispatchFailed`.
+fn dispatch_failure<T: pallet_nfts::Config<I>, I: 'static>(error: DispatchError) -> Failure {
+	let status = if error == pallet_nfts::Error::<T, I>::NoPermission.into() ||
+		error == DispatchError::BadOrigin
+	{
+		ExtensionError::NoPermission
+	} else if error == pallet_nfts::Error::<T, I>::UnknownCollection.into() ||
+		error == pallet_nfts::Error::<T, I>::UnknownItem.into()
+	{
+		ExtensionError::UnknownItem
+	} else if error == pallet_nfts::Error::<T, I>::AlreadyExists.into() {
+		ExtensionError::AlreadyExists
+	} else {
+		ExtensionError::DispatchFailed
+	};
+	status.into()
+}
+
+impl<T, I, W> Extension<T> for NftsExtension<I, W>
+where
+	T: pallet_contracts::Config + pallet_nfts::Config<I>,
+	I: 'static,
+	W: WeightInfo,
+{
+	const ID: u16 = 2;
+
+	fn call<E>(&mut self, func_id: u16, env: Environment<E, InitState>) -> ExtensionResult
+	where
+		E: Ext<T = T>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let mut env = env.buf_in_buf_out();
+		let contract = env.ext().address().clone();
+		let origin = || RawOrigin::Signed(contract.clone()).into();
+		trace!(
+			target: "runtime",
+			"[ChainExtension]|call|ext_id:{:}|func_id:{:}",
+			<Self as Extension<T>>::ID,
+			func_id
+		);
+
+		match func_id {
+			func::COLLECTION_OWNER => {
+				env.charge_weight(per_call(W::collection_owner))?;
+				let collection: CollectionIdOf<T, I> = read_input(&mut env)?;
+				let owner =
+					<pallet_nfts::Pallet<T, I> as Inspect<_>>::collection_owner(&collection);
+				write_output(&mut env, &owner)
+			},
+
+			func::OWNER_OF => {
+				env.charge_weight(per_call(W::owner_of))?;
+				let (collection, item): (CollectionIdOf<T, I>, ItemIdOf<T, I>) =
+					read_input(&mut env)?;
+				let owner = <pallet_nfts::Pallet<T, I> as Inspect<_>>::owner(&collection, &item);
+				write_output(&mut env, &owner)
+			},
+
+			func::ATTRIBUTE => {
+				env.charge_weight(per_call(W::attribute))?;
+				let (collection, item, key): (
+					CollectionIdOf<T, I>,
+					Option<ItemIdOf<T, I>>,
+					KeyOf<T, I>,
+				) = read_input(&mut env)?;
+				let value = match item {
+					Some(item) => <pallet_nfts::Pallet<T, I> as Inspect<_>>::attribute(
+						&collection,
+						&item,
+						&key,
+					),
+					None => <pallet_nfts::Pallet<T, I> as Inspect<_>>::collection_attribute(
+						&collection,
+						&key,
+					),
+				};
+				write_output(&mut env, &value)
+			},
+
+			func::CREATE_COLLECTION => {
+				env.charge_weight(per_call(W::create_collection))?;
+				let config = CollectionConfig {
+					settings: CollectionSettings::all_enabled(),
+					max_supply: None,
+					mint_settings: Default::default(),
+				};
+				// Through `create`, so `CreateOrigin` applies and the deposit is reserved.
+				let collection = with_storage_layer(|| {
+					let collection =
+						NextCollectionId::<T, I>::get()
+							.unwrap_or(CollectionIdOf::<T, I>::initial_value());
+					pallet_nfts::Pallet::<T, I>::create(
+						origin(),
+						T::Lookup::unlookup(contract.clone()),
+						config,
+					)?;
+					Ok(collection)
+				})
+				.map_err(dispatch_failure::<T, I>)?;
+				write_output(&mut env, &collection)
+			},
+
+			func::MINT => {
+				env.charge_weight(per_call(W::mint))?;
+				let (collection, item, to): (CollectionIdOf<T, I>, ItemIdOf<T, I>, AccountIdOf<T>) =
+					read_input(&mut env)?;
+				with_storage_layer(|| {
+					pallet_nfts::Pallet::<T, I>::mint(
+						origin(),
+						collection,
+						item,
+						T::Lookup::unlookup(to),
+						None,
+					)
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::TRANSFER => {
+				env.charge_weight(per_call(W::transfer))?;
+				let (collection, item, to): (CollectionIdOf<T, I>, ItemIdOf<T, I>, AccountIdOf<T>) =
+					read_input(&mut env)?;
+				with_storage_layer(|| {
+					pallet_nfts::Pallet::<T, I>::transfer(
+						origin(),
+						collection,
+						item,
+						T::Lookup::unlookup(to),
+					)
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::APPROVE => {
+				env.charge_weight(per_call(W::approve))?;
+				let (collection, item, delegate, approved): (
+					CollectionIdOf<T, I>,
+					ItemIdOf<T, I>,
+					AccountIdOf<T>,
+					bool,
+				) = read_input(&mut env)?;
+				with_storage_layer(|| {
+					let delegate = T::Lookup::unlookup(delegate);
+					if approved {
+						pallet_nfts::Pallet::<T, I>::approve_transfer(
+							origin(),
+							collection,
+							item,
+							delegate,
+							None,
+						)
+					} else {
+						pallet_nfts::Pallet::<T, I>::cancel_approval(
+							origin(),
+							collection,
+							item,
+							delegate,
+						)
+					}
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::BURN => {
+				env.charge_weight(per_call(W::burn))?;
+				let (collection, item): (CollectionIdOf<T, I>, ItemIdOf<T, I>) =
+					read_input(&mut env)?;
+				with_storage_layer(|| pallet_nfts::Pallet::<T, I>::burn(origin(), collection, item))
+					.map_err(dispatch_failure::<T, I>)
+			},
+
+			func::SET_ATTRIBUTE => {
+				env.charge_weight(per_call(W::set_attribute))?;
+				let (collection, item, key, value): (
+					CollectionIdOf<T, I>,
+					Option<ItemIdOf<T, I>>,
+					KeyOf<T, I>,
+					ValueOf<T, I>,
+				) = read_input(&mut env)?;
+				with_storage_layer(|| {
+					pallet_nfts::Pallet::<T, I>::set_attribute(
+						origin(),
+						collection,
+						item,
+						AttributeNamespace::CollectionOwner,
+						key,
+						value,
+					)
+				})
+				.map_err(dispatch_failure::<T, I>)
+			},
+
+			_ => Err(ExtensionError::UnknownFunction.into()),
+		}
+	}
+}
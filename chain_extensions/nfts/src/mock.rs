@@ -0,0 +1,82 @@
+use crate::NftsExtension;
+use frame_support::{
+	parameter_types,
+	traits::{AsEnsureOriginWithArg, ConstU32, ConstU64, EnsureOriginWithArg, GenesisBuild},
+};
+use frame_system::{EnsureRoot, EnsureSigned};
+use nativex_chain_extensions::Registry;
+pub use nativex_chain_extensions::test_utils::{ALICE, BOB};
+use pallet_nfts::PalletFeatures;
+use sp_runtime::{AccountId32, MultiSignature, MultiSigner};
+
+// Configure a mock runtime with contracts and NFTs to test the extension.
+nativex_chain_extensions::impl_mock_runtime!(
+	extension = Registry<(NftsExtension,)>,
+	pallets { Nfts: pallet_nfts }
+);
+
+parameter_types! {
+	pub Features: PalletFeatures = PalletFeatures::all_enabled();
+	/// Whether `CreateOrigin` lets signed origins create collections.
+	pub static CreationAllowed: bool = true;
+}
+
+/// Signed origins, while [`CreationAllowed`] is set.
+pub struct CreateOrigin;
+impl EnsureOriginWithArg<RuntimeOrigin, u32> for CreateOrigin {
+	type Success = AccountId32;
+
+	fn try_origin(o: RuntimeOrigin, collection: &u32) -> Result<AccountId32, RuntimeOrigin> {
+		if !CreationAllowed::get() {
+			return Err(o)
+		}
+		AsEnsureOriginWithArg::<EnsureSigned<AccountId32>>::try_origin(o, collection)
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin(_: &u32) -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::signed(ALICE))
+	}
+}
+
+impl pallet_nfts::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type CollectionId = u32;
+	type ItemId = u32;
+	type Currency = Balances;
+	type CreateOrigin = CreateOrigin;
+	type ForceOrigin = EnsureRoot<AccountId32>;
+	type Locker = ();
+	type CollectionDeposit = ConstU64<2>;
+	type ItemDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type AttributeDepositBase = ConstU64<1>;
+	type DepositPerByte = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type KeyLimit = ConstU32<50>;
+	type ValueLimit = ConstU32<50>;
+	type ApprovalsLimit = ConstU32<10>;
+	type ItemAttributesApprovalsLimit = ConstU32<2>;
+	type MaxTips = ConstU32<10>;
+	type MaxDeadlineDuration = ConstU64<10000>;
+	type MaxAttributesPerCall = ConstU32<2>;
+	type Features = Features;
+	type OffchainSignature = MultiSignature;
+	type OffchainPublic = MultiSigner;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type Helper = ();
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000_000_000), (BOB, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
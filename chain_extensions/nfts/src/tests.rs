@@ -0,0 +1,191 @@
+use crate::{func, mock::*, NftsExtension};
+use frame_support::{
+	assert_ok,
+	traits::{tokens::nonfungibles_v2::Inspect, ReservableCurrency},
+};
+use nativex_chain_extensions::{
+	test_utils::{self, decode, deploy_proxy},
+	ExtensionError, SUCCESS,
+};
+use parity_scale_codec::Encode;
+use sp_runtime::AccountId32;
+
+/// Deploy the proxy contract, which forwards its input to the chain extension, and fund it.
+fn deploy() -> AccountId32 {
+	deploy_proxy::<Test>(1_000)
+}
+
+/// Call function `func_id` of the NFTs extension through `contract`; returns the status code
+/// and the output.
+fn call(contract: &AccountId32, func_id: u16, input: impl Encode) -> (u32, Vec<u8>) {
+	test_utils::call::<Test, NftsExtension>(contract, func_id, input)
+}
+
+fn owner(collection: u32, item: u32) -> Option<AccountId32> {
+	<Nfts as Inspect<_>>::owner(&collection, &item)
+}
+
+/// Deploy the proxy and let it create a collection; returns both.
+fn deploy_with_collection() -> (AccountId32, u32) {
+	let contract = deploy();
+	let collection = decode::<u32>(call(&contract, func::CREATE_COLLECTION, ()));
+	(contract, collection)
+}
+
+#[test]
+fn contract_owns_the_collection_it_creates() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+
+		assert_eq!(<Nfts as Inspect<_>>::collection_owner(&collection), Some(contract.clone()));
+		assert_eq!(
+			decode::<Option<AccountId32>>(call(&contract, func::COLLECTION_OWNER, collection)),
+			Some(contract)
+		);
+	});
+}
+
+#[test]
+fn creating_a_collection_follows_create_origin_and_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		let reserved = Balances::reserved_balance(&contract);
+		CreationAllowed::set(false);
+		assert_eq!(
+			call(&contract, func::CREATE_COLLECTION, ()).0,
+			ExtensionError::NoPermission as u32
+		);
+		assert_eq!(Balances::reserved_balance(&contract), reserved);
+
+		CreationAllowed::set(true);
+		assert_eq!(decode::<u32>(call(&contract, func::CREATE_COLLECTION, ())), 0);
+		assert_eq!(decode::<u32>(call(&contract, func::CREATE_COLLECTION, ())), 1);
+		// `CollectionDeposit` is 2.
+		assert_eq!(Balances::reserved_balance(&contract), reserved + 4);
+	});
+}
+
+#[test]
+fn contract_mints_as_issuer() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+
+		assert_eq!(call(&contract, func::MINT, (collection, 7u32, BOB)).0, SUCCESS);
+		assert_eq!(
+			decode::<Option<AccountId32>>(call(&contract, func::OWNER_OF, (collection, 7u32))),
+			Some(BOB)
+		);
+		assert_eq!(
+			call(&contract, func::MINT, (collection, 7u32, BOB)).0,
+			ExtensionError::AlreadyExists as u32
+		);
+	});
+}
+
+#[test]
+fn contract_cannot_mint_into_foreign_collection() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+		assert_ok!(Nfts::create(RuntimeOrigin::signed(ALICE), ALICE, Default::default()));
+
+		assert_eq!(
+			call(&contract, func::MINT, (0u32, 1u32, BOB)).0,
+			ExtensionError::NoPermission as u32
+		);
+		assert_eq!(owner(0, 1), None);
+	});
+}
+
+#[test]
+fn transfer_requires_ownership_or_approval() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+		assert_eq!(call(&contract, func::MINT, (collection, 1u32, contract.clone())).0, SUCCESS);
+		assert_eq!(call(&contract, func::MINT, (collection, 2u32, BOB)).0, SUCCESS);
+
+		assert_eq!(call(&contract, func::TRANSFER, (collection, 1u32, BOB)).0, SUCCESS);
+		assert_eq!(owner(collection, 1), Some(BOB));
+
+		assert_eq!(
+			call(&contract, func::TRANSFER, (collection, 2u32, ALICE)).0,
+			ExtensionError::NoPermission as u32
+		);
+		assert_eq!(owner(collection, 2), Some(BOB));
+	});
+}
+
+#[test]
+fn approved_delegate_can_transfer() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+		assert_eq!(call(&contract, func::MINT, (collection, 1u32, contract.clone())).0, SUCCESS);
+
+		assert_eq!(call(&contract, func::APPROVE, (collection, 1u32, BOB, true)).0, SUCCESS);
+		assert_ok!(Nfts::transfer(RuntimeOrigin::signed(BOB), collection, 1, ALICE));
+		assert_eq!(owner(collection, 1), Some(ALICE));
+	});
+}
+
+#[test]
+fn revoked_delegate_cannot_transfer() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+		assert_eq!(call(&contract, func::MINT, (collection, 1u32, contract.clone())).0, SUCCESS);
+
+		assert_eq!(call(&contract, func::APPROVE, (collection, 1u32, BOB, true)).0, SUCCESS);
+		assert_eq!(call(&contract, func::APPROVE, (collection, 1u32, BOB, false)).0, SUCCESS);
+		assert!(Nfts::transfer(RuntimeOrigin::signed(BOB), collection, 1, ALICE).is_err());
+		assert_eq!(owner(collection, 1), Some(contract));
+	});
+}
+
+#[test]
+fn burn_removes_item() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+		assert_eq!(call(&contract, func::MINT, (collection, 1u32, contract.clone())).0, SUCCESS);
+
+		assert_eq!(call(&contract, func::BURN, (collection, 1u32)).0, SUCCESS);
+		assert_eq!(owner(collection, 1), None);
+		assert_eq!(
+			call(&contract, func::BURN, (collection, 1u32)).0,
+			ExtensionError::UnknownItem as u32
+		);
+	});
+}
+
+#[test]
+fn attributes_are_set_and_read() {
+	new_test_ext().execute_with(|| {
+		let (contract, collection) = deploy_with_collection();
+		assert_eq!(call(&contract, func::MINT, (collection, 1u32, BOB)).0, SUCCESS);
+
+		let (key, value) = (b"rarity".to_vec(), b"legendary".to_vec());
+		assert_eq!(
+			call(&contract, func::SET_ATTRIBUTE, (collection, Some(1u32), &key, &value)).0,
+			SUCCESS
+		);
+		let collection_value = b"set".to_vec();
+		assert_eq!(
+			call(&contract, func::SET_ATTRIBUTE, (collection, None::<u32>, &key, &collection_value))
+				.0,
+			SUCCESS
+		);
+
+		let attribute = |item: Option<u32>, key: &Vec<u8>| {
+			decode::<Option<Vec<u8>>>(call(&contract, func::ATTRIBUTE, (collection, item, key)))
+		};
+		assert_eq!(attribute(Some(1), &key), Some(value));
+		assert_eq!(attribute(None, &key), Some(collection_value));
+		assert_eq!(attribute(Some(1), &b"missing".to_vec()), None);
+	});
+}
+
+#[test]
+fn unknown_function_returns_status_code() {
+	new_test_ext().execute_with(|| {
+		let contract = deploy();
+
+		assert_eq!(call(&contract, 100, ()).0, ExtensionError::UnknownFunction as u32);
+	});
+}
@@ -0,0 +1,311 @@
+//! Weights for nativex_nfts_extension
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=nativex_nfts_extension` before relying on them.
+//!
+//! Each function `f(r)` is the cost of a contract call making `r` calls of the function, and the
+//! extension charges its slope, see [`nativex_chain_extensions::per_call`]:
+//! - The base is a `pallet_contracts` call into a small contract. It cancels out of the charge.
+//! - Each call counts the storage accesses listed above the function. The proof size adds up, for
+//!   every item read, its `MaxEncodedLen` in the runtime plus the trie proof budget the benchmarks
+//!   use: 2475 bytes for a map entry and 495 bytes for a plain value. The execution time is given
+//!   with its justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for nativex_nfts_extension.
+pub trait WeightInfo {
+	fn collection_owner(r: u32, ) -> Weight;
+	fn owner_of(r: u32, ) -> Weight;
+	fn attribute(r: u32, ) -> Weight;
+	fn create_collection(r: u32, ) -> Weight;
+	fn mint(r: u32, ) -> Weight;
+	fn transfer(r: u32, ) -> Weight;
+	fn approve(r: u32, ) -> Weight;
+	fn burn(r: u32, ) -> Weight;
+	fn set_attribute(r: u32, ) -> Weight;
+}
+
+/// Weights for nativex_nfts_extension using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Collection (r:1 w:0)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Execution: one map read, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn collection_owner(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_559).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Item (r:1 w:0)
+	/// Proof: Nfts Item (max_size: 861, added: 3336)
+	/// Execution: one map read, 10 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn owner_of(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 3_336).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Attribute (r:1 w:0)
+	/// Proof: Nfts Attribute (max_size: 446, added: 2921)
+	/// Execution: one map read and a copy of up to 256 bytes, 12 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn attribute(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 2_921).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts NextCollectionId (r:1 w:1)
+	/// Proof: Nfts NextCollectionId (max_size: 4, added: 499)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts CollectionRoleOf (r:0 w:1)
+	/// Storage: Nfts CollectionConfigOf (r:0 w:1)
+	/// Storage: Nfts CollectionAccount (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a deposit reserve and five inserts in a storage layer, 40 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn create_collection(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(40_000_000, 5_661).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((6_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Item (r:1 w:1)
+	/// Proof: Nfts Item (max_size: 861, added: 3336)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts CollectionConfigOf (r:1 w:0)
+	/// Proof: Nfts CollectionConfigOf (max_size: 73, added: 2548)
+	/// Storage: Nfts CollectionRoleOf (r:1 w:0)
+	/// Proof: Nfts CollectionRoleOf (max_size: 69, added: 2544)
+	/// Storage: Nfts ItemConfigOf (r:1 w:1)
+	/// Proof: Nfts ItemConfigOf (max_size: 48, added: 2523)
+	/// Storage: Nfts Account (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: the `pallet_nfts` `mint` checks and a deposit reserve in a storage layer, 50 µs.
+	/// The range of component `r` is `[0, 1]`.
+	fn mint(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 16_113).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Item (r:1 w:1)
+	/// Proof: Nfts Item (max_size: 861, added: 3336)
+	/// Storage: Nfts Collection (r:1 w:0)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts CollectionConfigOf (r:1 w:0)
+	/// Proof: Nfts CollectionConfigOf (max_size: 73, added: 2548)
+	/// Storage: Nfts ItemConfigOf (r:1 w:0)
+	/// Proof: Nfts ItemConfigOf (max_size: 48, added: 2523)
+	/// Storage: Nfts Attribute (r:1 w:0)
+	/// Proof: Nfts Attribute (max_size: 446, added: 2921)
+	/// Storage: Nfts Account (r:0 w:2)
+	/// Storage: Nfts ItemPriceOf (r:0 w:1)
+	/// Storage: Nfts PendingSwapOf (r:0 w:1)
+	/// Execution: the `pallet_nfts` `transfer` checks, including the transfer lock attribute, in a
+	/// storage layer, 50 µs.
+	/// The range of component `r` is `[0, 1]`.
+	fn transfer(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 13_887).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Item (r:1 w:1)
+	/// Proof: Nfts Item (max_size: 861, added: 3336)
+	/// Storage: Nfts Collection (r:1 w:0)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts CollectionConfigOf (r:1 w:0)
+	/// Proof: Nfts CollectionConfigOf (max_size: 73, added: 2548)
+	/// Storage: Nfts ItemConfigOf (r:1 w:0)
+	/// Proof: Nfts ItemConfigOf (max_size: 48, added: 2523)
+	/// Execution: an insert into or removal from the item's approvals, at most 20, in a storage
+	/// layer, 30 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn approve(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 10_966).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts Item (r:1 w:1)
+	/// Proof: Nfts Item (max_size: 861, added: 3336)
+	/// Storage: Nfts ItemConfigOf (r:1 w:1)
+	/// Proof: Nfts ItemConfigOf (max_size: 48, added: 2523)
+	/// Storage: Nfts CollectionRoleOf (r:1 w:0)
+	/// Proof: Nfts CollectionRoleOf (max_size: 69, added: 2544)
+	/// Storage: Nfts Account (r:0 w:1)
+	/// Storage: Nfts ItemMetadataOf (r:1 w:0)
+	/// Proof: Nfts ItemMetadataOf (max_size: 140, added: 2615)
+	/// Storage: Nfts ItemPriceOf (r:0 w:1)
+	/// Storage: Nfts ItemAttributesApprovalsOf (r:0 w:1)
+	/// Storage: Nfts PendingSwapOf (r:0 w:1)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: the `pallet_nfts` `burn` checks, six removals and a deposit unreserve in a
+	/// storage layer, 50 µs.
+	/// The range of component `r` is `[0, 1]`.
+	fn burn(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 16_180).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((8_u64).saturating_mul(r.into())))
+	}
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: Nfts Collection (r:1 w:1)
+	/// Proof: Nfts Collection (max_size: 84, added: 2559)
+	/// Storage: Nfts CollectionConfigOf (r:1 w:0)
+	/// Proof: Nfts CollectionConfigOf (max_size: 73, added: 2548)
+	/// Storage: Nfts ItemConfigOf (r:1 w:0)
+	/// Proof: Nfts ItemConfigOf (max_size: 48, added: 2523)
+	/// Storage: Nfts Attribute (r:1 w:1)
+	/// Proof: Nfts Attribute (max_size: 446, added: 2921)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a deposit reserve and the insert of up to 256 bytes in a storage layer, 50 µs.
+	/// The range of component `r` is `[0, 20]`.
+	fn set_attribute(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 13_154).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn collection_owner(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 2_559).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn owner_of(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(10_000_000, 3_336).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn attribute(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(12_000_000, 2_921).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn create_collection(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(40_000_000, 5_661).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((6_u64).saturating_mul(r.into())))
+	}
+	fn mint(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 16_113).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(r.into())))
+	}
+	fn transfer(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 13_887).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(r.into())))
+	}
+	fn approve(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(30_000_000, 10_966).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((4_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	fn burn(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 16_180).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((6_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((8_u64).saturating_mul(r.into())))
+	}
+	fn set_attribute(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(50_000_000, 13_154).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+	}
+}
@@ -0,0 +1,52 @@
+//! Benchmarks for the functions of `FetchRandomExtension`.
+//!
+//! Chain extensions are not pallets, so the benchmarks hang off the stand-in [`Pallet`] below,
+//! which the runtime registers as `[nativex_rand_extension, RandExtensionBench::<Runtime>]`.
+//!
+//! `fetch_random` calls a contract that calls `FetchRandom` `r` times, see
+//! [`nativex_chain_extensions::benchmarking`]. The runtime's chain extension must route `func_id`
+//! [`FETCH_RANDOM`] of extension id 0 to `FetchRandomExtension`.
+
+use crate::{FetchRandomExtension, FETCH_RANDOM};
+use frame_benchmarking::v2::*;
+use frame_support::traits::OnInitialize;
+use nativex_chain_extensions::{
+	benchmarking::{call_fixture, deploy_fixture, MAX_CALLS},
+	SUCCESS,
+};
+
+pub struct Pallet<T: Config>(frame_system::Pallet<T>);
+pub trait Config: pallet_insecure_randomness_collective_flip::Config + pallet_contracts::Config {}
+
+/// Fill the random material so `random` mixes the maximum number of hashes.
+fn fill_random_material<T: Config>() {
+	for block in 0..81u32 {
+		pallet_insecure_randomness_collective_flip::Pallet::<T>::on_initialize(block.into());
+	}
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn fetch_random(r: Linear<0, MAX_CALLS>) -> Result<(), BenchmarkError> {
+		fill_random_material::<T>();
+		let caller: T::AccountId = whitelisted_caller();
+		let contract = deploy_fixture::<T>(&caller)?;
+
+		#[block]
+		{
+			let (status, _seed) = call_fixture::<T, FetchRandomExtension>(
+				caller,
+				contract,
+				r,
+				FETCH_RANDOM,
+				[1u8; 32],
+			);
+			assert_eq!(status, SUCCESS);
+		}
+
+		Ok(())
+	}
+}
@@ -1,58 +1,71 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{
-	log::{error, trace},
-	traits::Randomness,
-};
-use pallet_contracts::chain_extension::{
-	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig,
+#[cfg(feature = "runtime-benchmarks")]
+pub mod benchmarking;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+pub use weights::WeightInfo;
+
+use frame_support::{log::trace, traits::Randomness, weights::Weight};
+use nativex_chain_extensions::{
+	per_call, read_input, write_output, Extension, ExtensionError, ExtensionResult,
 };
-use parity_scale_codec::Encode;
+use pallet_contracts::chain_extension::{Environment, Ext, InitState, SysConfig};
 use sp_core::crypto::UncheckedFrom;
-use sp_runtime::DispatchError;
+use sp_std::marker::PhantomData;
 
-/// Contract extension for `FetchRandom`
-#[derive(Default)]
-pub struct FetchRandomExtension;
+/// `func_id` of `FetchRandom`.
+pub const FETCH_RANDOM: u16 = 1101;
+
+/// The cost of one `FetchRandom` call: the benchmarked cost of a contract calling it once, less
+/// the cost of the same contract not calling it.
+pub fn fetch_random_weight<W: WeightInfo>() -> Weight {
+	per_call(W::fetch_random)
+}
+
+/// Contract extension for `FetchRandom`, charging weights `W`.
+///
+/// Registered under extension id 0, so contracts keep calling `FetchRandom` with `func_id` 1101.
+pub struct FetchRandomExtension<W = ()>(PhantomData<W>);
+
+impl<W> Default for FetchRandomExtension<W> {
+	fn default() -> Self {
+		Self(PhantomData)
+	}
+}
 
-impl<T> ChainExtension<T> for FetchRandomExtension
+impl<T, W> Extension<T> for FetchRandomExtension<W>
 where
 	T: pallet_contracts::Config + pallet_insecure_randomness_collective_flip::Config,
-
-	<T as SysConfig>::AccountId: UncheckedFrom<<T as SysConfig>::Hash> + AsRef<[u8]>,
+	W: WeightInfo,
 {
-	fn call<E: Ext>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	const ID: u16 = 0;
+
+	fn call<E>(&mut self, func_id: u16, env: Environment<E, InitState>) -> ExtensionResult
 	where
 		E: Ext<T = T>,
 		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
 	{
-		let func_id = env.func_id();
 		match func_id {
-			1101 => {
+			FETCH_RANDOM => {
 				let mut env = env.buf_in_buf_out();
-				let arg: [u8; 32] = env.read_as()?;
+				// The seed is a `T::Hash`, so the cost does not depend on the input.
+				env.charge_weight(fetch_random_weight::<W>())?;
+
+				let arg: [u8; 32] = read_input(&mut env)?;
 				let random_seed =
-					pallet_insecure_randomness_collective_flip::Pallet::<T>::random(&arg);
+					pallet_insecure_randomness_collective_flip::Pallet::<T>::random(&arg).0;
 
-				let random_slice = random_seed.0.encode();
 				trace!(
 					target: "runtime",
 					"[ChainExtension]|call|func_id:{:}",
 					func_id
 				);
-				env.write(&random_slice, false, None)
-					.map_err(|_| DispatchError::Other("ChainExtension failed to call random"))?;
+				write_output(&mut env, &random_seed)
 			},
 
-			_ => {
-				error!("Called an unregistered `func_id`: {:}", func_id);
-				return Err(DispatchError::Other("Unimplemented func_id"));
-			},
+			_ => Err(ExtensionError::UnknownFunction.into()),
 		}
-		Ok(RetVal::Converging(0))
-	}
-
-	fn enabled() -> bool {
-		true
 	}
 }
@@ -0,0 +1,12 @@
+use crate::{fetch_random_weight, WeightInfo};
+use frame_support::weights::{constants::RocksDbWeight, Weight};
+
+#[test]
+fn charges_the_cost_of_one_call() {
+	let one_call = Weight::from_parts(60_000_000, 3_089) + RocksDbWeight::get().reads(1);
+	assert_eq!(fetch_random_weight::<()>(), one_call);
+	assert_eq!(
+		<() as WeightInfo>::fetch_random(2),
+		<() as WeightInfo>::fetch_random(0) + one_call.saturating_mul(2)
+	);
+}
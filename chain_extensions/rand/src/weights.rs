@@ -0,0 +1,53 @@
+//! Weights for nativex_rand_extension
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=nativex_rand_extension` before relying on them.
+//!
+//! `fetch_random(r)` is the cost of a contract call making `r` `FetchRandom` calls, and the
+//! extension charges its slope, see [`crate::fetch_random_weight`]:
+//! - The base is a `pallet_contracts` call into a small contract. It cancels out of the charge.
+//! - Each call reads `RandomMaterial`, whose proof is its `MaxEncodedLen` (81 hashes, 2594 bytes)
+//!   plus 495 bytes of trie proof for a plain value, and hashes the 81 hashes with the subject.
+//!   60 µs leaves a wide margin over that and the copy of the 32-byte seed.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for nativex_rand_extension.
+pub trait WeightInfo {
+	fn fetch_random(r: u32, ) -> Weight;
+}
+
+/// Weights for nativex_rand_extension using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: Contracts ContractInfoOf (r:1 w:1)
+	/// Storage: Contracts CodeStorage (r:1 w:0)
+	/// Storage: Timestamp Now (r:1 w:0)
+	/// Storage: System Account (r:1 w:0)
+	/// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	/// Proof: RandomnessCollectiveFlip RandomMaterial (max_size: 2594, added: 3089)
+	/// The range of component `r` is `[0, 20]`.
+	fn fetch_random(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 3_089).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn fetch_random(r: u32, ) -> Weight {
+		Weight::from_parts(300_000_000, 0)
+			.saturating_add(Weight::from_parts(60_000_000, 3_089).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
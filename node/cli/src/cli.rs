@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use sc_cli::RunCmd;
 
 #[derive(Debug, clap::Parser)]
@@ -7,6 +9,25 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Read the options the command line doesn't set from this TOML file.
+	///
+	/// Every key is the long name of an option without the leading `--`, see
+	/// `nativex config print-default`.
+	#[arg(long, value_name = "FILE")]
+	pub config: Option<PathBuf>,
+
+	#[clap(flatten)]
+	pub eth: service::eth::EthConfiguration,
+
+	#[clap(flatten)]
+	pub indexer: service::indexer::IndexerConfiguration,
+
+	#[clap(flatten)]
+	pub storage_chain: service::storage_chain::StorageChainConfiguration,
+
+	#[clap(flatten)]
+	pub health: service::health::HealthConfiguration,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -50,4 +71,24 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Configuration file utilities.
+	#[command(subcommand)]
+	Config(crate::config::ConfigCmd),
+
+	/// Export or import the slashing protection history of the validator keys.
+	SlashingProtection(service::slashing_protection::SlashingProtectionCmd),
+
+	/// Build and sign extrinsics offline, and submit them.
+	#[command(subcommand)]
+	Tx(crate::tx::TxCmd),
+
+	/// Decode blocks, extrinsics and storage of the local database.
+	Inspect(crate::inspect::InspectCmd),
+
+	/// Turn the state of a live chain into the chain spec of a testnet run by dev authorities.
+	ForkOff(crate::fork_off::ForkOffCmd),
+
+	/// Export the balances of every account at a block, for airdrops and audits.
+	ExportBalances(crate::export_balances::ExportBalancesCmd),
 }
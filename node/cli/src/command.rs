@@ -14,7 +14,7 @@ use sp_core::hexdisplay::ascii_format;
 use sp_keyring::Sr25519Keyring;
 
 #[cfg(feature = "try-runtime")]
-use try_runtime_cli::block_building_info::timestamp_with_aura_info;
+use try_runtime_cli::block_building_info::timestamp_with_babe_info;
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -47,6 +47,8 @@ impl SubstrateCli for Cli {
 			"dev" => Box::new(chain_spec::development::development_config()?),
 			#[cfg(feature = "with-development-runtime")]
 			"" | "local" => Box::new(chain_spec::development::local_testnet_config()?),
+			#[cfg(feature = "with-development-runtime")]
+			"staging" => Box::new(chain_spec::development::staging_config()?),
 			//path => Box::new(chain_spec::development::ChainSpec::from_json_file(
 			//	std::path::PathBuf::from(path),
 			//)?),
@@ -76,7 +78,7 @@ impl SubstrateCli for Cli {
 
 /// Parse and run command line arguments
 pub fn run() -> sc_cli::Result<()> {
-	let cli = Cli::from_args();
+	let cli = crate::config::parse_args()?;
 
 	match &cli.subcommand {
 		Some(Subcommand::Key(cmd)) => cmd.run(&cli),
@@ -213,8 +215,8 @@ pub fn run() -> sc_cli::Result<()> {
 		// },
 		#[cfg(feature = "try-runtime")]
 		Some(Subcommand::TryRuntime(cmd)) => {
-			use crate::service::ExecutorDispatch;
 			use sc_executor::{sp_wasm_interface::ExtendedHostFunctions, NativeExecutionDispatch};
+			use service::DevelopmentExecutor;
 			let runner = cli.create_runner(cmd)?;
 			runner.async_run(|config| {
 				// we don't need any of the components of new_partial, just a runtime, or a task
@@ -223,12 +225,13 @@ pub fn run() -> sc_cli::Result<()> {
 				let task_manager =
 					sc_service::TaskManager::new(config.tokio_handle.clone(), registry)
 						.map_err(|e| sc_cli::Error::Service(sc_service::Error::Prometheus(e)))?;
-				let info_provider = timestamp_with_aura_info(6000);
+				let info_provider =
+					timestamp_with_babe_info(development_runtime::MILLISECS_PER_BLOCK);
 
 				Ok((
 					cmd.run::<Block, ExtendedHostFunctions<
 						sp_io::SubstrateHostFunctions,
-						<ExecutorDispatch as NativeExecutionDispatch>::ExtendHostFunctions,
+						<DevelopmentExecutor as NativeExecutionDispatch>::ExtendHostFunctions,
 					>, _>(Some(info_provider)),
 					task_manager,
 				))
@@ -242,8 +245,36 @@ pub fn run() -> sc_cli::Result<()> {
 			let runner = cli.create_runner(cmd)?;
 			runner.sync_run(|config| cmd.run::<Block>(&config))
 		},
+		Some(Subcommand::Config(cmd)) => cmd.run(),
+		Some(Subcommand::SlashingProtection(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(&config))
+		},
+		Some(Subcommand::Tx(cmd)) => cmd.run(),
+		Some(Subcommand::Inspect(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|mut config| {
+				let (client, _, _, _task_manager) = service::new_chain_ops(&mut config)?;
+				cmd.run(&client)
+			})
+		},
+		Some(Subcommand::ForkOff(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|config| cmd.run(config))
+		},
+		Some(Subcommand::ExportBalances(cmd)) => {
+			let runner = cli.create_runner(cmd)?;
+			runner.sync_run(|mut config| {
+				let (client, _, _, _task_manager) = service::new_chain_ops(&mut config)?;
+				cmd.run(&client)
+			})
+		},
 		None => {
 			let runner = cli.create_runner(&cli.run)?;
+			let eth_config = cli.eth.clone();
+			let indexer_config = cli.indexer.clone();
+			let storage_chain = cli.storage_chain.clone();
+			let health_config = cli.health.clone();
 			runner.run_node_until_exit(|config| async move {
 				let chain_spec = &config.chain_spec;
 				if chain_spec.is_development() {
@@ -252,7 +283,7 @@ pub fn run() -> sc_cli::Result<()> {
 						return service::new_full::<
 							service::development_runtime::RuntimeApi,
 							service::DevelopmentExecutor,
-						>(config)
+						>(config, eth_config, indexer_config, storage_chain, health_config)
 						.map_err(sc_cli::Error::Service);
 					}
 					#[cfg(not(feature = "with-development-runtime"))]
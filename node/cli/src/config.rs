@@ -0,0 +1,174 @@
+//! Node options read from the TOML file given with `--config`.
+//!
+//! Every key of the file is the long name of a command line option, without the leading `--`:
+//! `rpc-port = 9944` stands for `--rpc-port=9944`, `validator = true` for `--validator` and
+//! `bootnodes = ["a", "b"]` for `--bootnodes=a --bootnodes=b`. An option given on the command
+//! line overrides the file. The file only applies when running the node, not to subcommands.
+
+use std::{ffi::OsString, path::Path};
+
+use clap::{parser::ValueSource, CommandFactory};
+use sc_cli::SubstrateCli;
+
+use crate::cli::Cli;
+
+/// The configuration file printed by `nativex config print-default`.
+pub const DEFAULT_CONFIG: &str = r#"# Options of the `nativex` node. Every key is the long name of a
+# command line option without the leading `--`. Options given on the command line override the
+# ones of this file.
+
+# Node
+# name = "validator-1"
+# chain = "local"
+# base-path = "/var/lib/nativex"
+validator = false
+
+# Network
+port = 30333
+bootnodes = []
+# public-addr = ["/dns/validator-1.example.com/tcp/30333"]
+# reserved-nodes = []
+reserved-only = false
+
+# RPC
+rpc-port = 9944
+rpc-external = false
+rpc-methods = "auto"
+# rpc-cors = "all"
+
+# Prometheus
+prometheus-port = 9615
+prometheus-external = false
+
+# Telemetry
+# telemetry-url = ["wss://telemetry.polkadot.io/submit/ 0"]
+no-telemetry = false
+
+# Database
+# database = "rocksdb"
+# db-cache = 1024
+state-pruning = "256"
+blocks-pruning = "archive-canonical"
+"#;
+
+/// Configuration file utilities.
+#[derive(Debug, clap::Subcommand)]
+pub enum ConfigCmd {
+	/// Print a configuration file with the default options.
+	PrintDefault,
+}
+
+impl ConfigCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			ConfigCmd::PrintDefault => print!("{DEFAULT_CONFIG}"),
+		}
+		Ok(())
+	}
+}
+
+/// Parse the command line, adding the options of the `--config` file that it doesn't set.
+///
+/// Like [`SubstrateCli::from_args`], exits on invalid arguments.
+pub fn parse_args() -> sc_cli::Result<Cli> {
+	let args: Vec<OsString> = std::env::args_os().collect();
+	let cli = Cli::from_iter(args.iter());
+	match (&cli.config, &cli.subcommand) {
+		(Some(path), None) => Ok(Cli::from_iter(with_file(args, &read(path)?)?)),
+		_ => Ok(cli),
+	}
+}
+
+fn read(path: &Path) -> sc_cli::Result<toml::Table> {
+	let content = std::fs::read_to_string(path).map_err(|e| {
+		sc_cli::Error::Input(format!("Can't read config file {}: {e}", path.display()))
+	})?;
+	content
+		.parse()
+		.map_err(|e| sc_cli::Error::Input(format!("Invalid config file {}: {e}", path.display())))
+}
+
+/// `args` with the options of `file` that `args` don't set inserted after the binary name.
+fn with_file(args: Vec<OsString>, file: &toml::Table) -> sc_cli::Result<Vec<OsString>> {
+	let command = Cli::command();
+	let matches = command.clone().try_get_matches_from(&args).unwrap_or_else(|e| e.exit());
+
+	let mut file_args = Vec::new();
+	for (key, value) in file {
+		let arg = command
+			.get_arguments()
+			.find(|arg| arg.get_long() == Some(key.as_str()) && key != "config")
+			.ok_or_else(|| sc_cli::Error::Input(format!("Unknown option `{key}` in config file")))?;
+		if matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine) {
+			continue
+		}
+		let values = match value {
+			toml::Value::Array(values) => values.iter().collect(),
+			value => vec![value],
+		};
+		for value in values {
+			let value = match value {
+				toml::Value::String(value) => value.clone(),
+				toml::Value::Integer(_) | toml::Value::Float(_) | toml::Value::Boolean(_) =>
+					value.to_string(),
+				_ =>
+					return Err(sc_cli::Error::Input(format!(
+						"`{key}` in config file must be a string, a number, a boolean or an array \
+						 of them",
+					))),
+			};
+			if arg.get_action().takes_values() {
+				file_args.push(format!("--{key}={value}"));
+			} else if value == "true" {
+				file_args.push(format!("--{key}"));
+			}
+		}
+	}
+
+	let mut args = args.into_iter();
+	Ok(args.next().into_iter().chain(file_args.into_iter().map(Into::into)).chain(args).collect())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use clap::Parser;
+
+	const BOOTNODE: &str =
+		"/ip4/127.0.0.1/tcp/30333/p2p/12D3KooWEyoppNCUx8Yx66oV9fJnriXwCcXwDDUA2kj6vnc6iDEp";
+
+	fn args(args: &[&str]) -> Vec<OsString> {
+		args.iter().map(Into::into).collect()
+	}
+
+	#[test]
+	fn default_config_is_valid() {
+		let file = DEFAULT_CONFIG.parse().unwrap();
+		let args = with_file(args(&["nativex"]), &file).unwrap();
+		let cli = Cli::try_parse_from(args).unwrap();
+		assert_eq!(cli.run.network_params.port, Some(30333));
+		assert!(!cli.run.validator);
+	}
+
+	#[test]
+	fn command_line_overrides_file() {
+		let file = format!(
+			"rpc-port = 9944\nport = 30334\nvalidator = true\nbootnodes = [\"{BOOTNODE}\"]",
+		)
+		.parse()
+		.unwrap();
+		let args = with_file(args(&["nativex", "--rpc-port=9955"]), &file).unwrap();
+		let bootnodes = format!("--bootnodes={BOOTNODE}");
+		assert_eq!(
+			args,
+			self::args(&["nativex", &bootnodes, "--port=30334", "--validator", "--rpc-port=9955"]),
+		);
+	}
+
+	#[test]
+	fn unknown_options_are_rejected() {
+		let file = "rpc-prot = 9944".parse().unwrap();
+		assert!(with_file(args(&["nativex"]), &file).is_err());
+	}
+}
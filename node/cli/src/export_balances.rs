@@ -0,0 +1,379 @@
+//! `nativex export-balances` writes the balances of every account at a block of the local
+//! database, for airdrops and audits.
+//!
+//! The accounts of `System::Account` and of the assets are walked straight from the database and
+//! written line by line, so the state is never loaded whole. What is staked, still vesting or
+//! pooled is looked up for each account as it is written.
+
+use std::{
+	collections::BTreeMap,
+	fs::File,
+	io::{BufWriter, Write},
+	path::PathBuf,
+};
+
+use development_runtime::Runtime;
+use pallet_nomination_pools::PoolMember;
+use pallet_staking::StakingLedger;
+use pallet_vesting::VestingInfo;
+use parity_scale_codec::Decode;
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::{HeaderBackend, StorageKey, StorageProvider};
+use sp_core::{crypto::Ss58Codec, H256};
+use sp_runtime::traits::ConvertInto;
+
+use crate::storage_keys::{blake2_128_concat, map_key, twox_64_concat, value_key};
+use common_primitives::{AccountId, Balance, BlockNumber};
+use service::client::Client;
+
+type AccountInfo = frame_system::AccountInfo<
+	<Runtime as frame_system::Config>::Index,
+	<Runtime as frame_system::Config>::AccountData,
+>;
+type AssetId = u32;
+
+/// The instances of `pallet_assets`.
+const ASSET_PALLETS: [&str; 2] = ["Assets", "PoolAssets"];
+
+/// The columns of the snapshot.
+const COLUMNS: [&str; 9] = [
+	"account",
+	"asset",
+	"free",
+	"reserved",
+	"frozen",
+	"staked",
+	"unvested",
+	"pool_id",
+	"pool_points",
+];
+
+/// The leading fields of `pallet_assets::AssetAccount`, which are private.
+#[derive(Decode)]
+struct AssetAccount {
+	balance: Balance,
+}
+
+/// The leading fields of `pallet_assets::AssetDetails`, which are private.
+#[derive(Decode)]
+struct AssetDetails {
+	_owner: AccountId,
+	_issuer: AccountId,
+	_admin: AccountId,
+	_freezer: AccountId,
+	supply: Balance,
+}
+
+/// The format of the snapshot.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum Format {
+	/// A header line, then a line of comma-separated values per row.
+	Csv,
+	/// An array of objects, with balances as decimal strings.
+	Json,
+}
+
+/// Export the balances of every account at a block of the local database.
+///
+/// Each account has a row of its native balances, and a row per asset it holds. The sum of the
+/// balances of the accounts is checked against the total issuance, and the supply of each asset.
+#[derive(Debug, clap::Parser)]
+pub struct ExportBalancesCmd {
+	/// The number or the hash of the block, the best block by default.
+	#[arg(long, value_name = "NUMBER_OR_HASH")]
+	pub at: Option<String>,
+
+	/// The format of the snapshot.
+	#[arg(long, value_enum, default_value_t = Format::Csv)]
+	pub format: Format,
+
+	/// Where to write the snapshot, the standard output by default.
+	#[arg(long, short, value_name = "FILE")]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl ExportBalancesCmd {
+	/// Run the command.
+	pub fn run(&self, client: &Client) -> sc_cli::Result<()> {
+		let hash = match &self.at {
+			Some(at) => crate::inspect::block_hash(client, at)?,
+			None => client.info().best_hash,
+		};
+		let number = client.number(hash)?.ok_or("The block has no number")?;
+		let output: Box<dyn Write> = match &self.output {
+			Some(output) => Box::new(File::create(output)?),
+			None => Box::new(std::io::stdout().lock()),
+		};
+		let mut snapshot = Snapshot::new(BufWriter::new(output), self.format)?;
+		let state = State { client, hash };
+
+		let mut balances: Balance = 0;
+		let accounts = value_key("System", "Account");
+		let pairs = client.storage_pairs(hash, Some(&StorageKey(accounts.clone())), None)?;
+		for (key, value) in pairs {
+			let account = concat_key::<AccountId>(&mut &key.0[accounts.len()..], 16)?;
+			let info = decode::<AccountInfo>(&value.0)?;
+			balances += info.data.free + info.data.reserved;
+			let mut row = Row {
+				account: account.to_ss58check(),
+				free: info.data.free,
+				reserved: info.data.reserved,
+				frozen: info.data.frozen,
+				staked: state.staked(&account)?,
+				unvested: state.unvested(&account, number)?,
+				..Default::default()
+			};
+			if let Some(member) = state.pool_member(&account)? {
+				row.pool = Some((member.pool_id, member.points));
+			}
+			snapshot.write(&row)?;
+		}
+
+		let mut supplies = BTreeMap::new();
+		for pallet in ASSET_PALLETS {
+			let accounts = value_key(pallet, "Account");
+			let pairs = client.storage_pairs(hash, Some(&StorageKey(accounts.clone())), None)?;
+			for (key, value) in pairs {
+				let key = &mut &key.0[accounts.len()..];
+				let asset = concat_key::<AssetId>(key, 16)?;
+				let account = concat_key::<AccountId>(key, 16)?;
+				let balance = decode::<AssetAccount>(&value.0)?.balance;
+				*supplies.entry((pallet, asset)).or_insert(0) += balance;
+				snapshot.write(&Row {
+					account: account.to_ss58check(),
+					asset: Some(format!("{pallet}:{asset}")),
+					free: balance,
+					..Default::default()
+				})?;
+			}
+		}
+		snapshot.finish()?;
+
+		let issuance = state.get::<Balance>(value_key("Balances", "TotalIssuance"))?;
+		let mut mismatches = Vec::new();
+		if issuance != Some(balances) {
+			mismatches.push(format!("total issuance {issuance:?}, accounts {balances}"));
+		}
+		for ((pallet, asset), balances) in supplies {
+			let details = map_key(pallet, "Asset", &[blake2_128_concat(asset)]);
+			let supply = state.get::<AssetDetails>(details)?.map(|details| details.supply);
+			if supply != Some(balances) {
+				mismatches.push(format!("{pallet}:{asset} supply {supply:?}, accounts {balances}"));
+			}
+		}
+		if !mismatches.is_empty() {
+			return Err(format!("Balances don't add up: {}", mismatches.join("; ")).into())
+		}
+		log::info!("Balances at {hash:?} add up to the total issuance and the asset supplies");
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ExportBalancesCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+/// The state at a block, read a key at a time.
+struct State<'a> {
+	client: &'a Client,
+	hash: H256,
+}
+
+impl State<'_> {
+	fn get<T: Decode>(&self, key: Vec<u8>) -> sc_cli::Result<Option<T>> {
+		match self.client.storage(self.hash, &StorageKey(key))? {
+			Some(value) => decode(&value.0).map(Some),
+			None => Ok(None),
+		}
+	}
+
+	/// What `account` bonded as a stash, unbonding funds included.
+	fn staked(&self, account: &AccountId) -> sc_cli::Result<Balance> {
+		let bonded = map_key("Staking", "Bonded", &[twox_64_concat(account)]);
+		let Some(controller) = self.get::<AccountId>(bonded)? else { return Ok(0) };
+		let ledger = map_key("Staking", "Ledger", &[blake2_128_concat(controller)]);
+		Ok(self.get::<StakingLedger<Runtime>>(ledger)?.map_or(0, |ledger| ledger.total))
+	}
+
+	/// What is still locked by the vesting schedules of `account` at block `number`.
+	fn unvested(&self, account: &AccountId, number: BlockNumber) -> sc_cli::Result<Balance> {
+		let vesting = map_key("Vesting", "Vesting", &[blake2_128_concat(account)]);
+		let schedules = self.get::<Vec<VestingInfo<Balance, BlockNumber>>>(vesting)?;
+		Ok(schedules
+			.unwrap_or_default()
+			.iter()
+			.map(|schedule| schedule.locked_at::<ConvertInto>(number))
+			.sum())
+	}
+
+	fn pool_member(&self, account: &AccountId) -> sc_cli::Result<Option<PoolMember<Runtime>>> {
+		self.get(map_key("NominationPools", "PoolMembers", &[twox_64_concat(account)]))
+	}
+}
+
+fn decode<T: Decode>(value: &[u8]) -> sc_cli::Result<T> {
+	T::decode(&mut &value[..])
+		.map_err(|e| sc_cli::Error::Input(format!("Storage can't be decoded: {e}")))
+}
+
+/// Decode a map key hashed with a concatenating hasher of `hash_len` bytes, and skip past it.
+fn concat_key<T: Decode>(key: &mut &[u8], hash_len: usize) -> sc_cli::Result<T> {
+	*key = key.get(hash_len..).ok_or("A storage key is too short")?;
+	T::decode(key).map_err(|e| sc_cli::Error::Input(format!("Storage key can't be decoded: {e}")))
+}
+
+/// The native balances of an account, or its balance of an asset.
+#[derive(Default)]
+struct Row {
+	account: String,
+	asset: Option<String>,
+	free: Balance,
+	reserved: Balance,
+	frozen: Balance,
+	staked: Balance,
+	unvested: Balance,
+	/// The pool the account is a member of, and its points in it.
+	pool: Option<(u32, Balance)>,
+}
+
+impl Row {
+	/// The values of the columns, empty where they don't apply.
+	fn values(&self) -> [String; COLUMNS.len()] {
+		let native = |balance: Balance| match self.asset {
+			Some(_) => String::new(),
+			None => balance.to_string(),
+		};
+		[
+			self.account.clone(),
+			self.asset.clone().unwrap_or_default(),
+			self.free.to_string(),
+			native(self.reserved),
+			native(self.frozen),
+			native(self.staked),
+			native(self.unvested),
+			self.pool.map(|(id, _)| id.to_string()).unwrap_or_default(),
+			self.pool.map(|(_, points)| points.to_string()).unwrap_or_default(),
+		]
+	}
+}
+
+/// Writes rows as they come.
+struct Snapshot<W: Write> {
+	output: W,
+	format: Format,
+	rows: usize,
+}
+
+impl<W: Write> Snapshot<W> {
+	fn new(mut output: W, format: Format) -> std::io::Result<Self> {
+		match format {
+			Format::Csv => writeln!(output, "{}", COLUMNS.join(","))?,
+			Format::Json => write!(output, "[")?,
+		}
+		Ok(Self { output, format, rows: 0 })
+	}
+
+	fn write(&mut self, row: &Row) -> std::io::Result<()> {
+		let values = row.values();
+		match self.format {
+			Format::Csv => writeln!(self.output, "{}", values.join(","))?,
+			Format::Json => {
+				let object = COLUMNS
+					.iter()
+					.zip(values)
+					.filter(|(_, value)| !value.is_empty())
+					.map(|(column, value)| (column.to_string(), value.into()))
+					.collect::<serde_json::Map<_, _>>();
+				let separator = if self.rows == 0 { "" } else { "," };
+				write!(self.output, "{separator}\n  {}", serde_json::Value::Object(object))?;
+			},
+		}
+		self.rows += 1;
+		Ok(())
+	}
+
+	fn finish(mut self) -> std::io::Result<()> {
+		if let Format::Json = self.format {
+			writeln!(self.output, "\n]")?;
+		}
+		self.output.flush()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rows() -> [Row; 2] {
+		[
+			Row {
+				account: "alice".into(),
+				free: 10,
+				staked: 5,
+				pool: Some((1, 3)),
+				..Default::default()
+			},
+			Row {
+				account: "bob".into(),
+				asset: Some("Assets:7".into()),
+				free: 2,
+				..Default::default()
+			},
+		]
+	}
+
+	fn snapshot(format: Format) -> String {
+		let mut output = Vec::new();
+		let mut snapshot = Snapshot::new(&mut output, format).unwrap();
+		for row in rows() {
+			snapshot.write(&row).unwrap();
+		}
+		snapshot.finish().unwrap();
+		String::from_utf8(output).unwrap()
+	}
+
+	#[test]
+	fn rows_are_written_as_csv() {
+		assert_eq!(
+			snapshot(Format::Csv),
+			"account,asset,free,reserved,frozen,staked,unvested,pool_id,pool_points\n\
+			 alice,,10,0,0,5,0,1,3\n\
+			 bob,Assets:7,2,,,,,,\n",
+		);
+	}
+
+	#[test]
+	fn rows_are_written_as_json() {
+		let rows: serde_json::Value = serde_json::from_str(&snapshot(Format::Json)).unwrap();
+		assert_eq!(
+			rows,
+			serde_json::json!([
+				{
+					"account": "alice",
+					"free": "10",
+					"reserved": "0",
+					"frozen": "0",
+					"staked": "5",
+					"unvested": "0",
+					"pool_id": "1",
+					"pool_points": "3",
+				},
+				{ "account": "bob", "asset": "Assets:7", "free": "2" },
+			]),
+		);
+	}
+}
@@ -0,0 +1,479 @@
+//! `nativex fork-off` turns the state of a live chain into the raw chain spec of a testnet that
+//! dev authorities start producing blocks on right away, to rehearse runtime upgrades on real
+//! data.
+//!
+//! The state is edited key by key, so it doesn't need to decode with the native runtime:
+//!
+//! - `Session`, `Babe`, `Grandpa`, `ImOnline` and `AuthorityDiscovery` get the dev authorities
+//!   and keys, and BABE and GRANDPA start over from their first epoch and set.
+//! - `Staking` has the dev authorities as its only validators, bonded with an endowment minted
+//!   for them and locked as bonding locks it, and no longer forces new eras, so the next election
+//!   can't replace them.
+//! - `Sudo` gets a new key, and `System::LastRuntimeUpgrade` is reset so the migrations of the
+//!   runtime run on the first block.
+
+use std::{
+	collections::{BTreeMap, BTreeSet},
+	io::Write,
+	path::PathBuf,
+};
+
+use development_runtime::{Runtime, SessionKeys};
+use pallet_balances::{BalanceLock, Reasons};
+use pallet_staking::{Exposure, Forcing, RewardDestination, StakingLedger, ValidatorPrefs};
+use parity_scale_codec::{Decode, Encode};
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::HeaderBackend;
+use sc_service::Configuration;
+use sp_consensus_grandpa::{AuthorityList, VersionedAuthorityList, GRANDPA_AUTHORITIES_KEY};
+use sp_core::{sr25519, storage::well_known_keys};
+use sp_runtime::{traits::OpaqueKeys, BuildStorage};
+
+use crate::storage_keys::{blake2_128_concat, map_key, twox_64_concat, value_key};
+use common_primitives::{AccountId, Balance};
+use service::chain_spec::{authority_keys_from_seed, get_account_id_from_seed, DummyChainSpec};
+
+type AccountInfo = frame_system::AccountInfo<
+	<Runtime as frame_system::Config>::Index,
+	<Runtime as frame_system::Config>::AccountData,
+>;
+
+/// The id of the lock `pallet_staking` puts on the bonded balance.
+const STAKING_ID: [u8; 8] = *b"staking ";
+
+/// Turn the state of a live chain into the raw chain spec of a testnet run by dev authorities.
+///
+/// The state is the one of the best block of the database, unless `--state` is set.
+#[derive(Debug, clap::Parser)]
+pub struct ForkOffCmd {
+	/// A raw chain spec holding the state to fork, as `export-state` writes it.
+	#[arg(long, value_name = "FILE")]
+	pub state: Option<PathBuf>,
+
+	/// The seed of a dev authority, such as `Alice`. Repeat it for more authorities.
+	#[arg(long = "authority", value_name = "SEED", default_value = "Alice")]
+	pub authorities: Vec<String>,
+
+	/// The new sudo key, the account of the first authority by default.
+	#[arg(long, value_name = "SS58")]
+	pub sudo: Option<AccountId>,
+
+	/// The balance minted for the accounts of each authority and the sudo key.
+	///
+	/// The stash of each authority bonds all of it.
+	#[arg(long, default_value_t = 1_000_000 * development_runtime::DOLLARS)]
+	pub endowment: Balance,
+
+	/// A Wasm runtime to swap in for the one of the state.
+	#[arg(long, value_name = "FILE")]
+	pub runtime: Option<PathBuf>,
+
+	/// Where to write the chain spec, the standard output by default.
+	#[arg(long, short, value_name = "FILE")]
+	pub output: Option<PathBuf>,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+impl ForkOffCmd {
+	/// Run the command.
+	pub fn run(&self, mut config: Configuration) -> sc_cli::Result<()> {
+		let (mut chain_spec, mut storage) = match &self.state {
+			Some(state) => {
+				let chain_spec = DummyChainSpec::from_json_file(state.clone())?;
+				let storage = chain_spec.build_storage()?;
+				(Box::new(chain_spec) as Box<dyn sc_service::ChainSpec>, storage)
+			},
+			None => {
+				let (client, _, _, _task_manager) = service::new_chain_ops(&mut config)?;
+				let best = client.info().best_hash;
+				let storage = sc_service::chain_ops::export_raw_state(client, best)?;
+				(config.chain_spec, storage)
+			},
+		};
+
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|seed| {
+				let (stash, controller, grandpa, babe, im_online, authority_discovery) =
+					authority_keys_from_seed(seed);
+				let keys = SessionKeys { grandpa, babe, im_online, authority_discovery };
+				Authority { stash, controller, keys }
+			})
+			.collect::<Vec<_>>();
+		let fork = Fork {
+			sudo: match &self.sudo {
+				Some(sudo) => sudo.clone(),
+				None => get_account_id_from_seed::<sr25519::Public>(&self.authorities[0]),
+			},
+			authorities,
+			endowment: self.endowment,
+			runtime: self.runtime.as_ref().map(std::fs::read).transpose()?,
+		};
+		fork.apply(&mut storage.top);
+		chain_spec.set_storage(storage);
+
+		let chain_spec = testnet(&chain_spec.as_json(true)?)?;
+		match &self.output {
+			Some(output) => std::fs::write(output, chain_spec)?,
+			None => std::io::stdout().write_all(chain_spec.as_bytes())?,
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for ForkOffCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+/// Give the chain spec of the fork its own name and id, so its database doesn't mix with the
+/// one of the chain it was forked from, and drop the boot nodes of that chain.
+fn testnet(chain_spec: &str) -> sc_cli::Result<String> {
+	let mut chain_spec: serde_json::Value = serde_json::from_str(chain_spec)
+		.map_err(|e| sc_cli::Error::Input(format!("Invalid chain spec: {e}")))?;
+	let name = format!("{} Fork", chain_spec["name"].as_str().unwrap_or_default());
+	let id = format!("{}_fork", chain_spec["id"].as_str().unwrap_or_default());
+	chain_spec["name"] = name.into();
+	chain_spec["id"] = id.into();
+	chain_spec["chainType"] = "Local".into();
+	chain_spec["bootNodes"] = serde_json::json!([]);
+	Ok(serde_json::to_string_pretty(&chain_spec).expect("JSON values serialize"))
+}
+
+/// A dev authority.
+pub struct Authority {
+	pub stash: AccountId,
+	pub controller: AccountId,
+	pub keys: SessionKeys,
+}
+
+/// What to hand a forked state over to.
+pub struct Fork {
+	pub authorities: Vec<Authority>,
+	pub sudo: AccountId,
+	pub endowment: Balance,
+	pub runtime: Option<Vec<u8>>,
+}
+
+impl Fork {
+	/// Hand the top trie of `state` over to the dev authorities and the sudo key.
+	pub fn apply(&self, state: &mut BTreeMap<Vec<u8>, Vec<u8>>) {
+		let mut state = State(state);
+		self.session(&mut state);
+		self.babe(&mut state);
+		self.grandpa(&mut state);
+		// The stashes are endowed before they bond.
+		self.endow(&mut state);
+		self.staking(&mut state);
+
+		state.put(value_key("Sudo", "Key"), &self.sudo);
+		state.remove(&value_key("System", "LastRuntimeUpgrade"));
+		if let Some(runtime) = &self.runtime {
+			state.0.insert(well_known_keys::CODE.to_vec(), runtime.clone());
+		}
+	}
+
+	fn stashes(&self) -> Vec<AccountId> {
+		self.authorities.iter().map(|authority| authority.stash.clone()).collect()
+	}
+
+	fn session(&self, state: &mut State) {
+		state.clear(&value_key("Session", "NextKeys"));
+		state.clear(&value_key("Session", "KeyOwner"));
+		state.remove(&value_key("Session", "QueuedChanged"));
+		state.remove(&value_key("Session", "DisabledValidators"));
+		state.put(value_key("Session", "Validators"), self.stashes());
+		let queued = self
+			.authorities
+			.iter()
+			.map(|authority| (authority.stash.clone(), authority.keys.clone()))
+			.collect::<Vec<_>>();
+		state.put(value_key("Session", "QueuedKeys"), queued);
+
+		for Authority { stash, keys, .. } in &self.authorities {
+			state.put(map_key("Session", "NextKeys", &[twox_64_concat(stash)]), keys);
+			for id in SessionKeys::key_ids() {
+				let owner = twox_64_concat((*id, keys.get_raw(*id)));
+				state.put(map_key("Session", "KeyOwner", &[owner]), stash);
+			}
+		}
+
+		let im_online = self.authorities.iter().map(|authority| authority.keys.im_online.clone());
+		state.put(value_key("ImOnline", "Keys"), im_online.collect::<Vec<_>>());
+		state.clear(&value_key("ImOnline", "ReceivedHeartbeats"));
+		state.clear(&value_key("ImOnline", "AuthoredBlocks"));
+		let discovery = self
+			.authorities
+			.iter()
+			.map(|authority| authority.keys.authority_discovery.clone())
+			.collect::<Vec<_>>();
+		state.put(value_key("AuthorityDiscovery", "Keys"), &discovery);
+		state.put(value_key("AuthorityDiscovery", "NextKeys"), &discovery);
+	}
+
+	/// With no genesis slot, the first block starts the first epoch again, with the authorities
+	/// the node reads from the genesis state.
+	fn babe(&self, state: &mut State) {
+		let authorities = self
+			.authorities
+			.iter()
+			.map(|authority| (authority.keys.babe.clone(), 1u64))
+			.collect::<Vec<_>>();
+		state.put(value_key("Babe", "Authorities"), &authorities);
+		state.put(value_key("Babe", "NextAuthorities"), &authorities);
+		for item in [
+			"GenesisSlot",
+			"CurrentSlot",
+			"EpochIndex",
+			"EpochStart",
+			"SegmentIndex",
+			"Initialized",
+			"AuthorVrfRandomness",
+			"Lateness",
+			"SkippedEpochs",
+		] {
+			state.remove(&value_key("Babe", item));
+		}
+		state.clear(&value_key("Babe", "UnderConstruction"));
+	}
+
+	/// The node starts GRANDPA over from set 0 on a new genesis, and so does the runtime here.
+	fn grandpa(&self, state: &mut State) {
+		let authorities: AuthorityList = self
+			.authorities
+			.iter()
+			.map(|authority| (authority.keys.grandpa.clone(), 1))
+			.collect();
+		// Older runtimes keep the authorities under a well-known key, newer ones in a storage
+		// item: replace whichever the state has.
+		if state.0.contains_key(GRANDPA_AUTHORITIES_KEY) {
+			state.put(
+				GRANDPA_AUTHORITIES_KEY.to_vec(),
+				VersionedAuthorityList::from(authorities.clone()),
+			);
+		}
+		if state.0.contains_key(&value_key("Grandpa", "Authorities")) {
+			state.put(value_key("Grandpa", "Authorities"), &authorities);
+		}
+		state.put(value_key("Grandpa", "CurrentSetId"), 0u64);
+		for item in ["PendingChange", "NextForced", "Stalled", "State"] {
+			state.remove(&value_key("Grandpa", item));
+		}
+	}
+
+	fn staking(&self, state: &mut State) {
+		let era = state.get::<u32>(&value_key("Staking", "CurrentEra")).unwrap_or_default();
+		let era_key = twox_64_concat(era);
+		state.clear(&value_key("Staking", "Validators"));
+		for item in ["ErasStakers", "ErasStakersClipped", "ErasValidatorPrefs"] {
+			state.clear(&map_key("Staking", item, &[era_key.clone()]));
+		}
+
+		for Authority { stash, controller, .. } in &self.authorities {
+			let ledger = StakingLedger::<Runtime> {
+				stash: stash.clone(),
+				total: self.endowment,
+				active: self.endowment,
+				unlocking: Default::default(),
+				claimed_rewards: Default::default(),
+			};
+			let exposure = Exposure::<AccountId, Balance> {
+				total: self.endowment,
+				own: self.endowment,
+				others: vec![],
+			};
+			let stash_key = twox_64_concat(stash);
+			let bonded = map_key("Staking", "Bonded", &[stash_key.clone()]);
+			let old_controller = state.get::<AccountId>(&bonded);
+			// A ledger is keyed by its controller: drop the one of the live controller, or it
+			// would still name the stash.
+			if let Some(old_controller) = old_controller.as_ref().filter(|old| *old != controller) {
+				state.remove(&map_key("Staking", "Ledger", &[blake2_128_concat(old_controller)]));
+			}
+			self.lock_bond(state, stash, old_controller.is_some());
+			state.put(bonded, controller);
+			state.put(map_key("Staking", "Ledger", &[blake2_128_concat(controller)]), ledger);
+			state.put(
+				map_key("Staking", "Payee", &[stash_key.clone()]),
+				RewardDestination::<AccountId>::Staked,
+			);
+			state.put(
+				map_key("Staking", "Validators", &[stash_key.clone()]),
+				ValidatorPrefs::default(),
+			);
+			for item in ["ErasStakers", "ErasStakersClipped"] {
+				let key = map_key("Staking", item, &[era_key.clone(), stash_key.clone()]);
+				state.put(key, &exposure);
+			}
+			state.put(
+				map_key("Staking", "ErasValidatorPrefs", &[era_key.clone(), stash_key]),
+				ValidatorPrefs::default(),
+			);
+		}
+
+		let count = self.authorities.len() as u32;
+		let total = self.endowment * Balance::from(count);
+		state.put(value_key("Staking", "CounterForValidators"), count);
+		state.put(map_key("Staking", "ErasTotalStake", &[era_key]), total);
+		state.put(value_key("Staking", "Invulnerables"), self.stashes());
+		state.put(value_key("Staking", "ValidatorCount"), count);
+		state.put(value_key("Staking", "MinimumValidatorCount"), 1u32);
+		state.put(value_key("Staking", "ForceEra"), Forcing::ForceNone);
+	}
+
+	/// Lock the endowment of `stash` as bonding does: with a `staking ` lock that freezes it, a
+	/// consumer reference for the bond unless the stash was bonded already, and one for the
+	/// locks if they are the first of the stash.
+	fn lock_bond(&self, state: &mut State, stash: &AccountId, was_bonded: bool) {
+		let account = map_key("System", "Account", &[blake2_128_concat(stash)]);
+		let locks_key = map_key("Balances", "Locks", &[blake2_128_concat(stash)]);
+		let mut info = state
+			.get::<AccountInfo>(&account)
+			.unwrap_or_else(|| AccountInfo { providers: 1, ..Default::default() });
+		let mut locks = state.get::<Vec<BalanceLock<Balance>>>(&locks_key).unwrap_or_default();
+		if !was_bonded {
+			info.consumers += 1;
+		}
+		if locks.is_empty() {
+			info.consumers += 1;
+		}
+		locks.retain(|lock| lock.id != STAKING_ID);
+		locks.push(BalanceLock { id: STAKING_ID, amount: self.endowment, reasons: Reasons::All });
+		// The runtime has no freezes, so the locks make up all of the frozen balance.
+		info.data.frozen = locks.iter().map(|lock| lock.amount).max().unwrap_or_default();
+		state.put(locks_key, locks);
+		state.put(account, info);
+	}
+
+	/// Mint the endowment for the accounts of the authorities and the sudo key.
+	fn endow(&self, state: &mut State) {
+		let accounts = self
+			.authorities
+			.iter()
+			.flat_map(|authority| [authority.stash.clone(), authority.controller.clone()])
+			.chain([self.sudo.clone()])
+			.collect::<BTreeSet<_>>();
+		for account in &accounts {
+			let key = map_key("System", "Account", &[blake2_128_concat(account)]);
+			let mut info = state
+				.get::<AccountInfo>(&key)
+				.unwrap_or_else(|| AccountInfo { providers: 1, ..Default::default() });
+			info.data.free += self.endowment;
+			state.put(key, info);
+		}
+
+		let key = value_key("Balances", "TotalIssuance");
+		let issuance = state.get::<Balance>(&key).unwrap_or_default();
+		state.put(key, issuance + self.endowment * accounts.len() as Balance);
+	}
+}
+
+/// The top trie of a state.
+struct State<'a>(&'a mut BTreeMap<Vec<u8>, Vec<u8>>);
+
+impl State<'_> {
+	fn get<T: Decode>(&self, key: &[u8]) -> Option<T> {
+		self.0.get(key).and_then(|value| T::decode(&mut &value[..]).ok())
+	}
+
+	fn put(&mut self, key: Vec<u8>, value: impl Encode) {
+		self.0.insert(key, value.encode());
+	}
+
+	fn remove(&mut self, key: &[u8]) {
+		self.0.remove(key);
+	}
+
+	/// Remove every key that starts with `prefix`.
+	fn clear(&mut self, prefix: &[u8]) {
+		self.0.retain(|key, _| !key.starts_with(prefix));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn alice() -> Authority {
+		let (stash, controller, grandpa, babe, im_online, authority_discovery) =
+			authority_keys_from_seed("Alice");
+		let keys = SessionKeys { grandpa, babe, im_online, authority_discovery };
+		Authority { stash, controller, keys }
+	}
+
+	#[test]
+	fn state_is_handed_over_to_dev_authorities() {
+		let live = AccountId::new([7; 32]);
+		let mut top = BTreeMap::new();
+		let mut state = State(&mut top);
+		state.put(map_key("Session", "NextKeys", &[twox_64_concat(&live)]), [0u8; 128]);
+		state.put(value_key("Sudo", "Key"), &live);
+		state.put(value_key("System", "LastRuntimeUpgrade"), (1u32, "nativex"));
+		state.put(value_key("Babe", "GenesisSlot"), 1_000u64);
+		state.put(value_key("Balances", "TotalIssuance"), 100 as Balance);
+		state.put(GRANDPA_AUTHORITIES_KEY.to_vec(), VersionedAuthorityList::from(vec![]));
+
+		// Alice's stash is bonded on the live chain, to another controller.
+		let authority = alice();
+		let stash = blake2_128_concat(&authority.stash);
+		let live_ledger = map_key("Staking", "Ledger", &[blake2_128_concat(&live)]);
+		state.put(map_key("Staking", "Bonded", &[twox_64_concat(&authority.stash)]), &live);
+		state.put(live_ledger.clone(), [0u8; 32]);
+		// The consumer reference of the live bond.
+		let live_info = AccountInfo { providers: 1, consumers: 1, ..Default::default() };
+		state.put(map_key("System", "Account", &[stash.clone()]), live_info);
+
+		let fork = Fork {
+			sudo: authority.controller.clone(),
+			authorities: vec![alice()],
+			endowment: 10,
+			runtime: Some(b"wasm".to_vec()),
+		};
+		fork.apply(&mut top);
+		let state = State(&mut top);
+
+		assert!(!state.0.contains_key(&map_key("Session", "NextKeys", &[twox_64_concat(&live)])));
+		let next_keys = map_key("Session", "NextKeys", &[twox_64_concat(&authority.stash)]);
+		assert_eq!(state.get::<SessionKeys>(&next_keys), Some(authority.keys.clone()));
+		assert_eq!(state.get(&value_key("Sudo", "Key")), Some(authority.controller.clone()));
+		assert!(!state.0.contains_key(&value_key("System", "LastRuntimeUpgrade")));
+		assert!(!state.0.contains_key(&value_key("Babe", "GenesisSlot")));
+		assert_eq!(
+			state.get(&value_key("Babe", "Authorities")),
+			Some(vec![(authority.keys.babe.clone(), 1u64)]),
+		);
+		let grandpa = state.get::<VersionedAuthorityList>(GRANDPA_AUTHORITIES_KEY).unwrap();
+		assert_eq!(AuthorityList::from(grandpa), vec![(authority.keys.grandpa.clone(), 1)]);
+		// Only the stash and the controller, which is the sudo key too, are endowed.
+		assert_eq!(state.get(&value_key("Balances", "TotalIssuance")), Some(120 as Balance));
+		// The stash bonded all of its endowment, to the dev controller.
+		assert!(!state.0.contains_key(&live_ledger));
+		let controller = blake2_128_concat(&authority.controller);
+		let ledger =
+			state.get::<StakingLedger<Runtime>>(&map_key("Staking", "Ledger", &[controller]));
+		assert_eq!(ledger.map(|ledger| ledger.stash), Some(authority.stash.clone()));
+		let locks = map_key("Balances", "Locks", &[stash.clone()]);
+		assert_eq!(
+			state.get::<Vec<BalanceLock<Balance>>>(&locks),
+			Some(vec![BalanceLock { id: STAKING_ID, amount: 10, reasons: Reasons::All }])
+		);
+		let info = state.get::<AccountInfo>(&map_key("System", "Account", &[stash])).unwrap();
+		assert_eq!((info.data.free, info.data.frozen), (10, 10));
+		// One consumer for the bond and one for the lock.
+		assert_eq!((info.providers, info.consumers), (1, 2));
+		assert_eq!(state.0.get(well_known_keys::CODE), Some(&b"wasm".to_vec()));
+	}
+}
@@ -0,0 +1,277 @@
+//! Decoding of extrinsics and storage with the runtime metadata.
+
+use frame_metadata::{
+	PalletStorageMetadata, RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType,
+	StorageHasher,
+};
+use parity_scale_codec::{Compact, Decode, Input};
+use scale_info::{PortableRegistry, TypeDef};
+use serde_json::{json, Map, Value};
+use sp_core::hashing::{blake2_256, twox_128};
+
+use service::indexer::{decode_value, EventsDecoder};
+
+/// The extrinsic format version the runtime uses.
+const EXTRINSIC_VERSION: u8 = 4;
+
+/// Decodes extrinsics, events and storage with the types of one runtime version.
+pub struct Inspector {
+	types: PortableRegistry,
+	address_ty: u32,
+	call_ty: u32,
+	signature_ty: u32,
+	/// Identifier and type of each signed extension, in `SignedExtra` order.
+	signed_extensions: Vec<(String, u32)>,
+	/// Name and storage of each pallet that has storage.
+	storage: Vec<(String, PalletStorageMetadata<scale_info::form::PortableForm>)>,
+	events: EventsDecoder,
+}
+
+impl Inspector {
+	/// Create an inspector from the SCALE-encoded runtime metadata.
+	pub fn new(metadata: &[u8]) -> Result<Self, String> {
+		let events = EventsDecoder::new(metadata)?;
+		let RuntimeMetadataPrefixed(_, RuntimeMetadata::V14(metadata)) =
+			RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+				.map_err(|e| format!("Runtime metadata can't be decoded: {e}"))?
+		else {
+			return Err("Runtime metadata version is not supported".into())
+		};
+
+		let extrinsic_ty = metadata
+			.types
+			.resolve(metadata.extrinsic.ty.id)
+			.ok_or("The extrinsic type is not in the metadata")?;
+		let param = |name: &str| {
+			extrinsic_ty
+				.type_params
+				.iter()
+				.find(|param| param.name == name)
+				.and_then(|param| param.ty.as_ref())
+				.map(|ty| ty.id)
+				.ok_or_else(|| format!("The extrinsic type has no `{name}` parameter"))
+		};
+		let (address_ty, call_ty, signature_ty) =
+			(param("Address")?, param("Call")?, param("Signature")?);
+		let signed_extensions = metadata
+			.extrinsic
+			.signed_extensions
+			.iter()
+			.map(|extension| (extension.identifier.clone(), extension.ty.id))
+			.collect();
+		let storage = metadata
+			.pallets
+			.into_iter()
+			.filter_map(|pallet| Some((pallet.name, pallet.storage?)))
+			.collect();
+
+		Ok(Self {
+			types: metadata.types,
+			address_ty,
+			call_ty,
+			signature_ty,
+			signed_extensions,
+			storage,
+			events,
+		})
+	}
+
+	/// Decode an extrinsic, with its length prefix.
+	///
+	/// The call is decoded the way `nativex tx build` describes calls.
+	pub fn extrinsic(&self, extrinsic: &[u8]) -> Result<Value, String> {
+		let input = &mut &extrinsic[..];
+		Compact::<u32>::decode(input).map_err(|e| format!("Invalid length prefix: {e}"))?;
+		let version = input.read_byte().map_err(|e| format!("Missing version: {e}"))?;
+		if version & 0b0111_1111 != EXTRINSIC_VERSION {
+			return Err(format!("Extrinsic version {} is not supported", version & 0b0111_1111))
+		}
+
+		let mut decoded = Map::new();
+		decoded.insert("hash".into(), array_bytes::bytes2hex("0x", blake2_256(extrinsic)).into());
+		if version & 0b1000_0000 != 0 {
+			decoded.insert("signer".into(), self.value(self.address_ty, input, "signer")?);
+			decoded.insert("signature".into(), self.value(self.signature_ty, input, "signature")?);
+			let mut extra = Map::new();
+			for (identifier, ty) in &self.signed_extensions {
+				extra.insert(identifier.clone(), self.value(*ty, input, identifier)?);
+			}
+			decoded.insert("signedExtra".into(), extra.into());
+		}
+		decoded.insert("call".into(), self.call(input)?);
+		if !input.is_empty() {
+			return Err(format!("{} bytes are left after the extrinsic", input.len()))
+		}
+		Ok(decoded.into())
+	}
+
+	/// Decode the value of `System::Events`.
+	pub fn events(&self, events: &[u8]) -> Result<Value, String> {
+		let events = self
+			.events
+			.decode(events)
+			.map_err(|e| format!("`System::Events` can't be decoded: {e}"))?;
+		Ok(events
+			.into_iter()
+			.map(|event| {
+				json!({
+					"phase": format!("{:?}", event.phase),
+					"pallet": event.pallet,
+					"event": event.event,
+					"fields": event.fields,
+				})
+			})
+			.collect())
+	}
+
+	/// Decode a storage key into its pallet, item and map keys, and its value if given.
+	///
+	/// A key that stops after some map keys, as a prefix to iterate over, decodes those only.
+	pub fn storage(&self, key: &[u8], value: Option<&[u8]>) -> Result<Value, String> {
+		if key.len() < 32 {
+			return Err("A storage key is at least 32 bytes".into())
+		}
+		let (pallet, storage) = self
+			.storage
+			.iter()
+			.find(|(_, storage)| twox_128(storage.prefix.as_bytes()) == key[..16])
+			.ok_or("The key is not in the storage of any pallet")?;
+		let entry = storage
+			.entries
+			.iter()
+			.find(|entry| twox_128(entry.name.as_bytes()) == key[16..32])
+			.ok_or_else(|| format!("The key is not in the storage of {pallet}"))?;
+
+		let input = &mut &key[32..];
+		let (keys, value_ty) = match &entry.ty {
+			StorageEntryType::Plain(value) => (Vec::new(), value.id),
+			StorageEntryType::Map { hashers, key, value } => {
+				let key_tys = match self.types.resolve(key.id).map(|ty| &ty.type_def) {
+					Some(TypeDef::Tuple(tuple)) if hashers.len() > 1 =>
+						tuple.fields.iter().map(|field| field.id).collect(),
+					_ => vec![key.id],
+				};
+				let mut keys = Vec::new();
+				for (hasher, ty) in hashers.iter().zip(key_tys) {
+					if input.is_empty() {
+						break
+					}
+					keys.push(self.map_key(hasher, ty, input)?);
+				}
+				(keys, value.id)
+			},
+		};
+		if !input.is_empty() {
+			return Err(format!("{} bytes are left after the key", input.len()))
+		}
+
+		let mut decoded = Map::new();
+		decoded.insert("pallet".into(), pallet.clone().into());
+		decoded.insert("item".into(), entry.name.clone().into());
+		decoded.insert("keys".into(), keys.into());
+		if let Some(value) = value {
+			let input = &mut &value[..];
+			decoded.insert("value".into(), self.value(value_ty, input, "value")?);
+			if !input.is_empty() {
+				return Err(format!("{} bytes are left after the value", input.len()))
+			}
+		}
+		Ok(decoded.into())
+	}
+
+	/// Decode a map key hashed with `hasher`; keys that can't be recovered stay hex-encoded.
+	fn map_key(
+		&self,
+		hasher: &StorageHasher,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<Value, String> {
+		let (hash_len, concat) = match hasher {
+			StorageHasher::Blake2_128Concat => (16, true),
+			StorageHasher::Twox64Concat => (8, true),
+			StorageHasher::Identity => (0, true),
+			StorageHasher::Blake2_128 | StorageHasher::Twox128 => (16, false),
+			StorageHasher::Blake2_256 | StorageHasher::Twox256 => (32, false),
+		};
+		if input.len() < hash_len {
+			return Err("The key is too short for its hashers".into())
+		}
+		let (hash, rest) = input.split_at(hash_len);
+		*input = rest;
+		if concat {
+			self.value(ty, input, "map key")
+		} else {
+			Ok(array_bytes::bytes2hex("0x", hash).into())
+		}
+	}
+
+	/// Decode a call to `{ pallet, call, args }`.
+	fn call(&self, input: &mut &[u8]) -> Result<Value, String> {
+		let call = self.value(self.call_ty, input, "call")?;
+		let (pallet, call) = single_variant(call).ok_or("The call is not a pallet call")?;
+		let (call, args) = match call {
+			Value::String(call) => (call, Value::Object(Map::new())),
+			call => single_variant(call).ok_or("The call is not a pallet call")?,
+		};
+		Ok(json!({ "pallet": pallet, "call": call, "args": args }))
+	}
+
+	fn value(&self, ty: u32, input: &mut &[u8], what: &str) -> Result<Value, String> {
+		decode_value(&self.types, ty, input).map_err(|e| format!("Invalid {what}: {e}"))
+	}
+}
+
+/// The name and fields of a variant decoded as `{ Name: fields }`.
+fn single_variant(value: Value) -> Option<(String, Value)> {
+	match value {
+		Value::Object(map) if map.len() == 1 => map.into_iter().next(),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use development_runtime::{Runtime, RuntimeCall, SystemCall, UncheckedExtrinsic};
+	use parity_scale_codec::Encode;
+	use sp_core::crypto::Ss58Codec;
+	use sp_keyring::AccountKeyring;
+
+	fn inspector() -> Inspector {
+		Inspector::new(&Runtime::metadata().encode()).unwrap()
+	}
+
+	#[test]
+	fn unsigned_extrinsic_decodes_to_its_call() {
+		let call = RuntimeCall::System(SystemCall::remark { remark: b"native".to_vec() });
+		let extrinsic = UncheckedExtrinsic::new_unsigned(call).encode();
+		let decoded = inspector().extrinsic(&extrinsic).unwrap();
+		assert_eq!(
+			decoded["call"],
+			json!({ "pallet": "System", "call": "remark", "args": { "remark": "0x6e6174697665" } }),
+		);
+		assert!(decoded.get("signedExtra").is_none());
+		assert!(inspector().extrinsic(&[&extrinsic[..], &[0]].concat()).is_err());
+	}
+
+	#[test]
+	fn storage_keys_decode_to_their_item() {
+		let alice = AccountKeyring::Alice.to_account_id();
+		let key = frame_system::Account::<Runtime>::hashed_key_for(&alice);
+		let info = frame_system::AccountInfo::<
+			<Runtime as frame_system::Config>::Index,
+			<Runtime as frame_system::Config>::AccountData,
+		> {
+			nonce: 3,
+			..Default::default()
+		};
+		let decoded = inspector().storage(&key, Some(&info.encode())).unwrap();
+		assert_eq!(decoded["pallet"], "System");
+		assert_eq!(decoded["item"], "Account");
+		assert_eq!(decoded["keys"], json!([alice.to_ss58check()]));
+		assert_eq!(decoded["value"]["nonce"], 3);
+
+		// The prefix of the map, without any key.
+		assert_eq!(inspector().storage(&key[..32], None).unwrap()["keys"], json!([]));
+	}
+}
@@ -0,0 +1,303 @@
+//! `nativex inspect` decodes blocks, extrinsics and storage of the local database with the
+//! metadata of the runtime that produced them, so no node needs to run.
+
+mod decode;
+
+use parity_scale_codec::{Decode, Encode};
+use sc_cli::{CliConfiguration, ImportParams, SharedParams};
+use sc_client_api::{BlockBackend, HeaderBackend, StorageKey, StorageProvider};
+use serde_json::{json, Value};
+use sp_consensus_babe::{
+	digests::{NextConfigDescriptor, PreDigest},
+	ConsensusLog as BabeConsensusLog, BABE_ENGINE_ID,
+};
+use sp_consensus_grandpa::{
+	AuthorityList, ConsensusLog as GrandpaConsensusLog, ScheduledChange, GRANDPA_ENGINE_ID,
+};
+use sp_core::{crypto::Ss58Codec, H256};
+use sp_runtime::{
+	traits::{Block as BlockT, Header as HeaderT},
+	ConsensusEngineId, DigestItem,
+};
+
+use common_primitives::BlockNumber;
+use decode::Inspector;
+use service::client::Client;
+
+/// Decode blocks, extrinsics and storage of the local database.
+#[derive(Debug, clap::Parser)]
+pub struct InspectCmd {
+	#[command(subcommand)]
+	pub command: InspectSubcommand,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub import_params: ImportParams,
+}
+
+/// What to decode.
+#[derive(Debug, clap::Subcommand)]
+pub enum InspectSubcommand {
+	/// Decode the header, digests, extrinsics and events of a block.
+	Block {
+		/// The number or the hash of the block.
+		#[arg(value_name = "NUMBER_OR_HASH")]
+		block: String,
+	},
+	/// Decode an extrinsic with the runtime of the best block.
+	Extrinsic {
+		/// The hex-encoded extrinsic, or `-` to read it from the standard input.
+		#[arg(value_name = "HEX")]
+		extrinsic: String,
+	},
+	/// Decode a hex-encoded storage key into its pallet, item and map keys.
+	///
+	/// The value is decoded as well, the given one or the one stored at `--at`.
+	Storage {
+		/// The hex-encoded storage key.
+		#[arg(value_name = "KEY")]
+		key: String,
+		/// The hex-encoded value to decode instead of the stored one.
+		#[arg(value_name = "VALUE")]
+		value: Option<String>,
+		/// The number or the hash of the block to read the value at, the best block by default.
+		#[arg(long, value_name = "NUMBER_OR_HASH", conflicts_with = "value")]
+		at: Option<String>,
+	},
+}
+
+impl InspectCmd {
+	/// Run the command and print what it decoded as JSON.
+	pub fn run(&self, client: &Client) -> sc_cli::Result<()> {
+		let decoded = match &self.command {
+			InspectSubcommand::Block { block } => {
+				let hash = block_hash(client, block)?;
+				inspect_block(client, hash)?
+			},
+			InspectSubcommand::Extrinsic { extrinsic } => {
+				let extrinsic = crate::tx::read_hex(extrinsic)?;
+				inspector(client, client.info().best_hash)?.extrinsic(&extrinsic).map_err(input)?
+			},
+			InspectSubcommand::Storage { key, value, at } => {
+				let key = crate::tx::read_hex(key)?;
+				let at = match at {
+					Some(at) => block_hash(client, at)?,
+					None => client.info().best_hash,
+				};
+				let value = match value {
+					Some(value) => Some(crate::tx::read_hex(value)?),
+					None => client
+						.storage(at, &StorageKey(key.clone()))?
+						.map(|value| value.0),
+				};
+				inspector(client, at)?.storage(&key, value.as_deref()).map_err(input)?
+			},
+		};
+		println!("{}", serde_json::to_string_pretty(&decoded).expect("JSON values serialize"));
+		Ok(())
+	}
+}
+
+impl CliConfiguration for InspectCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn import_params(&self) -> Option<&ImportParams> {
+		Some(&self.import_params)
+	}
+}
+
+fn input(error: String) -> sc_cli::Error {
+	sc_cli::Error::Input(error)
+}
+
+/// The hash of the block `block` names by number or hash.
+pub(crate) fn block_hash(client: &Client, block: &str) -> sc_cli::Result<H256> {
+	if let Ok(number) = block.parse::<BlockNumber>() {
+		return client
+			.hash(number)?
+			.ok_or_else(|| input(format!("The database has no block #{number}")))
+	}
+	let hash = block
+		.parse::<H256>()
+		.map_err(|_| input(format!("`{block}` is neither a block number nor a block hash")))?;
+	match client.header(hash)? {
+		Some(_) => Ok(hash),
+		None => Err(input(format!("The database has no block {hash:?}"))),
+	}
+}
+
+/// An inspector with the metadata of the runtime at block `at`.
+fn inspector(client: &Client, at: H256) -> sc_cli::Result<Inspector> {
+	let metadata = client.metadata(at).map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
+	Inspector::new(&metadata).map_err(input)
+}
+
+fn inspect_block(client: &Client, hash: H256) -> sc_cli::Result<Value> {
+	let block = client.block(hash)?.ok_or_else(|| input(format!("No body for {hash:?}")))?.block;
+	let (header, extrinsics) = block.deconstruct();
+
+	// The extrinsics of a block are those of the runtime of its parent, which differs from the
+	// one of the block when the block upgrades it.
+	let runtime = if *header.number() == 0 { hash } else { *header.parent_hash() };
+	let inspector = inspector(client, runtime)?;
+	let extrinsics = extrinsics
+		.iter()
+		.map(|extrinsic| inspector.extrinsic(&extrinsic.encode()))
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(input)?;
+	let events = match client.storage(hash, &StorageKey(events_key()))? {
+		Some(events) => inspector.events(&events.0).map_err(input)?,
+		None => json!([]),
+	};
+
+	Ok(json!({
+		"number": header.number(),
+		"hash": format!("{hash:?}"),
+		"parentHash": format!("{:?}", header.parent_hash()),
+		"stateRoot": format!("{:?}", header.state_root()),
+		"extrinsicsRoot": format!("{:?}", header.extrinsics_root()),
+		"digest": header.digest().logs().iter().map(digest_item).collect::<Vec<_>>(),
+		"extrinsics": extrinsics,
+		"events": events,
+	}))
+}
+
+/// The storage key of `System::Events`.
+fn events_key() -> Vec<u8> {
+	[sp_core::twox_128(b"System"), sp_core::twox_128(b"Events")].concat()
+}
+
+/// Decode a digest item, with the types of the BABE and GRANDPA ones.
+fn digest_item(item: &DigestItem) -> Value {
+	match item {
+		DigestItem::PreRuntime(BABE_ENGINE_ID, data) => match PreDigest::decode(&mut &data[..]) {
+			Ok(pre_digest) => json!({ "preRuntime": {
+				"engine": "BABE",
+				"slot": u64::from(pre_digest.slot()),
+				"authorityIndex": pre_digest.authority_index(),
+				"kind": match pre_digest {
+					PreDigest::Primary(_) => "primary",
+					PreDigest::SecondaryPlain(_) => "secondaryPlain",
+					PreDigest::SecondaryVRF(_) => "secondaryVRF",
+				},
+			}}),
+			Err(_) => opaque("preRuntime", BABE_ENGINE_ID, data),
+		},
+		DigestItem::Consensus(BABE_ENGINE_ID, data) =>
+			match BabeConsensusLog::decode(&mut &data[..]) {
+				Ok(log) => json!({ "consensus": { "engine": "BABE", "log": babe_log(log) } }),
+				Err(_) => opaque("consensus", BABE_ENGINE_ID, data),
+			},
+		DigestItem::Consensus(GRANDPA_ENGINE_ID, data) =>
+			match GrandpaConsensusLog::<BlockNumber>::decode(&mut &data[..]) {
+				Ok(log) => json!({ "consensus": { "engine": "GRANDPA", "log": grandpa_log(log) } }),
+				Err(_) => opaque("consensus", GRANDPA_ENGINE_ID, data),
+			},
+		DigestItem::PreRuntime(engine, data) => opaque("preRuntime", *engine, data),
+		DigestItem::Consensus(engine, data) => opaque("consensus", *engine, data),
+		DigestItem::Seal(engine, signature) => json!({ "seal": {
+			"engine": engine_name(*engine),
+			"signature": array_bytes::bytes2hex("0x", signature),
+		}}),
+		DigestItem::Other(data) => json!({ "other": array_bytes::bytes2hex("0x", data) }),
+		DigestItem::RuntimeEnvironmentUpdated => json!("runtimeEnvironmentUpdated"),
+	}
+}
+
+fn babe_log(log: BabeConsensusLog) -> Value {
+	match log {
+		BabeConsensusLog::NextEpochData(epoch) => json!({ "nextEpochData": {
+			"authorities": epoch
+				.authorities
+				.iter()
+				.map(|(id, weight)| json!([id.to_ss58check(), weight]))
+				.collect::<Vec<_>>(),
+			"randomness": array_bytes::bytes2hex("0x", epoch.randomness),
+		}}),
+		BabeConsensusLog::OnDisabled(index) => json!({ "onDisabled": index }),
+		BabeConsensusLog::NextConfigData(NextConfigDescriptor::V1 { c, allowed_slots }) =>
+			json!({ "nextConfigData": {
+				"c": [c.0, c.1],
+				"allowedSlots": format!("{allowed_slots:?}"),
+			}}),
+	}
+}
+
+fn grandpa_log(log: GrandpaConsensusLog<BlockNumber>) -> Value {
+	let change = |change: ScheduledChange<BlockNumber>| {
+		json!({ "nextAuthorities": authorities(&change.next_authorities), "delay": change.delay })
+	};
+	match log {
+		GrandpaConsensusLog::ScheduledChange(scheduled) =>
+			json!({ "scheduledChange": change(scheduled) }),
+		GrandpaConsensusLog::ForcedChange(median, forced) =>
+			json!({ "forcedChange": { "median": median, "change": change(forced) } }),
+		GrandpaConsensusLog::OnDisabled(index) => json!({ "onDisabled": index }),
+		GrandpaConsensusLog::Pause(delay) => json!({ "pause": delay }),
+		GrandpaConsensusLog::Resume(delay) => json!({ "resume": delay }),
+	}
+}
+
+fn authorities(authorities: &AuthorityList) -> Value {
+	authorities.iter().map(|(id, weight)| json!([id.to_ss58check(), weight])).collect()
+}
+
+/// A digest item of an engine without known types, hex-encoded.
+fn opaque(kind: &str, engine: ConsensusEngineId, data: &[u8]) -> Value {
+	json!({ kind: { "engine": engine_name(engine), "data": array_bytes::bytes2hex("0x", data) } })
+}
+
+fn engine_name(engine: ConsensusEngineId) -> String {
+	match engine {
+		BABE_ENGINE_ID => "BABE".into(),
+		GRANDPA_ENGINE_ID => "GRANDPA".into(),
+		engine => String::from_utf8_lossy(&engine).into_owned(),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_consensus_babe::digests::SecondaryPlainPreDigest;
+	use sp_keyring::Ed25519Keyring;
+
+	#[test]
+	fn consensus_digests_are_decoded() {
+		let pre_digest = PreDigest::SecondaryPlain(SecondaryPlainPreDigest {
+			authority_index: 2,
+			slot: 42.into(),
+		});
+		assert_eq!(
+			digest_item(&DigestItem::PreRuntime(BABE_ENGINE_ID, pre_digest.encode())),
+			json!({ "preRuntime": {
+				"engine": "BABE",
+				"slot": 42,
+				"authorityIndex": 2,
+				"kind": "secondaryPlain",
+			}}),
+		);
+
+		let alice = Ed25519Keyring::Alice.public();
+		let log = GrandpaConsensusLog::<BlockNumber>::ScheduledChange(ScheduledChange {
+			next_authorities: vec![(alice.into(), 1)],
+			delay: 5,
+		});
+		assert_eq!(
+			digest_item(&DigestItem::Consensus(GRANDPA_ENGINE_ID, log.encode())),
+			json!({ "consensus": { "engine": "GRANDPA", "log": { "scheduledChange": {
+				"nextAuthorities": [[alice.to_ss58check(), 1]],
+				"delay": 5,
+			}}}}),
+		);
+		assert_eq!(
+			digest_item(&DigestItem::Seal(*b"fron", vec![1, 2])),
+			json!({ "seal": { "engine": "fron", "signature": "0x0102" } }),
+		);
+	}
+}
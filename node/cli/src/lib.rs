@@ -1,6 +1,12 @@
 //mod benchmarking;
 mod cli;
 mod command;
+mod config;
+mod export_balances;
+mod fork_off;
+mod inspect;
+mod storage_keys;
+mod tx;
 
 //pub use benchmarking::*;
 pub use cli::*;
@@ -0,0 +1,26 @@
+//! Keys of runtime storage, for the commands that read or write raw state.
+
+use parity_scale_codec::Encode;
+use sp_core::hashing::{blake2_128, twox_128, twox_64};
+
+/// The key of storage value `pallet::item`, also the prefix of the keys of map `pallet::item`.
+pub fn value_key(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// The key of map `pallet::item` at `hashed_keys`.
+pub fn map_key(pallet: &str, item: &str, hashed_keys: &[Vec<u8>]) -> Vec<u8> {
+	[&[value_key(pallet, item)], hashed_keys].concat().concat()
+}
+
+/// `key` hashed with `Twox64Concat`.
+pub fn twox_64_concat(key: impl Encode) -> Vec<u8> {
+	let key = key.encode();
+	[&twox_64(&key)[..], &key].concat()
+}
+
+/// `key` hashed with `Blake2_128Concat`.
+pub fn blake2_128_concat(key: impl Encode) -> Vec<u8> {
+	let key = key.encode();
+	[&blake2_128(&key)[..], &key].concat()
+}
@@ -0,0 +1,357 @@
+//! Encoding of calls described in JSON with the runtime metadata.
+//!
+//! Values are written the way the indexer decodes them: accounts are SS58 or hex, byte arrays
+//! and vectors are hex, integers are numbers or decimal strings, enum variants without fields
+//! are their name and the others `{ "Variant": fields }`.
+
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed};
+use parity_scale_codec::{Compact, Decode, Encode};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::Value;
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	U256,
+};
+
+/// A call of a pallet, as `nativex tx build` reads it.
+#[derive(serde::Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct CallDescription {
+	/// Name of the pallet, as in `construct_runtime!`.
+	pub pallet: String,
+	/// Name of the call.
+	pub call: String,
+	/// The arguments of the call, by name.
+	#[serde(default)]
+	pub args: Value,
+}
+
+/// Encodes calls with the types of one runtime version.
+pub struct CallEncoder {
+	types: PortableRegistry,
+	/// Index and call enum type of each pallet with calls.
+	pallets: Vec<(String, u8, u32)>,
+}
+
+impl CallEncoder {
+	/// Create an encoder from the SCALE-encoded runtime metadata.
+	pub fn new(metadata: &[u8]) -> Result<Self, String> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|_| "Runtime metadata can't be decoded")?;
+		let RuntimeMetadata::V14(metadata) = metadata.1 else {
+			return Err("Runtime metadata version is not supported".into())
+		};
+		let pallets = metadata
+			.pallets
+			.iter()
+			.filter_map(|pallet| {
+				pallet.calls.as_ref().map(|calls| (pallet.name.clone(), pallet.index, calls.ty.id))
+			})
+			.collect();
+		Ok(Self { types: metadata.types, pallets })
+	}
+
+	/// Encode `call` as a `RuntimeCall`: the pallet index, then the call variant and its
+	/// arguments.
+	pub fn encode(&self, call: &CallDescription) -> Result<Vec<u8>, String> {
+		let (_, pallet_index, calls_ty) = self
+			.pallets
+			.iter()
+			.find(|(name, _, _)| *name == call.pallet)
+			.ok_or_else(|| format!("Pallet `{}` has no calls", call.pallet))?;
+		let variants = match self.types.resolve(*calls_ty).map(|ty| &ty.type_def) {
+			Some(TypeDef::Variant(variants)) => &variants.variants,
+			_ => return Err(format!("Calls of `{}` are not an enum", call.pallet)),
+		};
+		let variant = variants
+			.iter()
+			.find(|variant| variant.name == call.call)
+			.ok_or_else(|| format!("`{}` has no call `{}`", call.pallet, call.call))?;
+
+		let mut output = vec![*pallet_index, variant.index];
+		ValueEncoder { types: &self.types }
+			.fields(&variant.fields, &call.args, &mut output)
+			.map_err(|e| format!("Invalid arguments of `{}::{}`: {e}", call.pallet, call.call))?;
+		Ok(output)
+	}
+}
+
+/// Encodes JSON values to any type of a registry.
+struct ValueEncoder<'a> {
+	types: &'a PortableRegistry,
+}
+
+impl ValueEncoder<'_> {
+	/// Encode `value` as type `ty`.
+	fn value(&self, ty: u32, value: &Value, output: &mut Vec<u8>) -> Result<(), String> {
+		let ty = self.types.resolve(ty).ok_or("Type is not in the metadata")?;
+		if ty.path.segments.last().map(String::as_str) == Some("AccountId32") {
+			let account = match value {
+				Value::String(account) if account.starts_with("0x") =>
+					<[u8; 32]>::try_from(hex(account)?)
+						.map_err(|_| format!("`{account}` is not 32 bytes"))?,
+				Value::String(account) => AccountId32::from_ss58check(account)
+					.map_err(|e| format!("`{account}` is not an account: {e:?}"))?
+					.into(),
+				_ => return Err(format!("Expected an account, got `{value}`")),
+			};
+			account.encode_to(output);
+			return Ok(())
+		}
+
+		match &ty.type_def {
+			TypeDef::Composite(composite) => self.fields(&composite.fields, value, output),
+			TypeDef::Variant(variants) => {
+				let (name, fields) = match value {
+					Value::String(name) => (name, None),
+					Value::Object(object) if object.len() == 1 => object
+						.iter()
+						.next()
+						.map(|(name, fields)| (name, Some(fields)))
+						.expect("object has one entry; qed"),
+					_ => return Err(format!("Expected a variant, got `{value}`")),
+				};
+				let variant = variants
+					.variants
+					.iter()
+					.find(|variant| variant.name == *name)
+					.ok_or_else(|| format!("Unknown variant `{name}`"))?;
+				output.push(variant.index);
+				match fields {
+					Some(fields) => self.fields(&variant.fields, fields, output),
+					None if variant.fields.is_empty() => Ok(()),
+					None => Err(format!("Variant `{name}` has fields")),
+				}
+			},
+			TypeDef::Sequence(sequence) => {
+				let item = sequence.type_param.id;
+				if let (true, Value::String(bytes)) = (self.is_byte(item), value) {
+					hex(bytes)?.encode_to(output);
+					return Ok(())
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected an array: `{value}`"))?;
+				Compact(items.len() as u32).encode_to(output);
+				items.iter().try_for_each(|value| self.value(item, value, output))
+			},
+			TypeDef::Array(array) => {
+				let item = array.type_param.id;
+				if let (true, Value::String(bytes)) = (self.is_byte(item), value) {
+					let bytes = hex(bytes)?;
+					if bytes.len() != array.len as usize {
+						return Err(format!("Expected {} bytes, got {}", array.len, bytes.len()))
+					}
+					output.extend(bytes);
+					return Ok(())
+				}
+				let items =
+					value.as_array().ok_or_else(|| format!("Expected an array: `{value}`"))?;
+				if items.len() != array.len as usize {
+					return Err(format!("Expected {} items, got {}", array.len, items.len()))
+				}
+				items.iter().try_for_each(|value| self.value(item, value, output))
+			},
+			TypeDef::Tuple(tuple) => {
+				let items = match value {
+					Value::Null if tuple.fields.is_empty() => return Ok(()),
+					Value::Array(items) if items.len() == tuple.fields.len() => items,
+					_ => return Err(format!("Expected {} values: `{value}`", tuple.fields.len())),
+				};
+				tuple
+					.fields
+					.iter()
+					.zip(items)
+					.try_for_each(|(field, value)| self.value(field.id, value, output))
+			},
+			TypeDef::Primitive(primitive) => Self::primitive(primitive, value, output),
+			TypeDef::Compact(_) => {
+				Compact(integer::<u128>(value)?).encode_to(output);
+				Ok(())
+			},
+			TypeDef::BitSequence(_) => Err("Bit sequences are not supported".into()),
+		}
+	}
+
+	/// Encode the fields of a struct or an enum variant from an object if they are named, from
+	/// the value itself if there is a single unnamed field, from an array otherwise.
+	fn fields(
+		&self,
+		fields: &[Field<PortableForm>],
+		value: &Value,
+		output: &mut Vec<u8>,
+	) -> Result<(), String> {
+		match fields {
+			[] => Ok(()),
+			[field] if field.name.is_none() => self.value(field.ty.id, value, output),
+			fields if fields.iter().all(|field| field.name.is_some()) => {
+				let object =
+					value.as_object().ok_or_else(|| format!("Expected an object: `{value}`"))?;
+				if let Some(unknown) =
+					object.keys().find(|key| !fields.iter().any(|f| f.name.as_ref() == Some(key)))
+				{
+					return Err(format!("Unknown field `{unknown}`"))
+				}
+				fields.iter().try_for_each(|field| {
+					let name = field.name.as_deref().unwrap_or_default();
+					let value =
+						object.get(name).ok_or_else(|| format!("Missing field `{name}`"))?;
+					self.value(field.ty.id, value, output)
+						.map_err(|e| format!("Field `{name}`: {e}"))
+				})
+			},
+			fields => {
+				let items = match value.as_array() {
+					Some(items) if items.len() == fields.len() => items,
+					_ => return Err(format!("Expected {} values: `{value}`", fields.len())),
+				};
+				fields
+					.iter()
+					.zip(items)
+					.try_for_each(|(field, value)| self.value(field.ty.id, value, output))
+			},
+		}
+	}
+
+	fn is_byte(&self, ty: u32) -> bool {
+		matches!(
+			self.types.resolve(ty).map(|ty| &ty.type_def),
+			Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+		)
+	}
+
+	fn primitive(
+		primitive: &TypeDefPrimitive,
+		value: &Value,
+		output: &mut Vec<u8>,
+	) -> Result<(), String> {
+		match primitive {
+			TypeDefPrimitive::Bool => value
+				.as_bool()
+				.ok_or_else(|| format!("Expected a boolean: `{value}`"))?
+				.encode_to(output),
+			TypeDefPrimitive::Char => {
+				let mut chars = value.as_str().unwrap_or_default().chars();
+				match (chars.next(), chars.next()) {
+					(Some(char), None) => (char as u32).encode_to(output),
+					_ => return Err(format!("Expected a character: `{value}`")),
+				}
+			},
+			TypeDefPrimitive::Str => value
+				.as_str()
+				.ok_or_else(|| format!("Expected a string: `{value}`"))?
+				.encode_to(output),
+			TypeDefPrimitive::U8 => integer::<u8>(value)?.encode_to(output),
+			TypeDefPrimitive::U16 => integer::<u16>(value)?.encode_to(output),
+			TypeDefPrimitive::U32 => integer::<u32>(value)?.encode_to(output),
+			TypeDefPrimitive::U64 => integer::<u64>(value)?.encode_to(output),
+			TypeDefPrimitive::U128 => integer::<u128>(value)?.encode_to(output),
+			TypeDefPrimitive::U256 => U256::from_dec_str(&number(value)?)
+				.map_err(|_| format!("Expected an unsigned integer: `{value}`"))?
+				.encode_to(output),
+			TypeDefPrimitive::I8 => integer::<i8>(value)?.encode_to(output),
+			TypeDefPrimitive::I16 => integer::<i16>(value)?.encode_to(output),
+			TypeDefPrimitive::I32 => integer::<i32>(value)?.encode_to(output),
+			TypeDefPrimitive::I64 => integer::<i64>(value)?.encode_to(output),
+			TypeDefPrimitive::I128 => integer::<i128>(value)?.encode_to(output),
+			TypeDefPrimitive::I256 => {
+				let bytes = hex(value.as_str().unwrap_or_default())?;
+				if bytes.len() != 32 {
+					return Err(format!("Expected 32 bytes: `{value}`"))
+				}
+				output.extend(bytes);
+			},
+		}
+		Ok(())
+	}
+}
+
+/// The digits of a number or of a decimal string.
+fn number(value: &Value) -> Result<String, String> {
+	match value {
+		Value::Number(number) => Ok(number.to_string()),
+		Value::String(number) => Ok(number.replace('_', "")),
+		_ => Err(format!("Expected an integer: `{value}`")),
+	}
+}
+
+fn integer<T: std::str::FromStr>(value: &Value) -> Result<T, String> {
+	number(value)?
+		.parse()
+		.map_err(|_| format!("Expected an integer of {}: `{value}`", std::any::type_name::<T>()))
+}
+
+fn hex(bytes: &str) -> Result<Vec<u8>, String> {
+	array_bytes::hex2bytes(bytes).map_err(|_| format!("`{bytes}` is not hex"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use scale_info::{meta_type, Registry, TypeInfo};
+	use serde_json::json;
+
+	#[derive(Encode, TypeInfo)]
+	enum Status {
+		Active,
+		Frozen { until: u32 },
+	}
+
+	#[derive(Encode, TypeInfo)]
+	struct Transfer {
+		from: AccountId32,
+		to: AccountId32,
+		amount: u128,
+		#[codec(compact)]
+		nonce: u64,
+		memo: Vec<u8>,
+		statuses: Vec<Status>,
+		pair: (bool, Option<u16>),
+	}
+
+	#[test]
+	fn json_encodes_to_values() {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&meta_type::<Transfer>()).id;
+		let types: PortableRegistry = registry.into();
+
+		let from = AccountId32::new([1; 32]);
+		let to = AccountId32::new([2; 32]);
+		let transfer = Transfer {
+			from: from.clone(),
+			to: to.clone(),
+			amount: u128::MAX,
+			nonce: 7,
+			memo: b"hi".to_vec(),
+			statuses: vec![Status::Active, Status::Frozen { until: 9 }],
+			pair: (true, Some(3)),
+		};
+		let value = json!({
+			"from": from.to_ss58check(),
+			"to": array_bytes::bytes2hex("0x", [2; 32]),
+			"amount": u128::MAX.to_string(),
+			"nonce": 7,
+			"memo": "0x6869",
+			"statuses": ["Active", { "Frozen": { "until": 9 } }],
+			"pair": [true, { "Some": 3 }],
+		});
+
+		let mut output = Vec::new();
+		ValueEncoder { types: &types }.value(ty, &value, &mut output).unwrap();
+		assert_eq!(output, transfer.encode());
+	}
+
+	#[test]
+	fn invalid_json_is_rejected() {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&meta_type::<Status>()).id;
+		let types: PortableRegistry = registry.into();
+		let encoder = ValueEncoder { types: &types };
+		let encode = |value| encoder.value(ty, &value, &mut Vec::new());
+
+		assert!(encode(json!({ "Frozen": { "until": 9 } })).is_ok());
+		assert!(encode(json!("Thawed")).is_err());
+		assert!(encode(json!({ "Frozen": { "until": -1 } })).is_err());
+		assert!(encode(json!({ "Frozen": { "until": 9, "since": 1 } })).is_err());
+		assert!(encode(json!({ "Frozen": {} })).is_err());
+	}
+}
@@ -0,0 +1,314 @@
+//! Extrinsics built and signed without network access, for air-gapped signing:
+//!
+//! - `nativex tx build` encodes a call described in JSON or YAML with the runtime metadata.
+//! - `nativex tx sign` signs an encoded call with the `SignedExtra` of the runtime.
+//! - `nativex tx submit` submits a signed extrinsic to a node.
+//!
+//! Calls and extrinsics are printed hex-encoded, and optionally as a QR code. Every command reads
+//! them back from `-` as the standard input, so they can be piped.
+
+mod encode;
+
+use std::io::Read;
+
+use development_runtime::{
+	signed_extra, Address, Runtime, RuntimeCall, SignedPayload, UncheckedExtrinsic, VERSION,
+};
+use jsonrpsee::{core::client::ClientT, http_client::HttpClientBuilder, rpc_params};
+use parity_scale_codec::{DecodeAll, Encode};
+use sc_cli::KeystoreParams;
+use sc_keystore::LocalKeystore;
+use sp_core::{
+	crypto::{ExposeSecret, KeyTypeId, Ss58Codec},
+	sr25519, Pair, H256,
+};
+use sp_keystore::Keystore;
+use sp_runtime::generic::Era;
+
+use common_primitives::{Balance, Index};
+use encode::{CallDescription, CallEncoder};
+
+/// Build, sign and submit extrinsics.
+#[derive(Debug, clap::Subcommand)]
+pub enum TxCmd {
+	/// Encode a call described in a JSON or YAML file.
+	Build(BuildCmd),
+	/// Sign an encoded call into an extrinsic.
+	Sign(SignCmd),
+	/// Submit a signed extrinsic to a node.
+	Submit(SubmitCmd),
+}
+
+impl TxCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		match self {
+			TxCmd::Build(cmd) => cmd.run(),
+			TxCmd::Sign(cmd) => cmd.run(),
+			TxCmd::Submit(cmd) => cmd.run(),
+		}
+	}
+}
+
+/// How to print a call or an extrinsic.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct OutputParams {
+	/// Print a QR code of the hex output as well, to scan it on another machine.
+	#[arg(long)]
+	pub qr: bool,
+}
+
+impl OutputParams {
+	fn print(&self, bytes: &[u8]) -> sc_cli::Result<()> {
+		let hex = array_bytes::bytes2hex("0x", bytes);
+		println!("{hex}");
+		if self.qr {
+			let code = qrcode::QrCode::new(hex.as_bytes()).map_err(|e| {
+				sc_cli::Error::Input(format!("The output doesn't fit a QR code: {e}"))
+			})?;
+			println!("{}", code.render::<qrcode::render::unicode::Dense1x2>().build());
+		}
+		Ok(())
+	}
+}
+
+/// Encode a call described in JSON or YAML, such as
+///
+/// ```yaml
+/// pallet: Balances
+/// call: transfer_keep_alive
+/// args:
+///   dest: { Id: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY }
+///   value: "1000000000000"
+/// ```
+#[derive(Debug, clap::Parser)]
+pub struct BuildCmd {
+	/// The file describing the call, or `-` to read it from the standard input.
+	#[arg(value_name = "FILE")]
+	pub file: String,
+
+	#[clap(flatten)]
+	pub output: OutputParams,
+}
+
+impl BuildCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let description = match self.file.as_str() {
+			"-" => read_stdin()?,
+			file => std::fs::read_to_string(file)?,
+		};
+		// JSON is YAML as well.
+		let call: CallDescription = serde_yaml::from_str(&description)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid call description: {e}")))?;
+		let encoder =
+			CallEncoder::new(&Runtime::metadata().encode()).map_err(sc_cli::Error::Input)?;
+		let encoded = encoder.encode(&call).map_err(sc_cli::Error::Input)?;
+		// Catches what the metadata lets through but the runtime doesn't, like too long vectors.
+		RuntimeCall::decode_all(&mut &encoded[..]).map_err(|e| {
+			sc_cli::Error::Input(format!("The runtime can't decode the encoded call: {e}"))
+		})?;
+		self.output.print(&encoded)
+	}
+}
+
+/// Sign an encoded call with a key of a keystore or a secret URI.
+///
+/// The extrinsic is immortal unless `--mortality` is set.
+#[derive(Debug, clap::Parser)]
+pub struct SignCmd {
+	/// The hex-encoded call, as `nativex tx build` prints it, or `-` to read it from the
+	/// standard input.
+	#[arg(long, value_name = "HEX")]
+	pub call: String,
+
+	/// The nonce of the signer's account.
+	#[arg(long)]
+	pub nonce: Index,
+
+	/// The tip for the block author.
+	#[arg(long, default_value_t = 0)]
+	pub tip: Balance,
+
+	/// The hash of the genesis block of the chain.
+	#[arg(long, value_name = "HASH")]
+	pub genesis_hash: H256,
+
+	/// Make the extrinsic valid for this many blocks from `--block-number` only.
+	///
+	/// The period is rounded up to a power of two.
+	#[arg(long, value_name = "BLOCKS", requires_all = ["block_number", "block_hash"])]
+	pub mortality: Option<u64>,
+
+	/// The number of a recent block the mortality starts at.
+	#[arg(long, requires = "mortality")]
+	pub block_number: Option<u64>,
+
+	/// The hash of the block of `--block-number`.
+	#[arg(long, value_name = "HASH", requires = "mortality")]
+	pub block_hash: Option<H256>,
+
+	/// The spec version of the runtime, the one of the native runtime by default.
+	#[arg(long)]
+	pub spec_version: Option<u32>,
+
+	/// The transaction version of the runtime, the one of the native runtime by default.
+	#[arg(long)]
+	pub transaction_version: Option<u32>,
+
+	/// The secret URI of the signer's sr25519 key, or a file holding it.
+	#[arg(long, value_name = "SURI", required_unless_present = "signer")]
+	pub suri: Option<String>,
+
+	/// The SS58 address of the signer's sr25519 key, to sign with it from `--keystore-path`.
+	#[arg(long, value_name = "SS58", conflicts_with = "suri", requires = "keystore_path")]
+	pub signer: Option<String>,
+
+	/// The key type of the signer's key in the keystore.
+	#[arg(long, default_value = "acco")]
+	pub key_type: String,
+
+	#[clap(flatten)]
+	pub keystore_params: KeystoreParams,
+
+	#[clap(flatten)]
+	pub output: OutputParams,
+}
+
+impl SignCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let call = RuntimeCall::decode_all(&mut &read_hex(&self.call)?[..])
+			.map_err(|e| sc_cli::Error::Input(format!("The call can't be decoded: {e}")))?;
+		let (era, checkpoint) = match (self.mortality, self.block_number, self.block_hash) {
+			(Some(period), Some(number), Some(hash)) => (Era::mortal(period, number), hash),
+			_ => (Era::Immortal, self.genesis_hash),
+		};
+		let extra = signed_extra(self.nonce, era, self.tip);
+		let payload = SignedPayload::from_raw(
+			call.clone(),
+			extra.clone(),
+			(
+				(),
+				self.spec_version.unwrap_or(VERSION.spec_version),
+				self.transaction_version.unwrap_or(VERSION.transaction_version),
+				self.genesis_hash,
+				checkpoint,
+				(),
+				(),
+				(),
+				(),
+			),
+		);
+
+		let (public, signature) = self.sign(&payload)?;
+		let extrinsic = UncheckedExtrinsic::new_signed(
+			call,
+			Address::Id(public.into()),
+			signature.into(),
+			extra,
+		);
+		self.output.print(&extrinsic.encode())
+	}
+
+	/// Sign `payload` with the key of `--suri` or `--signer`.
+	fn sign(
+		&self,
+		payload: &SignedPayload,
+	) -> sc_cli::Result<(sr25519::Public, sr25519::Signature)> {
+		let password = self.keystore_params.read_password()?;
+		let Some(signer) = &self.signer else {
+			let suri = sc_cli::utils::read_uri(self.suri.as_ref())?;
+			let password = password.as_ref().map(|password| password.expose_secret().as_str());
+			let pair = sr25519::Pair::from_string(&suri, password)
+				.map_err(|e| sc_cli::Error::Input(format!("Invalid secret URI: {e:?}")))?;
+			return Ok((pair.public(), payload.using_encoded(|payload| pair.sign(payload))))
+		};
+
+		let public = sr25519::Public::from_ss58check(signer)
+			.map_err(|e| sc_cli::Error::Input(format!("Invalid signer `{signer}`: {e:?}")))?;
+		let key_type = KeyTypeId::try_from(self.key_type.as_str())
+			.map_err(|_| sc_cli::Error::KeyTypeInvalid)?;
+		let path = self.keystore_params.keystore_path.clone().unwrap_or_default();
+		let keystore = LocalKeystore::open(path, password)?;
+		let signature = payload
+			.using_encoded(|payload| keystore.sr25519_sign(key_type, &public, payload))
+			.map_err(|e| sc_cli::Error::Application(Box::new(e)))?
+			.ok_or_else(|| {
+				sc_cli::Error::Input(format!("The keystore has no {} key {signer}", self.key_type))
+			})?;
+		Ok((public, signature))
+	}
+}
+
+/// Submit a signed extrinsic over the RPC of a node.
+#[derive(Debug, clap::Parser)]
+pub struct SubmitCmd {
+	/// The hex-encoded extrinsic, as `nativex tx sign` prints it, or `-` to read it from the
+	/// standard input.
+	#[arg(value_name = "HEX")]
+	pub extrinsic: String,
+
+	/// The HTTP RPC endpoint of the node.
+	#[arg(long, value_name = "URL", default_value = "http://127.0.0.1:9944")]
+	pub url: String,
+}
+
+impl SubmitCmd {
+	/// Run the command.
+	pub fn run(&self) -> sc_cli::Result<()> {
+		let extrinsic = read_hex(&self.extrinsic)?;
+		UncheckedExtrinsic::decode_all(&mut &extrinsic[..])
+			.map_err(|e| sc_cli::Error::Input(format!("The extrinsic can't be decoded: {e}")))?;
+		let hash: H256 = sc_cli::build_runtime()?
+			.block_on(async {
+				let client = HttpClientBuilder::default().build(&self.url)?;
+				let extrinsic = array_bytes::bytes2hex("0x", &extrinsic);
+				client.request("author_submitExtrinsic", rpc_params![extrinsic]).await
+			})
+			.map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
+		println!("{hash:?}");
+		Ok(())
+	}
+}
+
+fn read_stdin() -> sc_cli::Result<String> {
+	let mut input = String::new();
+	std::io::stdin().read_to_string(&mut input)?;
+	Ok(input)
+}
+
+/// Decode `input`, or the standard input if it is `-`, from hex.
+pub(crate) fn read_hex(input: &str) -> sc_cli::Result<Vec<u8>> {
+	let input = if input == "-" { read_stdin()? } else { input.to_string() };
+	array_bytes::hex2bytes(input.trim())
+		.map_err(|_| sc_cli::Error::Input("The input is not hex".into()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use development_runtime::BalancesCall;
+	use serde_json::json;
+	use sp_keyring::AccountKeyring;
+
+	#[test]
+	fn built_call_is_the_runtime_call() {
+		let description = concat!(
+			"pallet: Balances\n",
+			"call: transfer_keep_alive\n",
+			"args:\n",
+			"  dest: { Id: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY }\n",
+			"  value: '1000000000000'\n",
+		);
+		let call: CallDescription = serde_yaml::from_str(description).unwrap();
+		assert_eq!(call.args["value"], json!("1000000000000"));
+
+		let encoder = CallEncoder::new(&Runtime::metadata().encode()).unwrap();
+		let expected = RuntimeCall::Balances(BalancesCall::transfer_keep_alive {
+			dest: Address::Id(AccountKeyring::Alice.to_account_id()),
+			value: 1_000_000_000_000,
+		});
+		assert_eq!(encoder.encode(&call).unwrap(), expected.encode());
+	}
+}
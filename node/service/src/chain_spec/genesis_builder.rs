@@ -0,0 +1,157 @@
+//! Build genesis configs from the presets exposed by a runtime Wasm blob.
+//!
+//! The runtime owns its presets (see the `GenesisBuilder` runtime API); the node executes the
+//! Wasm to get the JSON patch of a preset and merges it into the default `GenesisConfig`.
+//!
+//! Genesis configs of this Substrate version only deserialize and build their storage with
+//! `std`, so the merged JSON is deserialized into the native `GenesisConfig` of the same runtime
+//! crate; the Wasm alone is not enough to build a spec.
+//!
+//! The JSON is merged as text: `serde_json::Value` can't hold the `u128` balances of the
+//! presets.
+
+use std::collections::BTreeMap;
+
+use parity_scale_codec::{Decode, Encode};
+use sc_executor::WasmExecutor;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::value::RawValue;
+use sp_core::traits::{CallContext, CodeExecutor, RuntimeCode, WrappedRuntimeCode};
+use sp_state_machine::BasicExternalities;
+
+const PRESET_NAMES: &str = "GenesisBuilder_preset_names";
+const GET_PRESET: &str = "GenesisBuilder_get_preset";
+
+/// Call `method` of the runtime API in `code` with SCALE-encoded `data`.
+fn call(code: &[u8], method: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+	let executor = WasmExecutor::<sp_io::SubstrateHostFunctions>::builder()
+		.with_allow_missing_host_functions(true)
+		.build();
+	let runtime_code = RuntimeCode {
+		code_fetcher: &WrappedRuntimeCode(code.into()),
+		heap_pages: None,
+		hash: sp_core::blake2_256(code).to_vec(),
+	};
+	let mut ext = BasicExternalities::new_empty();
+
+	executor
+		.call(&mut ext, &runtime_code, method, data, false, CallContext::Offchain)
+		.0
+		.map_err(|e| format!("Failed to call `{}`: {}", method, e))
+}
+
+/// The names of all presets known to the runtime in `code`.
+pub fn preset_names(code: &[u8]) -> Result<Vec<String>, String> {
+	let names = call(code, PRESET_NAMES, &[])?;
+	let names = Vec::<Vec<u8>>::decode(&mut &names[..])
+		.map_err(|e| format!("Failed to decode preset names: {}", e))?;
+
+	Ok(names.into_iter().map(|name| String::from_utf8_lossy(&name).into_owned()).collect())
+}
+
+/// The JSON patch of preset `id`, as returned by the runtime in `code`.
+pub fn preset(code: &[u8], id: &str) -> Result<Box<RawValue>, String> {
+	let patch = call(code, GET_PRESET, &id.as_bytes().to_vec().encode())?;
+	let patch = Option::<Vec<u8>>::decode(&mut &patch[..])
+		.map_err(|e| format!("Failed to decode preset `{}`: {}", id, e))?
+		.ok_or_else(|| {
+			format!(
+				"Unknown genesis preset `{}`, available presets: {:?}",
+				id,
+				preset_names(code).unwrap_or_default()
+			)
+		})?;
+
+	let patch = String::from_utf8(patch).map_err(|e| format!("Invalid preset `{}`: {}", id, e))?;
+	RawValue::from_string(patch).map_err(|e| format!("Invalid preset `{}`: {}", id, e))
+}
+
+/// Build a genesis config by merging preset `id` of the runtime in `code` into `G::default()`.
+pub fn genesis_from_preset<G>(code: &[u8], id: &str) -> Result<G, String>
+where
+	G: Default + Serialize + DeserializeOwned,
+{
+	let config = serde_json::value::to_raw_value(&G::default())
+		.map_err(|e| format!("Failed to serialize default genesis config: {}", e))?;
+	let config = merge(&config, &preset(code, id)?)
+		.map_err(|e| format!("Failed to merge preset `{}`: {}", id, e))?;
+
+	serde_json::from_str(config.get())
+		.map_err(|e| format!("Preset `{}` does not match the genesis config: {}", id, e))
+}
+
+/// Recursively merge `patch` into `base`: objects are merged key by key, anything else in
+/// `patch` replaces the value in `base`. Values are kept as text, so numbers stay exact.
+fn merge(base: &RawValue, patch: &RawValue) -> serde_json::Result<Box<RawValue>> {
+	let is_object = |value: &RawValue| value.get().trim_start().starts_with('{');
+	if !is_object(base) || !is_object(patch) {
+		return Ok(patch.to_owned())
+	}
+
+	let mut merged: BTreeMap<String, Box<RawValue>> = serde_json::from_str(base.get())?;
+	let patch: BTreeMap<String, Box<RawValue>> = serde_json::from_str(patch.get())?;
+	for (key, value) in patch {
+		let value = match merged.get(&key) {
+			Some(base) => merge(base, &value)?,
+			None => value,
+		};
+		merged.insert(key, value);
+	}
+	serde_json::value::to_raw_value(&merged)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::{json, Value};
+
+	fn raw(value: &str) -> Box<RawValue> {
+		RawValue::from_string(value.into()).unwrap()
+	}
+
+	#[test]
+	fn merge_patches_nested_objects() {
+		let base = raw(r#"{
+			"balances": { "balances": [] },
+			"staking": { "validatorCount": 0, "minimumValidatorCount": 0 }
+		}"#);
+		let patch = raw(r#"{
+			"balances": { "balances": [["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 1]] },
+			"staking": { "validatorCount": 2 }
+		}"#);
+
+		let merged: Value = serde_json::from_str(merge(&base, &patch).unwrap().get()).unwrap();
+		assert_eq!(
+			merged,
+			json!({
+				"balances": { "balances": [["5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", 1]] },
+				"staking": { "validatorCount": 2, "minimumValidatorCount": 0 },
+			})
+		);
+	}
+
+	#[test]
+	fn merge_keeps_u128_exact() {
+		let balance = u128::MAX - 1;
+		let patch = raw(&format!(r#"{{"balance": {balance}}}"#));
+		let merged = merge(&raw(r#"{"balance": 0}"#), &patch).unwrap();
+
+		#[derive(serde::Deserialize)]
+		struct Account {
+			balance: u128,
+		}
+		let account: Account = serde_json::from_str(merged.get()).unwrap();
+		assert_eq!(account.balance, balance);
+	}
+
+	#[cfg(feature = "with-development-runtime")]
+	#[test]
+	fn runtime_presets_build_genesis_config() {
+		let code = development_runtime::WASM_BINARY.expect("wasm binary is built; qed");
+
+		for id in preset_names(code).unwrap() {
+			genesis_from_preset::<development_runtime::GenesisConfig>(code, &id).unwrap();
+		}
+	}
+}
@@ -9,6 +9,7 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 
 #[cfg(feature = "with-development-runtime")]
 pub mod development;
+pub mod genesis_builder;
 
 pub type DummyChainSpec = sc_service::GenericChainSpec<()>;
 type AccountPublic = <Signature as Verify>::Signer;
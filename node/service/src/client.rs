@@ -1,4 +1,4 @@
-use crate::{AccountId, Balance, Block, Index};
+use crate::{AccountId, Balance, Block, Hash, Index};
 use common_primitives::BlockNumber;
 use sp_api::NumberFor;
 use sp_runtime::{
@@ -26,6 +26,22 @@ impl From<Arc<crate::FullClient<development_runtime::RuntimeApi, crate::Developm
 		Self::Development(client)
 	}
 }
+
+impl Client {
+	/// The SCALE-encoded metadata of the runtime at block `hash`.
+	pub fn metadata(&self, hash: Hash) -> Result<Vec<u8>, sp_api::ApiError> {
+		use sp_api::{Metadata, ProvideRuntimeApi};
+
+		// Matching on `*self` keeps the match exhaustive when no runtime feature is enabled and
+		// `Client` has no variants.
+		match *self {
+			#[cfg(feature = "with-development-runtime")]
+			Self::Development(ref client) =>
+				client.runtime_api().metadata(hash).map(|metadata| metadata.to_vec()),
+		}
+	}
+}
+
 use crate::match_client;
 
 impl sc_client_api::BlockBackend<Block> for Client {
@@ -259,14 +275,19 @@ pub trait RuntimeApiCollection:
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
 	+ sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
+	+ sp_statement_store::runtime_api::ValidateStatement<Block>
 	+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>
 	+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
+	+ fee_estimation_runtime_api::FeeEstimationApi<Block, Balance>
+	+ contracts_debug_runtime_api::ContractsDebugApi<Block, AccountId, Balance, Hash>
+	+ unified_accounts_runtime_api::UnifiedAccountsApi<Block, AccountId>
+	+ account_overview_runtime_api::AccountOverviewApi<Block, AccountId, Balance, BlockNumber>
+	+ fp_rpc::EthereumRuntimeRPCApi<Block>
+	+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 	//+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>
 	//+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	//+ pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>
 	//+ mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>
-	//+ fp_rpc::EthereumRuntimeRPCApi<Block>
-	//+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 where
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
@@ -285,14 +306,19 @@ where
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
 		+ sp_transaction_pool::runtime_api::TaggedTransactionQueue<Block>
+		+ sp_statement_store::runtime_api::ValidateStatement<Block>
 		+ substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>
-		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
+		+ pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
+		+ fee_estimation_runtime_api::FeeEstimationApi<Block, Balance>
+		+ contracts_debug_runtime_api::ContractsDebugApi<Block, AccountId, Balance, Hash>
+		+ unified_accounts_runtime_api::UnifiedAccountsApi<Block, AccountId>
+		+ account_overview_runtime_api::AccountOverviewApi<Block, AccountId, Balance, BlockNumber>
+		+ fp_rpc::EthereumRuntimeRPCApi<Block>
+		+ fp_rpc::ConvertTransactionRuntimeApi<Block>,
 	//+ frame_system_rpc_runtime_api::AccountNonceApi<Block, AccountId, Nonce>,
 	//+ pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance>
 	//+ pallet_contracts_rpc::ContractsRuntimeApi<Block, AccountId, Balance, BlockNumber, Hash>
 	//+ mmr_rpc::MmrRuntimeApi<Block, <Block as sp_runtime::traits::Block>::Hash, BlockNumber>,
-	//+ fp_rpc::EthereumRuntimeRPCApi<Block>
-	//+ fp_rpc::ConvertTransactionRuntimeApi<Block>
 	<Self as sp_api::ApiExt<Block>>::StateBackend: sp_api::StateBackend<BlakeTwo256>,
 {
 }
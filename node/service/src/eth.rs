@@ -0,0 +1,132 @@
+//! Node-side parts of the Ethereum compatibility layer: the Frontier mapping database, its
+//! background tasks and their command-line options.
+
+use std::{collections::BTreeMap, path::PathBuf, sync::Arc, time::Duration};
+
+use fc_mapping_sync::{
+	kv::MappingSyncWorker, EthereumBlockNotification, EthereumBlockNotificationSinks,
+	SyncStrategy,
+};
+use fc_rpc::{EthTask, OverrideHandle};
+use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use futures::{future, prelude::*};
+use sc_client_api::BlockchainEvents;
+use sc_network_sync::SyncingService;
+use sc_service::{config::Configuration, TaskManager};
+use sp_api::ConstructRuntimeApi;
+
+use crate::{Block, FullBackend, FullClient, NativeExecutionDispatch, RuntimeApiCollection};
+
+/// The Frontier mapping database.
+pub type FrontierBackend = fc_db::kv::Backend<Block>;
+
+/// Where the Frontier mapping database of `config`'s chain lives.
+pub fn db_config_dir(config: &Configuration) -> PathBuf {
+	config.base_path.config_dir(config.chain_spec.id())
+}
+
+/// Options of the Ethereum RPC.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct EthConfiguration {
+	/// Maximum number of logs `eth_getLogs` returns.
+	#[arg(long, default_value = "10000")]
+	pub max_past_logs: u32,
+
+	/// Maximum number of blocks kept in the fee history cache.
+	#[arg(long, default_value = "2048")]
+	pub fee_history_limit: u64,
+
+	/// Sign transactions sent with `eth_sendTransaction` with the well-known development keys.
+	#[arg(long)]
+	pub enable_dev_signer: bool,
+
+	/// `eth_call` and `eth_estimateGas` may use up to this many times the block gas limit.
+	#[arg(long, default_value = "10")]
+	pub execute_gas_limit_multiplier: u64,
+
+	/// Size in bytes of the LRU cache for block data.
+	#[arg(long, default_value = "50")]
+	pub eth_log_block_cache: usize,
+
+	/// Size in bytes of the LRU cache for transaction statuses.
+	#[arg(long, default_value = "50")]
+	pub eth_statuses_cache: usize,
+}
+
+/// State shared between the Ethereum RPC and the Frontier background tasks.
+pub struct FrontierPartialComponents {
+	/// The filters installed with `eth_newFilter`.
+	pub filter_pool: Option<FilterPool>,
+	/// Fee history cache.
+	pub fee_history_cache: FeeHistoryCache,
+	/// Maximum number of blocks kept in the fee history cache.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+}
+
+/// Create the state shared between the Ethereum RPC and the Frontier background tasks.
+pub fn new_frontier_partial(config: &EthConfiguration) -> FrontierPartialComponents {
+	FrontierPartialComponents {
+		filter_pool: Some(Arc::new(std::sync::Mutex::new(BTreeMap::new()))),
+		fee_history_cache: Arc::new(std::sync::Mutex::new(BTreeMap::new())),
+		fee_history_cache_limit: config.fee_history_limit,
+	}
+}
+
+/// Spawn the tasks that keep the Frontier mapping database, the filter pool and the fee history
+/// up to date.
+#[allow(clippy::too_many_arguments)]
+pub fn spawn_frontier_tasks<RuntimeApi, Executor>(
+	task_manager: &TaskManager,
+	client: Arc<FullClient<RuntimeApi, Executor>>,
+	backend: Arc<FullBackend>,
+	frontier_backend: Arc<FrontierBackend>,
+	filter_pool: Option<FilterPool>,
+	overrides: Arc<OverrideHandle<Block>>,
+	fee_history_cache: FeeHistoryCache,
+	fee_history_cache_limit: FeeHistoryCacheLimit,
+	sync: Arc<SyncingService<Block>>,
+	pubsub_notification_sinks: Arc<
+		EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>,
+	>,
+) where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	Executor: NativeExecutionDispatch + 'static,
+{
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-mapping-sync-worker",
+		Some("frontier"),
+		MappingSyncWorker::new(
+			client.import_notification_stream(),
+			Duration::new(6, 0),
+			client.clone(),
+			backend,
+			overrides.clone(),
+			frontier_backend,
+			3,
+			0,
+			SyncStrategy::Normal,
+			sync,
+			pubsub_notification_sinks,
+		)
+		.for_each(|()| future::ready(())),
+	);
+
+	// Each filter is allowed to stay in the pool for 100 blocks.
+	const FILTER_RETAIN_THRESHOLD: u64 = 100;
+	if let Some(filter_pool) = filter_pool {
+		task_manager.spawn_essential_handle().spawn(
+			"frontier-filter-pool",
+			Some("frontier"),
+			EthTask::filter_pool_task(client.clone(), filter_pool, FILTER_RETAIN_THRESHOLD),
+		);
+	}
+
+	task_manager.spawn_essential_handle().spawn(
+		"frontier-fee-history",
+		Some("frontier"),
+		EthTask::fee_history_task(client, overrides, fee_history_cache, fee_history_cache_limit),
+	);
+}
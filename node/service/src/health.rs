@@ -0,0 +1,249 @@
+//! HTTP endpoints for orchestrators to probe the node:
+//!
+//! - `/health`: the node is running.
+//! - `/readiness`: the node is synced, has enough peers and its best block is recent.
+//! - `/validator`: the keystore holds the BABE and the GRANDPA key of a current authority.
+//!
+//! `/readiness` and `/validator` answer `200` or `503` with the state they checked as JSON.
+
+use std::{
+	net::{Ipv4Addr, SocketAddr},
+	sync::Arc,
+	time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use grandpa_primitives::GrandpaApi;
+use hyper::{
+	service::{make_service_fn, service_fn},
+	Body, Response, StatusCode,
+};
+use parity_scale_codec::Decode;
+use sc_client_api::StorageProvider;
+use sc_network_sync::SyncingService;
+use sc_service::TaskManager;
+use serde::Serialize;
+use sp_api::{ConstructRuntimeApi, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::SyncOracle;
+use sp_consensus_babe::BabeApi;
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId},
+	storage::StorageKey,
+};
+use sp_keystore::KeystorePtr;
+
+use crate::{
+	Block, BlockNumber, FullBackend, FullClient, NativeExecutionDispatch, RuntimeApiCollection,
+};
+
+const LOG_TARGET: &str = "health";
+
+/// Options of the health server.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct HealthConfiguration {
+	/// Serve `/health`, `/readiness` and `/validator` over HTTP on this port.
+	#[arg(long, value_name = "PORT")]
+	pub health_port: Option<u16>,
+
+	/// Listen for health probes on all interfaces, not only on localhost.
+	#[arg(long, requires = "health_port")]
+	pub health_external: bool,
+
+	/// The fewest peers the node may have to be ready.
+	#[arg(long, value_name = "COUNT", default_value_t = 1)]
+	pub health_min_peers: u32,
+
+	/// The oldest the best block may be, in seconds, for the node to be ready.
+	#[arg(long, value_name = "SECONDS", default_value_t = 60)]
+	pub health_max_block_age: u64,
+}
+
+/// What `/readiness` checked.
+#[derive(Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Readiness {
+	/// Whether the node is done with major syncing.
+	pub synced: bool,
+	/// The number of peers of the node.
+	pub peers: u32,
+	/// The best block of the node.
+	pub best_block: BlockNumber,
+	/// How many seconds ago the best block was authored, if its timestamp is known.
+	pub best_block_age: Option<u64>,
+}
+
+impl Readiness {
+	/// Whether the node is ready with `config`'s thresholds.
+	pub fn is_ready(&self, config: &HealthConfiguration) -> bool {
+		self.synced &&
+			self.peers >= config.health_min_peers &&
+			self.best_block_age.map_or(false, |age| age <= config.health_max_block_age)
+	}
+}
+
+/// What `/validator` checked.
+#[derive(Serialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Validator {
+	/// Whether the keystore holds the key of a BABE authority of the current epoch.
+	pub babe: bool,
+	/// Whether the keystore holds the key of a current GRANDPA authority.
+	pub grandpa: bool,
+}
+
+impl Validator {
+	/// Whether the node can author and finalize blocks.
+	pub fn is_validator(&self) -> bool {
+		self.babe && self.grandpa
+	}
+}
+
+struct Health<C> {
+	client: Arc<C>,
+	sync: Arc<SyncingService<Block>>,
+	keystore: KeystorePtr,
+	config: HealthConfiguration,
+}
+
+impl<C> Health<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + StorageProvider<Block, FullBackend>,
+	C::Api: BabeApi<Block> + GrandpaApi<Block>,
+{
+	async fn readiness(&self) -> Result<Readiness, String> {
+		let info = self.client.info();
+		let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+		let best_block_age = self
+			.client
+			.storage(info.best_hash, &timestamp_key())
+			.map_err(|e| e.to_string())?
+			.and_then(|timestamp| u64::decode(&mut &timestamp.0[..]).ok())
+			.map(|timestamp| now.saturating_sub(Duration::from_millis(timestamp)).as_secs());
+		let status = self.sync.status().await.map_err(|()| "Sync is down".to_string())?;
+		Ok(Readiness {
+			synced: !self.sync.is_major_syncing(),
+			peers: status.num_peers,
+			best_block: info.best_number,
+			best_block_age,
+		})
+	}
+
+	fn validator(&self) -> Result<Validator, String> {
+		let best = self.client.info().best_hash;
+		let api = self.client.runtime_api();
+		let babe = api.current_epoch(best).map_err(|e| e.to_string())?.authorities;
+		let grandpa = api.grandpa_authorities(best).map_err(|e| e.to_string())?;
+		let has_key = |public: &[u8], key_type: KeyTypeId| {
+			self.keystore.has_keys(&[(public.to_vec(), key_type)])
+		};
+		Ok(Validator {
+			babe: babe.iter().any(|(id, _)| has_key(id.as_slice(), sp_consensus_babe::KEY_TYPE)),
+			grandpa: grandpa
+				.iter()
+				.any(|(id, _)| has_key(id.as_slice(), grandpa_primitives::KEY_TYPE)),
+		})
+	}
+
+	async fn respond(&self, path: &str) -> Response<Body> {
+		let checked = match path {
+			"/health" => return response(StatusCode::OK, Body::from("OK")),
+			"/readiness" => self.readiness().await.map(|readiness| {
+				(readiness.is_ready(&self.config), serde_json::to_string(&readiness))
+			}),
+			"/validator" => self.validator().map(|validator| {
+				(validator.is_validator(), serde_json::to_string(&validator))
+			}),
+			_ => return response(StatusCode::NOT_FOUND, Body::empty()),
+		};
+		match checked {
+			Ok((ok, Ok(json))) => response(
+				if ok { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE },
+				Body::from(json),
+			),
+			Ok((_, Err(e))) =>
+				response(StatusCode::INTERNAL_SERVER_ERROR, Body::from(e.to_string())),
+			Err(e) => response(StatusCode::SERVICE_UNAVAILABLE, Body::from(e)),
+		}
+	}
+}
+
+fn response(status: StatusCode, body: Body) -> Response<Body> {
+	let mut response = Response::new(body);
+	*response.status_mut() = status;
+	response
+}
+
+/// The storage key of `Timestamp::Now`.
+fn timestamp_key() -> StorageKey {
+	let mut key = sp_core::twox_128(b"Timestamp").to_vec();
+	key.extend(sp_core::twox_128(b"Now"));
+	StorageKey(key)
+}
+
+/// Spawn the health server if `config` asks for it.
+pub fn spawn_health_server<RuntimeApi, Executor>(
+	task_manager: &TaskManager,
+	config: HealthConfiguration,
+	client: Arc<FullClient<RuntimeApi, Executor>>,
+	sync: Arc<SyncingService<Block>>,
+	keystore: KeystorePtr,
+) -> Result<(), sc_service::Error>
+where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	Executor: NativeExecutionDispatch + 'static,
+{
+	let Some(port) = config.health_port else { return Ok(()) };
+	let ip = if config.health_external { Ipv4Addr::UNSPECIFIED } else { Ipv4Addr::LOCALHOST };
+	let address = SocketAddr::from((ip, port));
+
+	let health = Arc::new(Health { client, sync, keystore, config });
+	let make_service = make_service_fn(move |_| {
+		let health = health.clone();
+		async move {
+			Ok::<_, hyper::Error>(service_fn(move |request| {
+				let health = health.clone();
+				async move { Ok::<_, hyper::Error>(health.respond(request.uri().path()).await) }
+			}))
+		}
+	});
+	let server = hyper::Server::try_bind(&address)
+		.map_err(|e| {
+			sc_service::Error::Other(format!("Health server can't listen on {address}: {e}"))
+		})?
+		.serve(make_service);
+	log::info!(target: LOG_TARGET, "Health server listening on http://{address}");
+
+	task_manager.spawn_handle().spawn("health-server", None, async move {
+		if let Err(e) = server.await {
+			log::error!(target: LOG_TARGET, "Health server failed: {e}");
+		}
+	});
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn config() -> HealthConfiguration {
+		HealthConfiguration {
+			health_port: Some(9933),
+			health_external: false,
+			health_min_peers: 1,
+			health_max_block_age: 60,
+		}
+	}
+
+	#[test]
+	fn readiness_needs_sync_peers_and_recent_block() {
+		let ready = Readiness { synced: true, peers: 1, best_block: 10, best_block_age: Some(6) };
+		assert!(ready.is_ready(&config()));
+		assert!(!Readiness { synced: false, ..ready.clone() }.is_ready(&config()));
+		assert!(!Readiness { peers: 0, ..ready.clone() }.is_ready(&config()));
+		assert!(!Readiness { best_block_age: Some(61), ..ready.clone() }.is_ready(&config()));
+		assert!(!Readiness { best_block_age: None, ..ready }.is_ready(&config()));
+	}
+}
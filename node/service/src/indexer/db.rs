@@ -0,0 +1,404 @@
+//! The SQLite database of the indexer.
+
+use std::{
+	path::Path,
+	sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::{params, types::ToSql, Connection, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+/// Bump when the schema or the way events are decoded changes, to re-index from genesis.
+const SCHEMA_VERSION: u32 = 1;
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS blocks (
+		number INTEGER PRIMARY KEY,
+		hash BLOB NOT NULL
+	);
+	CREATE TABLE IF NOT EXISTS events (
+		block_number INTEGER NOT NULL,
+		event_index INTEGER NOT NULL,
+		extrinsic_index INTEGER,
+		extrinsic_hash BLOB,
+		pallet TEXT NOT NULL,
+		event TEXT NOT NULL,
+		fields TEXT NOT NULL,
+		PRIMARY KEY (block_number, event_index)
+	);
+	CREATE INDEX IF NOT EXISTS events_by_name ON events (pallet, event, block_number);
+	CREATE INDEX IF NOT EXISTS events_by_extrinsic ON events (extrinsic_hash);
+	CREATE TABLE IF NOT EXISTS event_accounts (
+		account BLOB NOT NULL,
+		block_number INTEGER NOT NULL,
+		event_index INTEGER NOT NULL,
+		PRIMARY KEY (account, block_number, event_index)
+	);
+";
+
+/// Most events a query returns when it sets no limit.
+pub const DEFAULT_LIMIT: u32 = 100;
+/// Most events a query may return.
+pub const MAX_LIMIT: u32 = 1000;
+
+/// An indexed event.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct IndexedEvent {
+	/// Number of the block that deposited the event.
+	pub block_number: u32,
+	/// Hash of the block that deposited the event.
+	pub block_hash: H256,
+	/// Position of the event in `System::Events` of its block.
+	pub event_index: u32,
+	/// Position of the extrinsic that deposited the event, if one did.
+	pub extrinsic_index: Option<u32>,
+	/// Hash of the extrinsic that deposited the event, if one did.
+	pub extrinsic_hash: Option<H256>,
+	/// Name of the pallet that deposited the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub event: String,
+	/// The fields of the event.
+	pub fields: serde_json::Value,
+}
+
+/// An event to add to the index, see [`IndexedEvent`] for its fields.
+pub struct NewEvent {
+	pub extrinsic_index: Option<u32>,
+	pub extrinsic_hash: Option<H256>,
+	pub pallet: String,
+	pub event: String,
+	pub fields: serde_json::Value,
+	/// The accounts that appear in the fields of the event.
+	pub accounts: Vec<[u8; 32]>,
+}
+
+/// The position of an event in the index.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventCursor {
+	/// Number of the block that deposited the event.
+	pub block_number: u32,
+	/// Position of the event in `System::Events` of its block.
+	pub event_index: u32,
+}
+
+/// Which events a query returns. Every filter that is set must match.
+#[derive(Default, Clone, Debug)]
+pub struct EventFilter {
+	/// Events that name this account in their fields.
+	pub account: Option<[u8; 32]>,
+	/// Events of this pallet.
+	pub pallet: Option<String>,
+	/// Events with this name.
+	pub event: Option<String>,
+	/// Events of this block or later ones.
+	pub from_block: Option<u32>,
+	/// Events of this block or earlier ones.
+	pub to_block: Option<u32>,
+	/// Events of the extrinsic with this hash.
+	pub extrinsic_hash: Option<H256>,
+	/// Events after this one, to get the next page.
+	pub after: Option<EventCursor>,
+	/// Most events to return, [`DEFAULT_LIMIT`] if not set.
+	pub limit: Option<u32>,
+}
+
+/// A page of events, oldest first.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+	/// The events.
+	pub events: Vec<IndexedEvent>,
+	/// Pass as `after` to get the next page, if there is one.
+	pub next: Option<EventCursor>,
+	/// The last indexed block.
+	pub indexed_to: Option<u32>,
+}
+
+/// The index of events, shared between the indexer and the RPC.
+pub struct IndexerDb {
+	connection: Mutex<Connection>,
+}
+
+impl IndexerDb {
+	/// Open the index at `path`, creating it if needed.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		Self::new(Connection::open(path)?)
+	}
+
+	/// Open an index that lives in memory.
+	#[cfg(test)]
+	pub fn open_in_memory() -> rusqlite::Result<Self> {
+		Self::new(Connection::open_in_memory()?)
+	}
+
+	fn new(connection: Connection) -> rusqlite::Result<Self> {
+		let version: u32 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+		if version != SCHEMA_VERSION {
+			Self::drop_tables(&connection)?;
+			connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+		}
+		connection.execute_batch(SCHEMA)?;
+		Ok(Self { connection: Mutex::new(connection) })
+	}
+
+	fn connection(&self) -> MutexGuard<Connection> {
+		self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	fn drop_tables(connection: &Connection) -> rusqlite::Result<()> {
+		connection.execute_batch(
+			"DROP TABLE IF EXISTS event_accounts;
+			DROP TABLE IF EXISTS events;
+			DROP TABLE IF EXISTS blocks;",
+		)
+	}
+
+	/// Remove everything from the index.
+	pub fn clear(&self) -> rusqlite::Result<()> {
+		let connection = self.connection();
+		Self::drop_tables(&connection)?;
+		connection.execute_batch(SCHEMA)
+	}
+
+	/// The last indexed block.
+	pub fn last_block(&self) -> rusqlite::Result<Option<(u32, H256)>> {
+		self.connection()
+			.query_row("SELECT number, hash FROM blocks ORDER BY number DESC LIMIT 1", [], |row| {
+				Ok((row.get(0)?, H256::from_slice(&row.get::<_, Vec<u8>>(1)?)))
+			})
+			.optional()
+	}
+
+	/// Remove the blocks after `number` from the index.
+	pub fn remove_after(&self, number: u32) -> rusqlite::Result<()> {
+		let mut connection = self.connection();
+		let transaction = connection.transaction()?;
+		transaction.execute("DELETE FROM event_accounts WHERE block_number > ?1", [number])?;
+		transaction.execute("DELETE FROM events WHERE block_number > ?1", [number])?;
+		transaction.execute("DELETE FROM blocks WHERE number > ?1", [number])?;
+		transaction.commit()
+	}
+
+	/// Add a block and its events to the index.
+	pub fn insert_block(
+		&self,
+		number: u32,
+		hash: H256,
+		events: Vec<NewEvent>,
+	) -> rusqlite::Result<()> {
+		let mut connection = self.connection();
+		let transaction = connection.transaction()?;
+		transaction.execute(
+			"INSERT INTO blocks (number, hash) VALUES (?1, ?2)",
+			params![number, &hash.0[..]],
+		)?;
+		for (event_index, event) in events.into_iter().enumerate() {
+			transaction.execute(
+				"INSERT INTO events (block_number, event_index, extrinsic_index, extrinsic_hash,
+					pallet, event, fields)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+				params![
+					number,
+					event_index as u32,
+					event.extrinsic_index,
+					event.extrinsic_hash.map(|hash| hash.0.to_vec()),
+					event.pallet,
+					event.event,
+					event.fields.to_string(),
+				],
+			)?;
+			for account in event.accounts {
+				transaction.execute(
+					"INSERT OR IGNORE INTO event_accounts (account, block_number, event_index)
+					VALUES (?1, ?2, ?3)",
+					params![&account[..], number, event_index as u32],
+				)?;
+			}
+		}
+		transaction.commit()
+	}
+
+	/// The events that match `filter`.
+	pub fn query(&self, filter: &EventFilter) -> rusqlite::Result<EventPage> {
+		let mut conditions = Vec::<&str>::new();
+		let mut params = Vec::<Box<dyn ToSql>>::new();
+		if let Some(account) = filter.account {
+			conditions.push(
+				"EXISTS (SELECT 1 FROM event_accounts a WHERE a.account = ?
+					AND a.block_number = e.block_number AND a.event_index = e.event_index)",
+			);
+			params.push(Box::new(account.to_vec()));
+		}
+		if let Some(pallet) = &filter.pallet {
+			conditions.push("e.pallet = ?");
+			params.push(Box::new(pallet.clone()));
+		}
+		if let Some(event) = &filter.event {
+			conditions.push("e.event = ?");
+			params.push(Box::new(event.clone()));
+		}
+		if let Some(from_block) = filter.from_block {
+			conditions.push("e.block_number >= ?");
+			params.push(Box::new(from_block));
+		}
+		if let Some(to_block) = filter.to_block {
+			conditions.push("e.block_number <= ?");
+			params.push(Box::new(to_block));
+		}
+		if let Some(extrinsic_hash) = filter.extrinsic_hash {
+			conditions.push("e.extrinsic_hash = ?");
+			params.push(Box::new(extrinsic_hash.0.to_vec()));
+		}
+		if let Some(after) = filter.after {
+			conditions.push("(e.block_number, e.event_index) > (?, ?)");
+			params.push(Box::new(after.block_number));
+			params.push(Box::new(after.event_index));
+		}
+		let limit = filter.limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+		// One more than the limit tells whether there is a next page.
+		params.push(Box::new(limit + 1));
+
+		let mut sql = String::from(
+			"SELECT e.block_number, b.hash, e.event_index, e.extrinsic_index, e.extrinsic_hash,
+				e.pallet, e.event, e.fields
+			FROM events e JOIN blocks b ON b.number = e.block_number",
+		);
+		if !conditions.is_empty() {
+			sql.push_str(" WHERE ");
+			sql.push_str(&conditions.join(" AND "));
+		}
+		sql.push_str(" ORDER BY e.block_number, e.event_index LIMIT ?");
+
+		let connection = self.connection();
+		let mut statement = connection.prepare(&sql)?;
+		let mut events = statement
+			.query_map(rusqlite::params_from_iter(params.iter()), |row| {
+				let fields: String = row.get(7)?;
+				Ok(IndexedEvent {
+					block_number: row.get(0)?,
+					block_hash: H256::from_slice(&row.get::<_, Vec<u8>>(1)?),
+					event_index: row.get(2)?,
+					extrinsic_index: row.get(3)?,
+					extrinsic_hash: row
+						.get::<_, Option<Vec<u8>>>(4)?
+						.map(|hash| H256::from_slice(&hash)),
+					pallet: row.get(5)?,
+					event: row.get(6)?,
+					fields: serde_json::from_str(&fields).unwrap_or_default(),
+				})
+			})?
+			.collect::<rusqlite::Result<Vec<_>>>()?;
+		drop(statement);
+		drop(connection);
+
+		let next = if events.len() > limit as usize {
+			events.truncate(limit as usize);
+			events.last().map(|event| EventCursor {
+				block_number: event.block_number,
+				event_index: event.event_index,
+			})
+		} else {
+			None
+		};
+		let indexed_to = self.last_block()?.map(|(number, _)| number);
+		Ok(EventPage { events, next, indexed_to })
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use serde_json::json;
+
+	fn transfer(extrinsic_index: u32, from: u8, to: u8) -> NewEvent {
+		NewEvent {
+			extrinsic_index: Some(extrinsic_index),
+			extrinsic_hash: Some(H256::repeat_byte(extrinsic_index as u8)),
+			pallet: "Balances".into(),
+			event: "Transfer".into(),
+			fields: json!({ "from": from, "to": to }),
+			accounts: vec![[from; 32], [to; 32]],
+		}
+	}
+
+	fn index() -> IndexerDb {
+		let db = IndexerDb::open_in_memory().unwrap();
+		db.insert_block(1, H256::repeat_byte(1), vec![transfer(0, 1, 2), transfer(1, 2, 3)])
+			.unwrap();
+		let mut events = vec![transfer(0, 3, 1)];
+		events.push(NewEvent {
+			extrinsic_index: None,
+			extrinsic_hash: None,
+			pallet: "System".into(),
+			event: "NewAccount".into(),
+			fields: json!({ "account": 4 }),
+			accounts: vec![[4; 32]],
+		});
+		db.insert_block(2, H256::repeat_byte(2), events).unwrap();
+		db
+	}
+
+	fn positions(page: &EventPage) -> Vec<(u32, u32)> {
+		page.events.iter().map(|event| (event.block_number, event.event_index)).collect()
+	}
+
+	#[test]
+	fn query_filters_events() {
+		let db = index();
+		let query = |filter: EventFilter| positions(&db.query(&filter).unwrap());
+
+		assert_eq!(query(Default::default()), vec![(1, 0), (1, 1), (2, 0), (2, 1)]);
+		assert_eq!(
+			query(EventFilter { account: Some([1; 32]), ..Default::default() }),
+			vec![(1, 0), (2, 0)]
+		);
+		assert_eq!(
+			query(EventFilter { pallet: Some("System".into()), ..Default::default() }),
+			vec![(2, 1)]
+		);
+		assert_eq!(
+			query(EventFilter {
+				event: Some("Transfer".into()),
+				from_block: Some(2),
+				..Default::default()
+			}),
+			vec![(2, 0)]
+		);
+		let extrinsic_hash = Some(H256::repeat_byte(1));
+		assert_eq!(query(EventFilter { extrinsic_hash, ..Default::default() }), vec![(1, 1)]);
+		assert_eq!(query(EventFilter { to_block: Some(1), ..Default::default() }).len(), 2);
+	}
+
+	#[test]
+	fn query_pages_through_events() {
+		let db = index();
+
+		let first = db.query(&EventFilter { limit: Some(3), ..Default::default() }).unwrap();
+		assert_eq!(positions(&first), vec![(1, 0), (1, 1), (2, 0)]);
+		assert_eq!(first.next, Some(EventCursor { block_number: 2, event_index: 0 }));
+		assert_eq!(first.indexed_to, Some(2));
+		assert_eq!(first.events[0].fields, json!({ "from": 1, "to": 2 }));
+
+		let second = db
+			.query(&EventFilter { limit: Some(3), after: first.next, ..Default::default() })
+			.unwrap();
+		assert_eq!(positions(&second), vec![(2, 1)]);
+		assert_eq!(second.next, None);
+	}
+
+	#[test]
+	fn removing_blocks_removes_their_events() {
+		let db = index();
+		db.remove_after(1).unwrap();
+		assert_eq!(db.last_block().unwrap(), Some((1, H256::repeat_byte(1))));
+		let page = db.query(&EventFilter { account: Some([4; 32]), ..Default::default() }).unwrap();
+		assert!(page.events.is_empty());
+
+		db.clear().unwrap();
+		assert_eq!(db.last_block().unwrap(), None);
+	}
+}
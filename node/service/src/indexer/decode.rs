@@ -0,0 +1,326 @@
+//! Decoding of `System::Events` with the runtime metadata, so the indexer doesn't depend on the
+//! event types of any particular runtime version.
+
+use frame_metadata::{RuntimeMetadata, RuntimeMetadataPrefixed, StorageEntryType};
+use frame_system::Phase;
+use parity_scale_codec::{Compact, Decode, Error as CodecError, Input};
+use scale_info::{form::PortableForm, Field, PortableRegistry, TypeDef, TypeDefPrimitive};
+use serde_json::{Map, Value};
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	H256, U256,
+};
+
+/// An event of `System::Events`.
+pub struct DecodedEvent {
+	/// When the event was deposited.
+	pub phase: Phase,
+	/// Name of the pallet that deposited the event.
+	pub pallet: String,
+	/// Name of the event.
+	pub event: String,
+	/// The fields of the event as JSON.
+	pub fields: Value,
+	/// The accounts that appear in the fields of the event.
+	pub accounts: Vec<[u8; 32]>,
+}
+
+/// Decodes `System::Events` with the types of one runtime version.
+pub struct EventsDecoder {
+	types: PortableRegistry,
+	/// Type of the `event` field of `frame_system::EventRecord`.
+	event_ty: u32,
+}
+
+impl EventsDecoder {
+	/// Create a decoder from the SCALE-encoded runtime metadata.
+	pub fn new(metadata: &[u8]) -> Result<Self, &'static str> {
+		let metadata = RuntimeMetadataPrefixed::decode(&mut &metadata[..])
+			.map_err(|_| "Runtime metadata can't be decoded")?;
+		let RuntimeMetadata::V14(metadata) = metadata.1 else {
+			return Err("Runtime metadata version is not supported")
+		};
+
+		let events_ty = metadata
+			.pallets
+			.iter()
+			.find(|pallet| pallet.name == "System")
+			.and_then(|pallet| pallet.storage.as_ref())
+			.and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+			.and_then(|entry| match &entry.ty {
+				StorageEntryType::Plain(ty) => Some(ty.id),
+				_ => None,
+			})
+			.ok_or("`System::Events` is missing from the runtime metadata")?;
+		let record_ty = match metadata.types.resolve(events_ty).map(|ty| &ty.type_def) {
+			Some(TypeDef::Sequence(sequence)) => sequence.type_param.id,
+			_ => return Err("`System::Events` is not a sequence"),
+		};
+		let event_ty = match metadata.types.resolve(record_ty).map(|ty| &ty.type_def) {
+			Some(TypeDef::Composite(record)) => record
+				.fields
+				.iter()
+				.find(|field| field.name.as_deref() == Some("event"))
+				.map(|field| field.ty.id),
+			_ => None,
+		}
+		.ok_or("`System::Events` records have no `event` field")?;
+
+		Ok(Self { types: metadata.types, event_ty })
+	}
+
+	/// Decode the value of `System::Events`.
+	pub fn decode(&self, mut events: &[u8]) -> Result<Vec<DecodedEvent>, CodecError> {
+		let input = &mut events;
+		let len = Compact::<u32>::decode(input)?.0;
+		(0..len)
+			.map(|_| {
+				let phase = Phase::decode(input)?;
+				let event = self.decode_event(phase, input)?;
+				// Topics are not indexed.
+				Vec::<H256>::decode(input)?;
+				Ok(event)
+			})
+			.collect()
+	}
+
+	/// Decode a `RuntimeEvent`: the pallet variant, then the event variant and its fields.
+	fn decode_event(&self, phase: Phase, input: &mut &[u8]) -> Result<DecodedEvent, CodecError> {
+		let pallet = self.variant(self.event_ty, input)?;
+		let pallet_event = match &pallet.fields[..] {
+			[field] => field.ty.id,
+			_ => return Err("Pallet variant of `RuntimeEvent` has no event".into()),
+		};
+		let event = self.variant(pallet_event, input)?;
+
+		let mut decoder = ValueDecoder { types: &self.types, accounts: Vec::new() };
+		let fields = decoder.fields(&event.fields, input)?;
+		Ok(DecodedEvent {
+			phase,
+			pallet: pallet.name.clone(),
+			event: event.name.clone(),
+			fields,
+			accounts: decoder.accounts,
+		})
+	}
+
+	/// Read the index of a variant of enum `ty` and return the variant.
+	fn variant(
+		&self,
+		ty: u32,
+		input: &mut &[u8],
+	) -> Result<&scale_info::Variant<PortableForm>, CodecError> {
+		let variants = match self.types.resolve(ty).map(|ty| &ty.type_def) {
+			Some(TypeDef::Variant(variants)) => &variants.variants,
+			_ => return Err("Event type is not an enum".into()),
+		};
+		let index = input.read_byte()?;
+		variants
+			.iter()
+			.find(|variant| variant.index == index)
+			.ok_or_else(|| "Event variant is not in the metadata".into())
+	}
+}
+
+/// Decode a value of type `ty` of `types` to JSON, the way event fields are decoded.
+pub fn decode_value(
+	types: &PortableRegistry,
+	ty: u32,
+	input: &mut &[u8],
+) -> Result<Value, CodecError> {
+	ValueDecoder { types, accounts: Vec::new() }.value(ty, input)
+}
+
+/// Decodes values of any type of a registry to JSON, collecting the accounts it meets.
+struct ValueDecoder<'a> {
+	types: &'a PortableRegistry,
+	accounts: Vec<[u8; 32]>,
+}
+
+impl ValueDecoder<'_> {
+	/// Decode a value of type `ty`.
+	///
+	/// Accounts are SS58-encoded, byte arrays and vectors are hex-encoded, and integers that may
+	/// not fit a JSON number are decimal strings.
+	fn value(&mut self, ty: u32, input: &mut &[u8]) -> Result<Value, CodecError> {
+		let ty = self.types.resolve(ty).ok_or("Type is not in the metadata")?;
+		if ty.path.segments.last().map(String::as_str) == Some("AccountId32") {
+			let account = <[u8; 32]>::decode(input)?;
+			self.accounts.push(account);
+			return Ok(AccountId32::new(account).to_ss58check().into())
+		}
+
+		match &ty.type_def {
+			TypeDef::Composite(composite) => self.fields(&composite.fields, input),
+			TypeDef::Variant(variants) => {
+				let index = input.read_byte()?;
+				let variant = variants
+					.variants
+					.iter()
+					.find(|variant| variant.index == index)
+					.ok_or("Variant is not in the metadata")?;
+				if variant.fields.is_empty() {
+					return Ok(variant.name.clone().into())
+				}
+				let mut object = Map::new();
+				object.insert(variant.name.clone(), self.fields(&variant.fields, input)?);
+				Ok(object.into())
+			},
+			TypeDef::Sequence(sequence) => {
+				let len = Compact::<u32>::decode(input)?.0;
+				self.items(sequence.type_param.id, len, input)
+			},
+			TypeDef::Array(array) => self.items(array.type_param.id, array.len, input),
+			TypeDef::Tuple(tuple) =>
+				tuple.fields.iter().map(|field| self.value(field.id, input)).collect(),
+			TypeDef::Primitive(primitive) => Self::primitive(primitive, input),
+			TypeDef::Compact(_) => {
+				let value = Compact::<u128>::decode(input)?.0;
+				Ok(u64::try_from(value)
+					.map(Value::from)
+					.unwrap_or_else(|_| value.to_string().into()))
+			},
+			TypeDef::BitSequence(bits) => {
+				let store = self.types.resolve(bits.bit_store_type.id).map(|ty| &ty.type_def);
+				let store_bits = match store {
+					Some(TypeDef::Primitive(TypeDefPrimitive::U8)) => 8,
+					Some(TypeDef::Primitive(TypeDefPrimitive::U16)) => 16,
+					Some(TypeDef::Primitive(TypeDefPrimitive::U32)) => 32,
+					Some(TypeDef::Primitive(TypeDefPrimitive::U64)) => 64,
+					_ => return Err("Bit sequence store is not an unsigned integer".into()),
+				};
+				let len = Compact::<u32>::decode(input)?.0 as usize;
+				let stores = (len + store_bits - 1) / store_bits;
+				Self::bytes(stores * store_bits / 8, input)
+			},
+		}
+	}
+
+	/// Decode the fields of a struct or an enum variant: an object if they are named, the value
+	/// itself if there is a single unnamed field, an array otherwise.
+	fn fields(
+		&mut self,
+		fields: &[Field<PortableForm>],
+		input: &mut &[u8],
+	) -> Result<Value, CodecError> {
+		match fields {
+			[] => Ok(Value::Null),
+			[field] if field.name.is_none() => self.value(field.ty.id, input),
+			fields if fields.iter().all(|field| field.name.is_some()) => fields
+				.iter()
+				.map(|field| {
+					let name = field.name.clone().unwrap_or_default();
+					Ok((name, self.value(field.ty.id, input)?))
+				})
+				.collect::<Result<Map<_, _>, _>>()
+				.map(Value::Object),
+			fields => fields.iter().map(|field| self.value(field.ty.id, input)).collect(),
+		}
+	}
+
+	/// Decode `len` items of type `ty`.
+	fn items(&mut self, ty: u32, len: u32, input: &mut &[u8]) -> Result<Value, CodecError> {
+		let is_byte = matches!(
+			self.types.resolve(ty).map(|ty| &ty.type_def),
+			Some(TypeDef::Primitive(TypeDefPrimitive::U8))
+		);
+		if is_byte {
+			return Self::bytes(len as usize, input)
+		}
+		(0..len).map(|_| self.value(ty, input)).collect()
+	}
+
+	/// Read `len` bytes, hex-encoded.
+	fn bytes(len: usize, input: &mut &[u8]) -> Result<Value, CodecError> {
+		if len > input.len() {
+			return Err("Not enough data to fill buffer".into())
+		}
+		let mut bytes = vec![0; len];
+		input.read(&mut bytes)?;
+		Ok(array_bytes::bytes2hex("0x", bytes).into())
+	}
+
+	fn primitive(primitive: &TypeDefPrimitive, input: &mut &[u8]) -> Result<Value, CodecError> {
+		Ok(match primitive {
+			TypeDefPrimitive::Bool => bool::decode(input)?.into(),
+			TypeDefPrimitive::Char =>
+				char::from_u32(u32::decode(input)?).ok_or("Invalid char")?.to_string().into(),
+			TypeDefPrimitive::Str => String::decode(input)?.into(),
+			TypeDefPrimitive::U8 => u8::decode(input)?.into(),
+			TypeDefPrimitive::U16 => u16::decode(input)?.into(),
+			TypeDefPrimitive::U32 => u32::decode(input)?.into(),
+			TypeDefPrimitive::U64 => u64::decode(input)?.into(),
+			TypeDefPrimitive::U128 => u128::decode(input)?.to_string().into(),
+			TypeDefPrimitive::U256 => U256::decode(input)?.to_string().into(),
+			TypeDefPrimitive::I8 => i8::decode(input)?.into(),
+			TypeDefPrimitive::I16 => i16::decode(input)?.into(),
+			TypeDefPrimitive::I32 => i32::decode(input)?.into(),
+			TypeDefPrimitive::I64 => i64::decode(input)?.into(),
+			TypeDefPrimitive::I128 => i128::decode(input)?.to_string().into(),
+			TypeDefPrimitive::I256 => return Self::bytes(32, input),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use scale_info::{meta_type, Registry, TypeInfo};
+	use serde_json::json;
+
+	#[derive(Encode, TypeInfo)]
+	enum Status {
+		Active,
+		Frozen { until: u32 },
+	}
+
+	#[derive(Encode, TypeInfo)]
+	struct Transfer {
+		from: AccountId32,
+		to: AccountId32,
+		amount: u128,
+		#[codec(compact)]
+		nonce: u64,
+		memo: Vec<u8>,
+		statuses: Vec<Status>,
+		pair: (bool, Option<u16>),
+	}
+
+	#[test]
+	fn values_decode_to_json() {
+		let mut registry = Registry::new();
+		let ty = registry.register_type(&meta_type::<Transfer>()).id;
+		let types: PortableRegistry = registry.into();
+
+		let from = AccountId32::new([1; 32]);
+		let to = AccountId32::new([2; 32]);
+		let transfer = Transfer {
+			from: from.clone(),
+			to: to.clone(),
+			amount: u128::MAX,
+			nonce: 7,
+			memo: b"hi".to_vec(),
+			statuses: vec![Status::Active, Status::Frozen { until: 9 }],
+			pair: (true, None),
+		};
+		let encoded = transfer.encode();
+		let input = &mut &encoded[..];
+
+		let mut decoder = ValueDecoder { types: &types, accounts: Vec::new() };
+		assert_eq!(
+			decoder.value(ty, input).unwrap(),
+			json!({
+				"from": from.to_ss58check(),
+				"to": to.to_ss58check(),
+				"amount": u128::MAX.to_string(),
+				"nonce": 7,
+				"memo": "0x6869",
+				"statuses": ["Active", { "Frozen": { "until": 9 } }],
+				"pair": [true, "None"],
+			})
+		);
+		assert!(input.is_empty());
+		assert_eq!(decoder.accounts, vec![[1; 32], [2; 32]]);
+	}
+}
@@ -0,0 +1,289 @@
+//! An index of the events of finalized blocks, kept in an SQLite database next to the chain
+//! database and queried with the `nativex_indexer` RPC.
+//!
+//! The indexer follows finality and decodes `System::Events` of each block with the metadata of
+//! the runtime that produced it. When the node starts it catches up with the blocks finalized
+//! while it was down, and re-indexes from genesis if the index doesn't match the chain anymore.
+//! Indexing a block needs its state: on a node that prunes state, the indexer skips the blocks
+//! whose state is gone and starts at the oldest block that still has it.
+
+mod db;
+mod decode;
+
+use std::{
+	collections::HashMap,
+	fmt,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use frame_system::Phase;
+use futures::StreamExt;
+use sc_client_api::{Backend, BlockBackend, BlockchainEvents, StorageProvider};
+use sc_service::{config::Configuration, TaskManager};
+use sp_api::{ConstructRuntimeApi, Core, Metadata, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::StorageKey;
+use sp_runtime::traits::{BlakeTwo256, Hash as HashT};
+
+use crate::{
+	Block, BlockNumber, FullBackend, FullClient, Hash, NativeExecutionDispatch,
+	RuntimeApiCollection,
+};
+
+use db::NewEvent;
+pub use db::{
+	EventCursor, EventFilter, EventPage, IndexedEvent, IndexerDb, DEFAULT_LIMIT, MAX_LIMIT,
+};
+pub use decode::{decode_value, DecodedEvent, EventsDecoder};
+
+const LOG_TARGET: &str = "indexer";
+
+/// Options of the event indexer.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct IndexerConfiguration {
+	/// Index the events of finalized blocks and serve them with the `nativex_indexer` RPC.
+	#[arg(long)]
+	pub enable_indexer: bool,
+
+	/// Drop the event index and rebuild it from genesis when the node starts.
+	///
+	/// Indexing a block needs its state, so re-indexing an existing chain needs a node that keeps
+	/// the state of every block (`--state-pruning archive`).
+	#[arg(long, requires = "enable_indexer")]
+	pub indexer_reindex: bool,
+}
+
+/// Where the event index of `config`'s chain lives.
+pub fn db_path(config: &Configuration) -> PathBuf {
+	crate::eth::db_config_dir(config).join("indexer").join("events.sqlite")
+}
+
+/// Open the event index at `path`, creating it if needed. Empties it if `reindex` is set.
+pub fn open(path: &Path, reindex: bool) -> Result<Arc<IndexerDb>, Error> {
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir).map_err(|e| Error::Io(e.to_string()))?;
+	}
+	let db = IndexerDb::open(path)?;
+	if reindex {
+		db.clear()?;
+	}
+	Ok(Arc::new(db))
+}
+
+/// Error of the indexer.
+#[derive(Debug)]
+pub enum Error {
+	/// The index database failed.
+	Database(rusqlite::Error),
+	/// The index database could not be created.
+	Io(String),
+	/// The client failed to provide a block, its state or its metadata.
+	Client(String),
+	/// The events or the metadata of a block could not be decoded.
+	Decode(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Database(e) => write!(f, "Event index database error: {e}"),
+			Error::Io(e) => write!(f, "Event index database can't be created: {e}"),
+			Error::Client(e) => write!(f, "Client error: {e}"),
+			Error::Decode(e) => write!(f, "Decoding error: {e}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+	fn from(e: rusqlite::Error) -> Self {
+		Error::Database(e)
+	}
+}
+
+impl From<Error> for sc_service::Error {
+	fn from(e: Error) -> Self {
+		sc_service::Error::Other(e.to_string())
+	}
+}
+
+/// Keeps the event index in line with the finalized chain.
+struct Indexer<C> {
+	client: Arc<C>,
+	backend: Arc<FullBackend>,
+	db: Arc<IndexerDb>,
+	/// Decoders by runtime spec version.
+	decoders: HashMap<u32, EventsDecoder>,
+}
+
+impl<C> Indexer<C>
+where
+	C: ProvideRuntimeApi<Block>
+		+ BlockBackend<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, FullBackend>,
+	C::Api: Core<Block> + Metadata<Block>,
+{
+	/// Drop what the index holds beyond the finalized chain, all of it if its blocks are not
+	/// the ones of the chain.
+	fn reconcile(&self) -> Result<(), Error> {
+		let finalized = self.client.info().finalized_number;
+		self.db.remove_after(finalized)?;
+		if let Some((number, hash)) = self.db.last_block()? {
+			let canonical = self.client.hash(number).map_err(|e| Error::Client(e.to_string()))?;
+			if canonical != Some(hash) {
+				log::warn!(
+					target: LOG_TARGET,
+					"Event index doesn't match the chain at #{number}, re-indexing from genesis",
+				);
+				self.db.clear()?;
+			}
+		}
+		Ok(())
+	}
+
+	/// Index the finalized blocks that are not indexed yet.
+	///
+	/// If the state of the next block is pruned, the blocks up to the oldest one with state are
+	/// skipped: their events can't be read anymore.
+	fn catch_up(&mut self) -> Result<(), Error> {
+		let finalized = self.client.info().finalized_number;
+		let mut next = self.db.last_block()?.map_or(0, |(number, _)| number + 1);
+		if next <= finalized && !self.has_state(next)? {
+			let oldest = self.oldest_state(next, finalized)?;
+			log::warn!(
+				target: LOG_TARGET,
+				"The state of blocks #{next} to #{} is pruned, their events are not indexed. \
+				 Run the node with `--state-pruning archive` to index every block.",
+				oldest - 1,
+			);
+			next = oldest;
+		}
+		for number in next..=finalized {
+			self.index_block(number)?;
+			if number % 10_000 == 0 && number < finalized {
+				log::info!(
+					target: LOG_TARGET,
+					"Indexed events up to block #{number} of #{finalized}",
+				);
+			}
+		}
+		Ok(())
+	}
+
+	/// Whether the state of finalized block `number` is still in the database.
+	fn has_state(&self, number: BlockNumber) -> Result<bool, Error> {
+		let hash = self.client.hash(number).map_err(|e| Error::Client(e.to_string()))?;
+		Ok(hash.map_or(false, |hash| self.backend.have_state_at(hash, number)))
+	}
+
+	/// The oldest finalized block in `from..=to` that has its state.
+	///
+	/// State is pruned from the oldest blocks on, so the blocks with state follow those without.
+	fn oldest_state(&self, from: BlockNumber, to: BlockNumber) -> Result<BlockNumber, Error> {
+		if !self.has_state(to)? {
+			return Err(Error::Client(format!("The state of finalized block #{to} is pruned")))
+		}
+		// The block at `low` has no state, the one at `high` has.
+		let (mut low, mut high) = (from, to);
+		while high - low > 1 {
+			let middle = low + (high - low) / 2;
+			if self.has_state(middle)? {
+				high = middle;
+			} else {
+				low = middle;
+			}
+		}
+		Ok(high)
+	}
+
+	fn index_block(&mut self, number: BlockNumber) -> Result<(), Error> {
+		let client_error = |e: sp_blockchain::Error| Error::Client(e.to_string());
+		let hash = self
+			.client
+			.hash(number)
+			.map_err(client_error)?
+			.ok_or_else(|| Error::Client(format!("Block #{number} is not in the database")))?;
+		// There are no events in the genesis block.
+		let Some(events) = self.client.storage(hash, &events_key()).map_err(client_error)? else {
+			return Ok(self.db.insert_block(number, hash, Vec::new())?)
+		};
+		let extrinsics = self.client.block_body(hash).map_err(client_error)?.unwrap_or_default();
+
+		let events = self
+			.decoder(hash)?
+			.decode(&events.0)
+			.map_err(|e| Error::Decode(format!("Events of block #{number}: {e}")))?
+			.into_iter()
+			.map(|event| {
+				let extrinsic_index = match event.phase {
+					Phase::ApplyExtrinsic(index) => Some(index),
+					_ => None,
+				};
+				NewEvent {
+					extrinsic_index,
+					extrinsic_hash: extrinsic_index
+						.and_then(|index| extrinsics.get(index as usize))
+						.map(BlakeTwo256::hash_of),
+					pallet: event.pallet,
+					event: event.event,
+					fields: event.fields,
+					accounts: event.accounts,
+				}
+			})
+			.collect();
+		Ok(self.db.insert_block(number, hash, events)?)
+	}
+
+	/// The decoder for the events of block `hash`.
+	fn decoder(&mut self, hash: Hash) -> Result<&EventsDecoder, Error> {
+		let api = self.client.runtime_api();
+		let api_error = |e: sp_api::ApiError| Error::Client(e.to_string());
+		let spec_version = api.version(hash).map_err(api_error)?.spec_version;
+		if !self.decoders.contains_key(&spec_version) {
+			let metadata = api.metadata(hash).map_err(api_error)?;
+			let decoder = EventsDecoder::new(&metadata[..])
+				.map_err(|e| Error::Decode(format!("Metadata of runtime {spec_version}: {e}")))?;
+			self.decoders.insert(spec_version, decoder);
+		}
+		Ok(&self.decoders[&spec_version])
+	}
+}
+
+/// The storage key of `System::Events`.
+fn events_key() -> StorageKey {
+	let mut key = sp_core::twox_128(b"System").to_vec();
+	key.extend(sp_core::twox_128(b"Events"));
+	StorageKey(key)
+}
+
+/// Spawn the task that keeps the event index in line with the finalized chain.
+pub fn spawn_indexer_task<RuntimeApi, Executor>(
+	task_manager: &TaskManager,
+	client: Arc<FullClient<RuntimeApi, Executor>>,
+	backend: Arc<FullBackend>,
+	db: Arc<IndexerDb>,
+) where
+	RuntimeApi:
+		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
+	RuntimeApi::RuntimeApi:
+		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
+	Executor: NativeExecutionDispatch + 'static,
+{
+	let mut finality_notifications = client.finality_notification_stream();
+	let mut indexer = Indexer { client, backend, db, decoders: HashMap::new() };
+	task_manager.spawn_handle().spawn_blocking("event-indexer", Some("indexer"), async move {
+		if let Err(e) = indexer.reconcile().and_then(|()| indexer.catch_up()) {
+			log::error!(target: LOG_TARGET, "{e}");
+		}
+		// Each notification may stand for several blocks, and the indexer may have fallen behind
+		// after an error, so every notification catches up with the finalized chain.
+		while finality_notifications.next().await.is_some() {
+			if let Err(e) = indexer.catch_up() {
+				log::error!(target: LOG_TARGET, "{e}");
+			}
+		}
+	});
+}
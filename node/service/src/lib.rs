@@ -13,7 +13,7 @@ use sc_network_common::sync::warp::WarpSyncParams;
 use sc_network_sync::SyncingService;
 use sc_service::{config::Configuration, error::Error as ServiceError, RpcHandlers, TaskManager, ChainSpec};
 use sc_executor::{NativeExecutionDispatch};
-//use sc_statement_store::Store as StatementStore;
+use sc_statement_store::Store as StatementStore;
 use sc_telemetry::{telemetry, Telemetry, TelemetryWorker};
 use sp_api::ConstructRuntimeApi;
 use sp_core::crypto::Pair;
@@ -26,7 +26,12 @@ pub use common_primitives::{AccountId, Balance, Block, BlockNumber, Hash, Index}
 
 pub mod chain_spec;
 pub mod client;
+pub mod eth;
+pub mod health;
+pub mod indexer;
 pub mod rpc;
+pub mod slashing_protection;
+pub mod storage_chain;
 
 
 #[cfg(feature = "with-development-runtime")]
@@ -51,6 +56,12 @@ type FullGranpaBlockImport<RuntimeApi, Executor> = grandpa::GrandpaBlockImport<
 	FullClient<RuntimeApi, Executor>,
 	FullSelectChain,
 >;
+type FullEthDeps<RuntimeApi, Executor> = rpc::EthDeps<
+	FullClient<RuntimeApi, Executor>,
+	TransactionPool<RuntimeApi, Executor>,
+	sc_transaction_pool::FullChainApi<FullClient<RuntimeApi, Executor>, Block>,
+	fp_rpc::NoTransactionConverter,
+>;
 
 #[cfg(feature = "with-development-runtime")]
 pub struct DevelopmentExecutor;
@@ -59,10 +70,13 @@ pub struct DevelopmentExecutor;
 impl sc_executor::NativeExecutionDispatch for DevelopmentExecutor {
 	/// Only enable the benchmarking host functions when we actually want to benchmark.
 	#[cfg(feature = "runtime-benchmarks")]
-	type ExtendHostFunctions = frame_benchmarking::benchmarking::HostFunctions;
-	/// Otherwise we only use the default Substrate host functions.
+	type ExtendHostFunctions = (
+		frame_benchmarking::benchmarking::HostFunctions,
+		sp_statement_store::runtime_api::HostFunctions,
+	);
+	/// Otherwise we only add the statement store host functions to the default Substrate ones.
 	#[cfg(not(feature = "runtime-benchmarks"))]
-	type ExtendHostFunctions = ();
+	type ExtendHostFunctions = sp_statement_store::runtime_api::HostFunctions;
 
 	fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
 		development_runtime::api::dispatch(method, data)
@@ -147,6 +161,8 @@ pub fn new_partial<RuntimeApi, Executor>(
 			impl Fn(
 				rpc::DenyUnsafe,
 				sc_rpc::SubscriptionTaskExecutor,
+				FullEthDeps<RuntimeApi, Executor>,
+				Option<Arc<indexer::IndexerDb>>,
 			) -> Result<jsonrpsee::RpcModule<()>, sc_service::Error>,
 			(
 				sc_consensus_babe::BabeBlockImport<
@@ -159,7 +175,8 @@ pub fn new_partial<RuntimeApi, Executor>(
 			),
 			grandpa::SharedVoterState,
 			Option<Telemetry>,
-			//Arc<StatementStore>,
+			Arc<eth::FrontierBackend>,
+			Arc<StatementStore>,
 		),
 	>,
 	ServiceError,
@@ -199,6 +216,12 @@ where
 
 	let select_chain = sc_consensus::LongestChain::new(backend.clone());
 
+	let frontier_backend = Arc::new(eth::FrontierBackend::open(
+		client.clone(),
+		&config.database,
+		&eth::db_config_dir(config),
+	)?);
+
 	let transaction_pool = sc_transaction_pool::BasicPool::new_full(
 		config.transaction_pool.clone(),
 		config.role.is_authority().into(),
@@ -207,6 +230,18 @@ where
 		client.clone(),
 	);
 
+	let statement_store = StatementStore::new_shared(
+		&config.data_path,
+		Default::default(),
+		client.clone(),
+		keystore_container.local_keystore(),
+		config.prometheus_registry(),
+		&task_manager.spawn_handle(),
+	)
+	.map_err(|e| ServiceError::Other(format!("Statement store error: {:?}", e)))?;
+	// Lets the offchain worker of the statement pallet submit statements to the store.
+	client.execution_extensions().set_statement_store(statement_store.clone());
+
 	let (grandpa_block_import, grandpa_link) = grandpa::block_import(
 		client.clone(),
 		&(client.clone() as Arc<_>),
@@ -259,28 +294,34 @@ where
 		let chain_spec = config.chain_spec.cloned_box();
 
 		let rpc_backend = backend.clone();
-		//let rpc_statement_store = statement_store.clone();
-		let rpc_extensions_builder = move |deny_unsafe, subscription_executor| {
-			let deps = rpc::FullDeps {
-				client: client.clone(),
-				pool: pool.clone(),
-				select_chain: select_chain.clone(),
-				chain_spec: chain_spec.cloned_box(),
-				babe: rpc::BabeDeps {
-					keystore: keystore.clone(),
-					babe_worker_handle: babe_worker_handle.clone(),
-				},
-				deny_unsafe,
-				grandpa: rpc::GrandpaDeps {
-					shared_voter_state: shared_voter_state.clone(),
-					shared_authority_set: shared_authority_set.clone(),
-					justification_stream: justification_stream.clone(),
-					subscription_executor,
-					finality_provider: finality_proof_provider.clone(),
-				},
+		let rpc_statement_store = statement_store.clone();
+		let rpc_extensions_builder =
+			move |deny_unsafe,
+			      subscription_executor,
+			      eth: FullEthDeps<RuntimeApi, Executor>,
+			      indexer: Option<Arc<indexer::IndexerDb>>| {
+				let deps = rpc::FullDeps {
+					client: client.clone(),
+					pool: pool.clone(),
+					select_chain: select_chain.clone(),
+					chain_spec: chain_spec.cloned_box(),
+					babe: rpc::BabeDeps {
+						keystore: keystore.clone(),
+						babe_worker_handle: babe_worker_handle.clone(),
+					},
+					deny_unsafe,
+					grandpa: rpc::GrandpaDeps {
+						shared_voter_state: shared_voter_state.clone(),
+						shared_authority_set: shared_authority_set.clone(),
+						justification_stream: justification_stream.clone(),
+						subscription_executor,
+						finality_provider: finality_proof_provider.clone(),
+					},
+					statement_store: rpc_statement_store.clone(),
+					indexer,
+				};
+				rpc::create_full(deps, eth, rpc_backend.clone()).map_err(Into::into)
 			};
-			rpc::create_full(deps, rpc_backend.clone()).map_err(Into::into)
-		};
 		(rpc_extensions_builder, shared_voter_state2)
 	};
 
@@ -292,7 +333,14 @@ where
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (rpc_extension_builder, import_setup, rpc_setup, telemetry),
+		other: (
+			rpc_extension_builder,
+			import_setup,
+			rpc_setup,
+			telemetry,
+			frontier_backend,
+			statement_store,
+		),
 	})
 }
 
@@ -321,7 +369,11 @@ where
 
 /// Builds a new service for a full client.
 pub fn new_full_base<RuntimeApi, Executor>(
-	 config: Configuration,
+	mut config: Configuration,
+	eth_config: eth::EthConfiguration,
+	indexer_config: indexer::IndexerConfiguration,
+	storage_chain: storage_chain::StorageChainConfiguration,
+	health_config: health::HealthConfiguration,
 ) -> Result<NewFullBase<RuntimeApi, Executor>, ServiceError>
 where
 	RuntimeApi:
@@ -330,6 +382,7 @@ where
 		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
 	Executor: NativeExecutionDispatch + 'static,
 {
+	storage_chain::configure(&mut config, &storage_chain)?;
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -338,7 +391,8 @@ where
 		keystore_container,
 		select_chain,
 		transaction_pool,
-		other: (rpc_builder, import_setup, rpc_setup, mut telemetry),
+		other:
+			(rpc_builder, import_setup, rpc_setup, mut telemetry, frontier_backend, statement_store),
 	} = new_partial(&config)?;
 	let shared_voter_state = rpc_setup;
 	let auth_disc_publish_non_global_ips = config.network.allow_non_globals_in_dht;
@@ -351,15 +405,11 @@ where
 	//net_config.add_notification_protocol(grandpa::grandpa_peers_set_config(
 	//	grandpa_protocol_name.clone(),
 	//));
-	// let statement_handler_proto = sc_network_statement::StatementHandlerPrototype::new(
-	// 	client
-	// 		.block_hash((0u32).into())
-	// 		.ok()
-	// 		.flatten()
-	// 		.expect("Genesis block exist; qed"),
-	// 	config.chain_spec.fork_id(),
-	// );
-	//net_config.add_notification_protocol(statement_handler_proto.set_config());
+	let statement_handler_proto = sc_network_statement::StatementHandlerPrototype::new(
+		client.block_hash(0).ok().flatten().expect("Genesis block exists; qed"),
+		config.chain_spec.fork_id(),
+	);
+	config.network.extra_sets.push(statement_handler_proto.set_config());
 
 	let warp_sync = Arc::new(grandpa::warp_proof::NetworkProvider::new(
 		backend.clone(),
@@ -384,6 +434,26 @@ where
 			warp_sync_params: Some(WarpSyncParams::WithProvider(warp_sync)),
 		})?;
 
+	// Gossips statements with the peers that support the statement protocol.
+	let statement_protocol_executor = {
+		let spawn_handle = task_manager.spawn_handle();
+		Box::new(move |fut| {
+			spawn_handle.spawn("network-statement-validator", Some("networking"), fut);
+		})
+	};
+	let statement_handler = statement_handler_proto.build(
+		network.clone(),
+		sync_service.clone(),
+		statement_store.clone(),
+		config.prometheus_registry(),
+		statement_protocol_executor,
+	)?;
+	task_manager.spawn_handle().spawn(
+		"network-statement-handler",
+		Some("networking"),
+		statement_handler.run(),
+	);
+
 	if config.offchain_worker.enabled {
 		sc_service::build_offchain_workers(
 			&config,
@@ -402,9 +472,82 @@ where
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let eth::FrontierPartialComponents { filter_pool, fee_history_cache, fee_history_cache_limit } =
+		eth::new_frontier_partial(&eth_config);
+	let overrides = fc_storage::overrides_handle(client.clone());
+	let block_data_cache = Arc::new(rpc::eth::EthBlockDataCacheTask::new(
+		task_manager.spawn_handle(),
+		overrides.clone(),
+		eth_config.eth_log_block_cache,
+		eth_config.eth_statuses_cache,
+		prometheus_registry.clone(),
+	));
+	// Sinks for the `eth_subscribe` notifications the mapping sync worker sends once a block is
+	// mapped, so subscribers never see a block the Ethereum RPC can't yet resolve.
+	let pubsub_notification_sinks: fc_mapping_sync::EthereumBlockNotificationSinks<
+		fc_mapping_sync::EthereumBlockNotification<Block>,
+	> = Default::default();
+	let pubsub_notification_sinks = Arc::new(pubsub_notification_sinks);
+
+	let indexer_db = if indexer_config.enable_indexer {
+		Some(indexer::open(&indexer::db_path(&config), indexer_config.indexer_reindex)?)
+	} else {
+		None
+	};
+	// Authorities author and vote through the slashing protection, so that running their keys on
+	// two nodes doesn't get them slashed.
+	let protection = if role.is_authority() {
+		let db = slashing_protection::open(&slashing_protection::db_path(&config))?;
+		let keystore: sp_keystore::KeystorePtr = Arc::new(
+			slashing_protection::ProtectedKeystore::new(keystore_container.keystore(), db.clone()),
+		);
+		Some((keystore, db))
+	} else {
+		None
+	};
+
+	let rpc_builder = {
+		let client = client.clone();
+		let pool = transaction_pool.clone();
+		let is_authority = role.is_authority();
+		let network = network.clone();
+		let sync = sync_service.clone();
+		let frontier_backend = frontier_backend.clone();
+		let overrides = overrides.clone();
+		let filter_pool = filter_pool.clone();
+		let fee_history_cache = fee_history_cache.clone();
+		let pubsub_notification_sinks = pubsub_notification_sinks.clone();
+		let eth_config = eth_config.clone();
+		let indexer_db = indexer_db.clone();
+
+		move |deny_unsafe, subscription_executor| {
+			let eth = rpc::EthDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				graph: pool.pool().clone(),
+				converter: None::<fp_rpc::NoTransactionConverter>,
+				is_authority,
+				enable_dev_signer: eth_config.enable_dev_signer,
+				network: network.clone(),
+				sync: sync.clone(),
+				frontier_backend: frontier_backend.clone(),
+				overrides: overrides.clone(),
+				block_data_cache: block_data_cache.clone(),
+				filter_pool: filter_pool.clone(),
+				max_past_logs: eth_config.max_past_logs,
+				fee_history_cache: fee_history_cache.clone(),
+				fee_history_cache_limit,
+				execute_gas_limit_multiplier: eth_config.execute_gas_limit_multiplier,
+				forced_parent_hashes: None,
+				pubsub_notification_sinks: pubsub_notification_sinks.clone(),
+			};
+			rpc_builder(deny_unsafe, subscription_executor, eth, indexer_db.clone())
+		}
+	};
+
 	let rpc_handlers = sc_service::spawn_tasks(sc_service::SpawnTasksParams {
 		config,
-		backend,
+		backend: backend.clone(),
 		client: client.clone(),
 		keystore: keystore_container.keystore(),
 		network: network.clone(),
@@ -417,9 +560,32 @@ where
 		telemetry: telemetry.as_mut(),
 	})?;
 	let (block_import, grandpa_link, babe_link) = import_setup;
+
+	eth::spawn_frontier_tasks(
+		&task_manager,
+		client.clone(),
+		backend.clone(),
+		frontier_backend,
+		filter_pool,
+		overrides,
+		fee_history_cache,
+		fee_history_cache_limit,
+		sync_service.clone(),
+		pubsub_notification_sinks,
+	);
+	if let Some(indexer_db) = indexer_db {
+		indexer::spawn_indexer_task(&task_manager, client.clone(), backend, indexer_db);
+	}
+	health::spawn_health_server(
+		&task_manager,
+		health_config,
+		client.clone(),
+		sync_service.clone(),
+		keystore_container.keystore(),
+	)?;
 	//(wit_startup_data)(&block_import, &babe_link);
 
-	if let sc_service::config::Role::Authority {..} = &role {
+	if let Some((keystore, db)) = &protection {
 		let proposer = sc_basic_authorship::ProposerFactory::new(
 			task_manager.spawn_handle(),
 			client.clone(),
@@ -431,11 +597,15 @@ where
 		let client_clone = client.clone();
 		let slot_duration = babe_link.config().slot_duration();
 		let babe_config = sc_consensus_babe::BabeParams {
-			keystore: keystore_container.keystore(),
+			keystore: keystore.clone(),
 			client: client.clone(),
 			select_chain,
 			env: proposer,
-			block_import,
+			block_import: slashing_protection::ProtectedBlockImport::new(
+				block_import,
+				keystore.clone(),
+				db.clone(),
+			),
 			sync_oracle: sync_service.clone(),
 			justification_sync_link: sync_service.clone(),
 			create_inherent_data_providers: move |parent, ()| {
@@ -500,9 +670,7 @@ where
 	}
 	// if the node isn't actively participating in consensus then it doesn't
 	// need a keystore, regardless of which protocol we use below
-	let keystore = if role.is_authority() 
-	{ Some(keystore_container.keystore())} 
-	else { None};
+	let keystore = protection.map(|(keystore, _)| keystore);
 
 	let config = grandpa::Config {
 		// FIXE #1578 make this available through chainspec.
@@ -548,7 +716,13 @@ where
 }
 
 /// Builds a new service for a full client.
-pub fn new_full<RuntimeApi, Executor>(config: Configuration) -> Result<TaskManager, ServiceError>
+pub fn new_full<RuntimeApi, Executor>(
+	config: Configuration,
+	eth_config: eth::EthConfiguration,
+	indexer_config: indexer::IndexerConfiguration,
+	storage_chain: storage_chain::StorageChainConfiguration,
+	health_config: health::HealthConfiguration,
+) -> Result<TaskManager, ServiceError>
 where
 	RuntimeApi:
 		ConstructRuntimeApi<Block, FullClient<RuntimeApi, Executor>> + Send + Sync + 'static,
@@ -556,6 +730,12 @@ where
 		RuntimeApiCollection<StateBackend = sc_client_api::StateBackendFor<FullBackend, Block>>,
 	Executor: NativeExecutionDispatch + 'static,
 {
-	new_full_base::<RuntimeApi, Executor>(config)
+	new_full_base::<RuntimeApi, Executor>(
+		config,
+		eth_config,
+		indexer_config,
+		storage_chain,
+		health_config,
+	)
 		.map(|NewFullBase { task_manager, .. }| task_manager)
 }
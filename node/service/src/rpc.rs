@@ -5,7 +5,7 @@
 
 #![warn(missing_docs)]
 
-use crate::{client::RuntimeApiCollection, Block, BlockNumber, Hash};
+use crate::{client::RuntimeApiCollection, AccountId, Balance, Block, BlockNumber, Hash};
 use grandpa::{
 	FinalityProofProvider, GrandpaJustificationStream, SharedAuthoritySet, SharedVoterState,
 };
@@ -28,6 +28,16 @@ use std::sync::Arc;
 #[cfg(feature = "with-development-runtime")]
 use development_runtime;
 
+pub mod account_overview;
+pub mod contracts_debug;
+pub mod eth;
+pub mod fee;
+pub mod indexer;
+pub mod transaction_storage;
+pub mod unified_accounts;
+
+pub use eth::EthDeps;
+
 /// Extra dependencies for BABE.
 pub struct BabeDeps {
 	/// A handle to the BABE worker for issuing requests.
@@ -66,16 +76,23 @@ pub struct FullDeps<C, P, SC, B> {
 	pub babe: BabeDeps,
 	/// GRANDPA specific denpendencies
 	pub grandpa: GrandpaDeps<B>,
+	/// The store of the statements gossiped off-chain.
+	pub statement_store: Arc<dyn sp_statement_store::StatementStore>,
+	/// The event index, if the indexer is enabled.
+	pub indexer: Option<Arc<crate::indexer::IndexerDb>>,
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P, SC, BE>(
+pub fn create_full<C, P, SC, BE, A, CT>(
 	deps: FullDeps<C, P, SC, BE>,
+	eth: EthDeps<C, P, A, CT>,
 	backend: Arc<BE>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>
 		+ sc_client_api::BlockBackend<Block>
+		+ sc_client_api::BlockchainEvents<Block>
+		+ sc_client_api::StorageProvider<Block, BE>
 		+ HeaderBackend<Block>
 		+ AuxStore
 		+ HeaderMetadata<Block, Error = BlockChainError>
@@ -84,19 +101,30 @@ where
 		+ 'static,
 	C::Api: RuntimeApiCollection<StateBackend = BE::State>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	P: TransactionPool<Block = Block> + 'static,
 	SC: SelectChain<Block> + 'static,
 	BE: sc_client_api::Backend<Block> + Send + Sync + 'static,
+	A: sc_transaction_pool::ChainApi<Block = Block> + 'static,
+	CT: fp_rpc::ConvertTransaction<<Block as sp_runtime::traits::Block>::Extrinsic>
+		+ Send
+		+ Sync
+		+ 'static,
 	BE::State: sc_client_api::backend::StateBackend<sp_runtime::traits::HashFor<Block>>,
 	BE::Blockchain: BlockchainBackend<Block>,
 {
+	use self::account_overview::{AccountOverviewApiServer, AccountOverviews};
+	use self::contracts_debug::{ContractsDebug, ContractsDebugApiServer};
+	use self::fee::{FeeEstimation, FeeEstimationApiServer};
+	use self::indexer::{Indexer, IndexerApiServer};
+	use self::transaction_storage::{TransactionStorage, TransactionStorageApiServer};
+	use self::unified_accounts::{UnifiedAccounts, UnifiedAccountsApiServer};
 	//use mmr_rpc::{Mmr, MmrApiServer};
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use sc_consensus_babe_rpc::{Babe, BabeApiServer};
 	use sc_consensus_grandpa_rpc::{Grandpa, GrandpaApiServer};
 	use sc_rpc::{
 		dev::{Dev, DevApiServer},
-		//statement::StatementApiServer,
+		statement::StatementApiServer,
 	};
 	use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
 	use sc_sync_state_rpc::{SyncState, SyncStateApiServer};
@@ -104,7 +132,17 @@ where
 	use substrate_state_trie_migration_rpc::{StateMigration, StateMigrationApiServer};
 
 	let mut io = RpcModule::new(());
-	let FullDeps { client, pool, select_chain, chain_spec, deny_unsafe, babe, grandpa } = deps;
+	let FullDeps {
+		client,
+		pool,
+		select_chain,
+		chain_spec,
+		deny_unsafe,
+		babe,
+		grandpa,
+		statement_store,
+		indexer,
+	} = deps;
 	let BabeDeps { keystore, babe_worker_handle } = babe;
 	let GrandpaDeps {
 		shared_voter_state,
@@ -120,13 +158,25 @@ where
 	// These RPCs should use an asynchronous caller instead.
 	// io.merge(Mmr::new(client.clone()).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(FeeEstimation::new(client.clone()).into_rpc())?;
+	io.merge(UnifiedAccounts::<_, Block, AccountId>::new(client.clone()).into_rpc())?;
+	let account_overviews = AccountOverviews::<_, BE, Block, Balance>::new(client.clone());
+	io.merge(AccountOverviewApiServer::<_, AccountId, BlockNumber>::into_rpc(account_overviews))?;
+	io.merge(
+		ContractsDebug::<_, _, Block, AccountId, Balance>::new(client.clone(), backend.clone())
+			.into_rpc(),
+	)?;
+	io.merge(TransactionStorage::<_, Block>::new(client.clone()).into_rpc())?;
+	if let Some(indexer) = indexer {
+		io.merge(Indexer::new(indexer).into_rpc())?;
+	}
 	io.merge(
 		Babe::new(client.clone(), babe_worker_handle.clone(), keystore, select_chain, deny_unsafe)
 			.into_rpc(),
 	)?;
 	io.merge(
 		Grandpa::new(
-			subscription_executor,
+			subscription_executor.clone(),
 			shared_authority_set.clone(),
 			shared_voter_state,
 			justification_stream,
@@ -140,9 +190,11 @@ where
 	// )?;
 	// io.merge(StateMigration::new(client.clone(), backend, deny_unsafe).into_rpc())?;
 	// io.merge(Dev::new(client, deny_unsafe).into_rpc())?;
-	// let statement_store =
-	// 	sc_rpc::statement::StatementStore::new(statement_store, deny_unsafe).into_rpc();
-	// io.merge(statement_store)?;
+	let statement_store =
+		sc_rpc::statement::StatementStore::new(statement_store, deny_unsafe).into_rpc();
+	io.merge(statement_store)?;
+
+	let io = eth::create_eth(io, eth, subscription_executor)?;
 
 	Ok(io)
 }
@@ -0,0 +1,611 @@
+//! RPC for the state of an account across pallets in one call.
+//!
+//! The runtime API returns what can be read by key. Account indices and multisig deposits are
+//! only found by iterating a whole map, so the node adds them by iterating the keys under the
+//! map's prefix instead of having the runtime do it.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use account_overview_runtime_api::{self as api, AccountOverviewApi as AccountOverviewRuntimeApi};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::{Codec, Decode};
+use sc_client_api::{Backend, StorageKey, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, Bytes, H256};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::Block as BlockT;
+
+/// A balance lock.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BalanceLock {
+	/// The identifier of the lock.
+	pub id: Bytes,
+	/// The locked amount.
+	pub amount: NumberOrHex,
+	/// The withdrawals the lock prevents: `fee`, `misc` or `all`.
+	pub reasons: String,
+}
+
+/// An amount reserved, held or frozen for a named reason.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NamedAmount {
+	/// The SCALE-encoded identifier of the reason.
+	pub id: Bytes,
+	/// The amount.
+	pub amount: NumberOrHex,
+}
+
+/// The native balance of an account.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Balances {
+	/// The free balance, including what is locked or frozen.
+	pub free: NumberOrHex,
+	/// The reserved balance, including what is held.
+	pub reserved: NumberOrHex,
+	/// The part of the free balance that can't be withdrawn.
+	pub frozen: NumberOrHex,
+	/// The locks that make up `frozen`.
+	pub locks: Vec<BalanceLock>,
+	/// The freezes that make up `frozen`.
+	pub freezes: Vec<NamedAmount>,
+	/// The named reserves that are part of `reserved`.
+	pub reserves: Vec<NamedAmount>,
+	/// The holds that are part of `reserved`.
+	pub holds: Vec<NamedAmount>,
+}
+
+/// A `pallet_assets` asset the account holds.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBalance {
+	/// The asset.
+	pub asset_id: u32,
+	/// The balance of the account.
+	pub balance: NumberOrHex,
+	/// The symbol of the asset.
+	pub symbol: String,
+	/// The decimals of the asset.
+	pub decimals: u8,
+}
+
+/// A non-fungible item the account owns.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Item {
+	/// The collection of the item.
+	pub collection: u32,
+	/// The item.
+	pub item: u32,
+}
+
+/// An amount that can be withdrawn from the given era on.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Unbonding {
+	/// The amount.
+	pub value: NumberOrHex,
+	/// The era from which it can be withdrawn.
+	pub era: u32,
+}
+
+/// The staking ledger the account is the stash or the controller of.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Staking<AccountId> {
+	/// The stash account.
+	pub stash: AccountId,
+	/// The controller account.
+	pub controller: AccountId,
+	/// The bonded balance, including what is being unbonded.
+	pub total: NumberOrHex,
+	/// The bonded balance that is not being unbonded.
+	pub active: NumberOrHex,
+	/// The balance that is being unbonded.
+	pub unlocking: Vec<Unbonding>,
+	/// The validators the stash nominates, if it is a nominator.
+	pub nominations: Option<Vec<AccountId>>,
+	/// Whether the stash wants to validate.
+	pub validator: bool,
+}
+
+/// Membership of a nomination pool.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolMembership {
+	/// The pool.
+	pub pool_id: u32,
+	/// The points of the member in the pool.
+	pub points: NumberOrHex,
+	/// What the points are worth.
+	pub balance: NumberOrHex,
+	/// The rewards the member can claim.
+	pub pending_rewards: NumberOrHex,
+	/// The points being unbonded.
+	pub unbonding: Vec<Unbonding>,
+}
+
+/// A `pallet_indices` index the account holds.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountIndex {
+	/// The index.
+	pub index: u32,
+	/// The deposit for the index.
+	pub deposit: NumberOrHex,
+	/// Whether the index is frozen to the account.
+	pub frozen: bool,
+}
+
+/// The deposit the account made for a pending `pallet_multisig` operation.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MultisigDeposit<AccountId> {
+	/// The multisig account of the operation.
+	pub multisig: AccountId,
+	/// The hash of the call of the operation.
+	pub call_hash: H256,
+	/// The deposit.
+	pub deposit: NumberOrHex,
+}
+
+/// A vesting schedule.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VestingSchedule<BlockNumber> {
+	/// The amount locked at `starting_block`.
+	pub locked: NumberOrHex,
+	/// The amount unlocked at each block from `starting_block` on.
+	pub per_block: NumberOrHex,
+	/// The block at which unlocking starts.
+	pub starting_block: BlockNumber,
+}
+
+/// A proxy of the account.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Proxy<AccountId, BlockNumber> {
+	/// The account that may act for this one.
+	pub delegate: AccountId,
+	/// The calls the delegate may make, e.g. `Any` or `Staking`.
+	pub proxy_type: String,
+	/// The number of blocks a call must be announced for before the delegate may make it.
+	pub delay: BlockNumber,
+}
+
+/// The identity of the account.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity<AccountId> {
+	/// The display name of the identity, or of the sub-identity if the account is one.
+	pub display: Option<String>,
+	/// The account this is a sub-identity of.
+	pub parent: Option<AccountId>,
+	/// Whether a registrar judged the identity `Reasonable` or `KnownGood`.
+	pub verified: bool,
+	/// The deposit for the identity.
+	pub deposit: NumberOrHex,
+	/// The deposit for the sub-identities of the account.
+	pub subs_deposit: NumberOrHex,
+}
+
+/// The state of an account across pallets, as returned to RPC callers.
+///
+/// Balances are encoded as `NumberOrHex` so that values above `u64::MAX` survive JSON.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverview<AccountId, BlockNumber> {
+	/// The native balance.
+	pub balances: Balances,
+	/// The `pallet_assets` assets the account holds.
+	pub assets: Vec<AssetBalance>,
+	/// The `pallet_nfts` items the account owns.
+	pub nfts: Vec<Item>,
+	/// The `pallet_uniques` items the account owns.
+	pub uniques: Vec<Item>,
+	/// The staking ledger of the account.
+	pub staking: Option<Staking<AccountId>>,
+	/// The nomination pool the account is a member of.
+	pub nomination_pool: Option<PoolMembership>,
+	/// The vesting schedules of the account.
+	pub vesting: Vec<VestingSchedule<BlockNumber>>,
+	/// The proxies of the account.
+	pub proxies: Vec<Proxy<AccountId, BlockNumber>>,
+	/// The deposit for the proxies of the account.
+	pub proxy_deposit: NumberOrHex,
+	/// The identity of the account.
+	pub identity: Option<Identity<AccountId>>,
+	/// The `pallet_indices` indices the account holds.
+	pub indices: Vec<AccountIndex>,
+	/// The deposits the account made for pending `pallet_multisig` operations.
+	pub multisig_deposits: Vec<MultisigDeposit<AccountId>>,
+}
+
+fn number<Balance: TryInto<NumberOrHex>>(balance: Balance) -> Result<NumberOrHex, ()> {
+	balance.try_into().map_err(|_| ())
+}
+
+fn text(bytes: Vec<u8>) -> String {
+	String::from_utf8_lossy(&bytes).into_owned()
+}
+
+fn named_amounts<Balance: TryInto<NumberOrHex>>(
+	amounts: Vec<api::NamedAmount<Balance>>,
+) -> Result<Vec<NamedAmount>, ()> {
+	amounts
+		.into_iter()
+		.map(|named| Ok(NamedAmount { id: named.id.into(), amount: number(named.amount)? }))
+		.collect()
+}
+
+fn items(items: Vec<api::Item>) -> Vec<Item> {
+	items.into_iter().map(|item| Item { collection: item.collection, item: item.item }).collect()
+}
+
+impl<AccountId, BlockNumber> AccountOverview<AccountId, BlockNumber> {
+	fn try_from_api<Balance: TryInto<NumberOrHex>>(
+		overview: api::AccountOverview<AccountId, Balance, BlockNumber>,
+	) -> Result<Self, ()> {
+		let api::Balances { free, reserved, frozen, locks, freezes, reserves, holds } =
+			overview.balances;
+		let balances = Balances {
+			free: number(free)?,
+			reserved: number(reserved)?,
+			frozen: number(frozen)?,
+			locks: locks
+				.into_iter()
+				.map(|lock| {
+					let reasons = match lock.reasons {
+						api::LockReasons::Fee => "fee",
+						api::LockReasons::Misc => "misc",
+						api::LockReasons::All => "all",
+					};
+					Ok(BalanceLock {
+						id: lock.id.to_vec().into(),
+						amount: number(lock.amount)?,
+						reasons: reasons.into(),
+					})
+				})
+				.collect::<Result<_, ()>>()?,
+			freezes: named_amounts(freezes)?,
+			reserves: named_amounts(reserves)?,
+			holds: named_amounts(holds)?,
+		};
+
+		let assets = overview
+			.assets
+			.into_iter()
+			.map(|asset| {
+				Ok(AssetBalance {
+					asset_id: asset.asset_id,
+					balance: number(asset.balance)?,
+					symbol: text(asset.symbol),
+					decimals: asset.decimals,
+				})
+			})
+			.collect::<Result<_, ()>>()?;
+
+		let unbonding = |chunks: Vec<(u32, Balance)>| {
+			chunks
+				.into_iter()
+				.map(|(era, value)| Ok(Unbonding { value: number(value)?, era }))
+				.collect::<Result<Vec<_>, ()>>()
+		};
+		let staking = match overview.staking {
+			Some(staking) => Some(Staking {
+				stash: staking.stash,
+				controller: staking.controller,
+				total: number(staking.total)?,
+				active: number(staking.active)?,
+				unlocking: unbonding(
+					staking.unlocking.into_iter().map(|chunk| (chunk.era, chunk.value)).collect(),
+				)?,
+				nominations: staking.nominations,
+				validator: staking.validator,
+			}),
+			None => None,
+		};
+		let nomination_pool = match overview.nomination_pool {
+			Some(member) => Some(PoolMembership {
+				pool_id: member.pool_id,
+				points: number(member.points)?,
+				balance: number(member.balance)?,
+				pending_rewards: number(member.pending_rewards)?,
+				unbonding: unbonding(member.unbonding)?,
+			}),
+			None => None,
+		};
+
+		let vesting = overview
+			.vesting
+			.into_iter()
+			.map(|schedule| {
+				Ok(VestingSchedule {
+					locked: number(schedule.locked)?,
+					per_block: number(schedule.per_block)?,
+					starting_block: schedule.starting_block,
+				})
+			})
+			.collect::<Result<_, ()>>()?;
+		let proxies = overview
+			.proxies
+			.into_iter()
+			.map(|proxy| Proxy {
+				delegate: proxy.delegate,
+				proxy_type: text(proxy.proxy_type),
+				delay: proxy.delay,
+			})
+			.collect();
+		let identity = match overview.identity {
+			Some(identity) => Some(Identity {
+				display: identity.display.map(text),
+				parent: identity.parent,
+				verified: identity.verified,
+				deposit: number(identity.deposit)?,
+				subs_deposit: number(identity.subs_deposit)?,
+			}),
+			None => None,
+		};
+
+		Ok(Self {
+			balances,
+			assets,
+			nfts: items(overview.nfts),
+			uniques: items(overview.uniques),
+			staking,
+			nomination_pool,
+			vesting,
+			proxies,
+			proxy_deposit: number(overview.proxy_deposit)?,
+			identity,
+			indices: Vec::new(),
+			multisig_deposits: Vec::new(),
+		})
+	}
+}
+
+/// The storage prefix of map `item` of `pallet`.
+fn map_prefix(pallet: &str, item: &str) -> Vec<u8> {
+	[twox_128(pallet.as_bytes()), twox_128(item.as_bytes())].concat()
+}
+
+/// The index, deposit and frozen flag of the `Indices::Accounts` entries owned by `who`.
+///
+/// `pairs` are the entries with the map prefix stripped from the keys, which are the
+/// `Blake2_128Concat` hash of the index.
+fn account_indices<AccountId: Decode + PartialEq, Balance: Decode>(
+	pairs: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+	who: &AccountId,
+) -> Result<Vec<(u32, Balance, bool)>, parity_scale_codec::Error> {
+	let mut indices = Vec::new();
+	for (key, value) in pairs {
+		let (owner, deposit, frozen) = <(AccountId, Balance, bool)>::decode(&mut &value[..])?;
+		if owner == *who {
+			let index = u32::decode(&mut key.get(16..).unwrap_or_default())?;
+			indices.push((index, deposit, frozen));
+		}
+	}
+	Ok(indices)
+}
+
+/// The multisig account, call hash and deposit of the `Multisig::Multisigs` entries `who` is the
+/// depositor of.
+///
+/// `pairs` are the entries with the map prefix stripped from the keys, which are the
+/// `Twox64Concat` hash of the multisig account followed by the `Blake2_128Concat` hash of the call
+/// hash. The values start with the timepoint, deposit and depositor of the operation.
+fn multisig_deposits<AccountId: Decode + PartialEq, Balance: Decode, BlockNumber: Decode>(
+	pairs: impl IntoIterator<Item = (Vec<u8>, Vec<u8>)>,
+	who: &AccountId,
+) -> Result<Vec<(AccountId, H256, Balance)>, parity_scale_codec::Error> {
+	let mut deposits = Vec::new();
+	for (key, value) in pairs {
+		let (_height, _index, deposit, depositor) =
+			<(BlockNumber, u32, Balance, AccountId)>::decode(&mut &value[..])?;
+		if depositor == *who {
+			let key = &mut key.get(8..).unwrap_or_default();
+			let multisig = AccountId::decode(key)?;
+			let call_hash = H256::decode(&mut key.get(16..).unwrap_or_default())?;
+			deposits.push((multisig, call_hash, deposit));
+		}
+	}
+	Ok(deposits)
+}
+
+#[rpc(client, server)]
+pub trait AccountOverviewApi<BlockHash, AccountId, BlockNumber> {
+	/// The balances, assets, items, staking, vesting, proxies and identity of `account`.
+	#[method(name = "nativex_accountOverview")]
+	fn account_overview(
+		&self,
+		account: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountOverview<AccountId, BlockNumber>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+	/// A balance does not fit into `NumberOrHex`.
+	InvalidBalance,
+	/// Reading or decoding the storage failed.
+	StorageError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidBalance => 2,
+			Error::StorageError => 3,
+		}
+	}
+}
+
+/// Provides RPC methods to get the overview of an account.
+pub struct AccountOverviews<C, BE, B, Balance> {
+	client: Arc<C>,
+	_marker: PhantomData<(BE, B, Balance)>,
+}
+
+impl<C, BE, B, Balance> AccountOverviews<C, BE, B, Balance> {
+	/// Create new `AccountOverviews` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, BE, Block, Balance> AccountOverviews<C, BE, Block, Balance>
+where
+	Block: BlockT,
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	/// The entries of map `item` of `pallet` at `at`, with the map prefix stripped from the keys.
+	fn map_pairs(
+		&self,
+		at: Block::Hash,
+		pallet: &str,
+		item: &str,
+	) -> sp_blockchain::Result<Vec<(Vec<u8>, Vec<u8>)>> {
+		let prefix = map_prefix(pallet, item);
+		let pairs = self.client.storage_pairs(at, Some(&StorageKey(prefix.clone())), None)?;
+		Ok(pairs.map(|(key, value)| (key.0[prefix.len()..].to_vec(), value.0)).collect())
+	}
+}
+
+impl<C, BE, Block, AccountId, Balance, BlockNumber>
+	AccountOverviewApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber>
+	for AccountOverviews<C, BE, Block, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ StorageProvider<Block, BE>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: AccountOverviewRuntimeApi<Block, AccountId, Balance, BlockNumber>,
+	BE: Backend<Block> + Send + Sync + 'static,
+	AccountId: Codec + PartialEq + Serialize + for<'de> Deserialize<'de> + Send + Sync + 'static,
+	Balance: Codec + TryInto<NumberOrHex> + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + Send + Sync + 'static,
+{
+	fn account_overview(
+		&self,
+		account: AccountId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<AccountOverview<AccountId, BlockNumber>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let storage_error = |e: String| {
+			CallError::Custom(ErrorObject::owned(
+				Error::StorageError.into(),
+				"Unable to read the indices and multisig deposits.",
+				Some(e),
+			))
+		};
+		let indices = self
+			.map_pairs(at_hash, "Indices", "Accounts")
+			.map_err(|e| e.to_string())
+			.and_then(|pairs| {
+				account_indices::<_, Balance>(pairs, &account).map_err(|e| e.to_string())
+			})
+			.map_err(storage_error)?;
+		let multisig_deposits = self
+			.map_pairs(at_hash, "Multisig", "Multisigs")
+			.map_err(|e| e.to_string())
+			.and_then(|pairs| {
+				multisig_deposits::<_, Balance, BlockNumber>(pairs, &account)
+					.map_err(|e| e.to_string())
+			})
+			.map_err(storage_error)?;
+
+		let overview = api.account_overview(at_hash, account).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to get the account overview.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		let invalid_balance = |_| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::InvalidBalance.into(),
+				"Account balance does not fit into NumberOrHex.",
+				None::<()>,
+			)))
+		};
+		let mut overview = AccountOverview::try_from_api(overview).map_err(invalid_balance)?;
+		overview.indices = indices
+			.into_iter()
+			.map(|(index, deposit, frozen)| {
+				Ok(AccountIndex { index, deposit: number(deposit)?, frozen })
+			})
+			.collect::<Result<_, ()>>()
+			.map_err(invalid_balance)?;
+		overview.multisig_deposits = multisig_deposits
+			.into_iter()
+			.map(|(multisig, call_hash, deposit)| {
+				Ok(MultisigDeposit { multisig, call_hash, deposit: number(deposit)? })
+			})
+			.collect::<Result<_, ()>>()
+			.map_err(invalid_balance)?;
+		Ok(overview)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use sp_core::hashing::{blake2_128, twox_64};
+
+	#[test]
+	fn indices_are_found_by_owner() {
+		let entry = |index: u32, owner: u64, deposit: u128| {
+			let key = [blake2_128(&index.encode()).to_vec(), index.encode()].concat();
+			(key, (owner, deposit, index == 7).encode())
+		};
+		let pairs = vec![entry(3, 1, 10), entry(5, 2, 20), entry(7, 1, 30)];
+
+		assert_eq!(
+			account_indices::<u64, u128>(pairs, &1).unwrap(),
+			vec![(3, 10, false), (7, 30, true)]
+		);
+	}
+
+	#[test]
+	fn multisig_deposits_are_found_by_depositor() {
+		let entry = |multisig: u64, call_hash: H256, depositor: u64, deposit: u128| {
+			let key = [
+				twox_64(&multisig.encode()).to_vec(),
+				multisig.encode(),
+				blake2_128(&call_hash.encode()).to_vec(),
+				call_hash.encode(),
+			]
+			.concat();
+			// Timepoint, deposit, depositor and approvals.
+			let value = ((4u32, 1u32), deposit, depositor, vec![depositor]).encode();
+			(key, value)
+		};
+		let (call, other_call) = (H256::repeat_byte(1), H256::repeat_byte(2));
+		let pairs =
+			vec![entry(100, call, 1, 10), entry(100, other_call, 2, 20), entry(200, call, 1, 30)];
+
+		assert_eq!(
+			multisig_deposits::<u64, u128, u32>(pairs, &1).unwrap(),
+			vec![(100, call, 10), (200, call, 30)]
+		);
+	}
+}
@@ -0,0 +1,432 @@
+//! RPC for debugging contract calls, either as a dry-run or by replaying a historical extrinsic.
+//!
+//! The result summarizes the call as a whole and is not a per-frame call trace, see
+//! [`contracts_debug_runtime_api`] for what it can and cannot show. Storage writes are the net
+//! changes the call made to the child tries contracts keep their storage in, grouped by child
+//! trie: `pallet_contracts` has no API mapping a contract to its trie, and the overlay cannot tell
+//! which frame made a change. Reads leave no trace in the overlay and are not reported.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use contracts_debug_runtime_api::{
+	CallSummary, ContractEvent, ContractsDebugApi as ContractsDebugRuntimeApi,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_contracts_primitives::StorageDeposit;
+use parity_scale_codec::Codec;
+use sc_client_api::{Backend, BlockBackend, StateBackend};
+use serde::{Deserialize, Serialize};
+use sp_api::{ApiExt, ApiRef, Core, ProvideRuntimeApi};
+use sp_block_builder::BlockBuilder;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, HashFor, Header as HeaderT};
+use sp_weights::Weight;
+
+/// Reference time and proof size, as `Weight` does not serialize in this release.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Gas {
+	/// Reference time in picoseconds.
+	pub ref_time: u64,
+	/// Proof size in bytes.
+	pub proof_size: u64,
+}
+
+impl From<Weight> for Gas {
+	fn from(weight: Weight) -> Self {
+		Self { ref_time: weight.ref_time(), proof_size: weight.proof_size() }
+	}
+}
+
+impl From<Gas> for Weight {
+	fn from(gas: Gas) -> Self {
+		Weight::from_parts(gas.ref_time, gas.proof_size)
+	}
+}
+
+/// A contract call to dry-run.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DebugCallRequest<AccountId> {
+	/// The account making the call.
+	pub origin: AccountId,
+	/// The contract to call.
+	pub dest: AccountId,
+	/// The value transferred along with the call.
+	pub value: Option<NumberOrHex>,
+	/// The gas limit, the maximum block weight if omitted.
+	pub gas_limit: Option<Gas>,
+	/// The storage deposit limit, unlimited if omitted.
+	pub storage_deposit_limit: Option<NumberOrHex>,
+	/// The SCALE-encoded message, starting with its selector.
+	pub input_data: Bytes,
+}
+
+/// The storage deposit a call charged or refunded.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Deposit {
+	/// The origin paid this deposit.
+	Charge(NumberOrHex),
+	/// The origin was refunded this deposit.
+	Refund(NumberOrHex),
+}
+
+/// The outcome of a debugged call.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum Outcome {
+	/// The contract returned. It reverted if bit 0 of `flags` is set.
+	Returned {
+		/// The return flags.
+		flags: u32,
+		/// The SCALE-encoded return value.
+		data: Bytes,
+	},
+	/// The call failed with this dispatch error.
+	Failed(String),
+}
+
+/// A contract event returned to RPC callers, see [`ContractEvent`].
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum SummaryEvent<AccountId, Hash> {
+	/// `caller` called `contract`, and the call did not revert.
+	#[serde(rename_all = "camelCase")]
+	Called { caller: AccountId, contract: AccountId },
+	/// `contract` ran the code of `code_hash` in its own context, and did not revert.
+	#[serde(rename_all = "camelCase")]
+	DelegateCalled { contract: AccountId, code_hash: Hash },
+	/// `deployer` instantiated `contract`.
+	#[serde(rename_all = "camelCase")]
+	Instantiated { deployer: AccountId, contract: AccountId },
+	/// `contract` terminated itself and sent its balance to `beneficiary`.
+	#[serde(rename_all = "camelCase")]
+	Terminated { contract: AccountId, beneficiary: AccountId },
+	/// `contract` emitted an event with `data`.
+	#[serde(rename_all = "camelCase")]
+	Emitted { contract: AccountId, data: Bytes },
+}
+
+impl<AccountId, Hash> From<ContractEvent<AccountId, Hash>> for SummaryEvent<AccountId, Hash> {
+	fn from(event: ContractEvent<AccountId, Hash>) -> Self {
+		match event {
+			ContractEvent::Called { caller, contract } => Self::Called { caller, contract },
+			ContractEvent::DelegateCalled { contract, code_hash } =>
+				Self::DelegateCalled { contract, code_hash },
+			ContractEvent::Instantiated { deployer, contract } =>
+				Self::Instantiated { deployer, contract },
+			ContractEvent::Terminated { contract, beneficiary } =>
+				Self::Terminated { contract, beneficiary },
+			ContractEvent::Emitted { contract, data } =>
+				Self::Emitted { contract, data: data.into() },
+		}
+	}
+}
+
+/// A storage item the call wrote.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageWrite {
+	/// The prefixed storage key of the child trie holding the item.
+	pub child_trie: Bytes,
+	/// The hashed key of the item.
+	pub key: Bytes,
+	/// The new value, `None` if the item was removed.
+	pub value: Option<Bytes>,
+}
+
+/// Call summary returned to RPC callers.
+///
+/// Balances are encoded as `NumberOrHex` so that values above `u64::MAX` survive JSON.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractCallSummary<AccountId, Hash> {
+	/// The selector of the message or constructor the top-level call ran.
+	pub selector: Option<Bytes>,
+	/// The gas the whole call consumed.
+	pub gas_consumed: Gas,
+	/// The gas limit the call needs to succeed.
+	pub gas_required: Gas,
+	/// The storage deposit the call charged or refunded.
+	pub storage_deposit: Deposit,
+	/// What the called contract returned, or why the call failed.
+	pub outcome: Outcome,
+	/// The debug messages the contracts printed.
+	pub debug_message: String,
+	/// The contract events of the call, in deposit order.
+	pub events: Vec<SummaryEvent<AccountId, Hash>>,
+	/// The storage items the call wrote, grouped by child trie.
+	pub storage_writes: Vec<StorageWrite>,
+}
+
+#[rpc(client, server)]
+pub trait ContractsDebugApi<BlockHash, AccountId> {
+	/// Dry-run a contract call against the state of block `at` and summarize it.
+	#[method(name = "nativex_debugContractCall")]
+	fn debug_call(
+		&self,
+		request: DebugCallRequest<AccountId>,
+		at: Option<BlockHash>,
+	) -> RpcResult<ContractCallSummary<AccountId, BlockHash>>;
+
+	/// Replay block `block` up to the extrinsic at `index` and summarize the contract call it
+	/// makes.
+	#[method(name = "nativex_debugContractExtrinsic")]
+	fn debug_extrinsic(
+		&self,
+		block: BlockHash,
+		index: u32,
+	) -> RpcResult<ContractCallSummary<AccountId, BlockHash>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+	/// The block or its state is not available.
+	UnknownBlock,
+	/// The block has no extrinsic at the requested index.
+	UnknownExtrinsic,
+	/// The extrinsic is not a signed call into `Contracts`.
+	NotAContractCall,
+	/// A value could not be converted into the runtime balance type.
+	InvalidBalance,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::UnknownBlock => 2,
+			Error::UnknownExtrinsic => 3,
+			Error::NotAContractCall => 4,
+			Error::InvalidBalance => 5,
+		}
+	}
+}
+
+fn error(code: Error, message: &str, data: Option<String>) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(code.into(), message, data)).into()
+}
+
+fn runtime_error(e: impl ToString) -> JsonRpseeError {
+	error(Error::RuntimeError, "Unable to debug contract call.", Some(e.to_string()))
+}
+
+/// Child storage changes, keyed by prefixed child key.
+type ChildChanges = Vec<(Vec<u8>, Vec<(Vec<u8>, Option<Vec<u8>>)>)>;
+
+/// Provides RPC methods to debug contract calls.
+pub struct ContractsDebug<C, BE, B, AccountId, Balance> {
+	client: Arc<C>,
+	backend: Arc<BE>,
+	_marker: PhantomData<(B, AccountId, Balance)>,
+}
+
+impl<C, BE, B, AccountId, Balance> ContractsDebug<C, BE, B, AccountId, Balance> {
+	/// Create new `ContractsDebug` with the given reference to the client and backend.
+	pub fn new(client: Arc<C>, backend: Arc<BE>) -> Self {
+		Self { client, backend, _marker: Default::default() }
+	}
+}
+
+impl<C, BE, Block, AccountId, Balance> ContractsDebug<C, BE, Block, AccountId, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + BlockBackend<Block> + Send + Sync,
+	C::Api: ContractsDebugRuntimeApi<Block, AccountId, Balance, Block::Hash>
+		+ BlockBuilder<Block>
+		+ ApiExt<Block, StateBackend = BE::State>,
+	BE: Backend<Block>,
+	BE::State: StateBackend<HashFor<Block>>,
+	AccountId: Codec + Clone + PartialEq,
+	Balance: Codec + TryInto<NumberOrHex>,
+{
+	/// Initialize the block of `header` on `api` and apply `extrinsics`, the way block import
+	/// did.
+	fn replay(
+		api: &ApiRef<C::Api>,
+		header: &Block::Header,
+		extrinsics: &[Block::Extrinsic],
+	) -> RpcResult<()> {
+		let parent = *header.parent_hash();
+		// The seal is added after execution and removed again before import.
+		let mut header = header.clone();
+		header.digest_mut().logs.retain(|item| item.as_seal().is_none());
+
+		api.initialize_block(parent, &header).map_err(runtime_error)?;
+		for extrinsic in extrinsics {
+			// Failed extrinsics are part of the block as well; only the runtime call may fail.
+			let _ = api.apply_extrinsic(parent, extrinsic.clone()).map_err(runtime_error)?;
+		}
+		Ok(())
+	}
+
+	/// The child storage `api` changed on top of block `at`.
+	fn child_changes(
+		&self,
+		api: &ApiRef<C::Api>,
+		at: Block::Hash,
+	) -> RpcResult<ChildChanges> {
+		let state = self.backend.state_at(at).map_err(|e| {
+			error(Error::UnknownBlock, "State of block not available.", Some(e.to_string()))
+		})?;
+		let changes = api.into_storage_changes(&state, at).map_err(runtime_error)?;
+		Ok(changes.child_storage_changes)
+	}
+
+	/// The child storage `changes` not already in `baseline`.
+	fn storage_writes(changes: ChildChanges, baseline: &ChildChanges) -> Vec<StorageWrite> {
+		let mut writes = Vec::new();
+		for (child_key, items) in changes {
+			let before = baseline.iter().find(|(key, _)| *key == child_key).map(|(_, items)| items);
+			for (key, value) in items {
+				if before.map_or(false, |before| before.contains(&(key.clone(), value.clone()))) {
+					continue
+				}
+				writes.push(StorageWrite {
+					child_trie: child_key.clone().into(),
+					key: key.into(),
+					value: value.map(Into::into),
+				});
+			}
+		}
+		writes
+	}
+
+	fn into_rpc_summary(
+		summary: CallSummary<AccountId, Balance, Block::Hash>,
+		storage_writes: Vec<StorageWrite>,
+	) -> RpcResult<ContractCallSummary<AccountId, Block::Hash>> {
+		let into = |b: Balance| {
+			b.try_into().map_err(|_| {
+				error(Error::RuntimeError, "Deposit does not fit into NumberOrHex.", None)
+			})
+		};
+		Ok(ContractCallSummary {
+			selector: summary.selector.map(|selector| selector.to_vec().into()),
+			gas_consumed: summary.gas_consumed.into(),
+			gas_required: summary.gas_required.into(),
+			storage_deposit: match summary.storage_deposit {
+				StorageDeposit::Charge(deposit) => Deposit::Charge(into(deposit)?),
+				StorageDeposit::Refund(deposit) => Deposit::Refund(into(deposit)?),
+			},
+			outcome: match summary.result {
+				Ok(value) =>
+					Outcome::Returned { flags: value.flags.bits(), data: value.data.into() },
+				Err(e) => Outcome::Failed(format!("{:?}", e)),
+			},
+			debug_message: String::from_utf8_lossy(&summary.debug_message).into_owned(),
+			events: summary.events.into_iter().map(Into::into).collect(),
+			storage_writes,
+		})
+	}
+}
+
+impl<C, BE, Block, AccountId, Balance> ContractsDebugApiServer<<Block as BlockT>::Hash, AccountId>
+	for ContractsDebug<C, BE, Block, AccountId, Balance>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block>
+		+ HeaderBackend<Block>
+		+ BlockBackend<Block>
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: ContractsDebugRuntimeApi<Block, AccountId, Balance, Block::Hash>
+		+ BlockBuilder<Block>
+		+ ApiExt<Block, StateBackend = BE::State>,
+	BE: Backend<Block> + Send + Sync + 'static,
+	BE::State: StateBackend<HashFor<Block>>,
+	AccountId: Codec + Clone + PartialEq + Serialize + for<'de> Deserialize<'de>
+		+ Send + Sync + 'static,
+	Balance: Codec + Default + TryInto<NumberOrHex> + TryFrom<u128> + Send + Sync + 'static,
+{
+	fn debug_call(
+		&self,
+		request: DebugCallRequest<AccountId>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<ContractCallSummary<AccountId, Block::Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let DebugCallRequest { origin, dest, value, gas_limit, storage_deposit_limit, input_data } =
+			request;
+
+		let balance = |value: NumberOrHex| {
+			value
+				.into_u256()
+				.try_into()
+				.ok()
+				.and_then(|value: u128| Balance::try_from(value).ok())
+				.ok_or_else(|| {
+					error(
+						Error::InvalidBalance,
+						"Value does not fit into the balance type.",
+						Some(format!("{:?}", value)),
+					)
+				})
+		};
+		let value = value.map(balance).transpose()?.unwrap_or_default();
+		let storage_deposit_limit = storage_deposit_limit.map(balance).transpose()?;
+
+		let api = self.client.runtime_api();
+		let summary = api
+			.debug_call(
+				at,
+				origin,
+				dest,
+				value,
+				gas_limit.map(Into::into),
+				storage_deposit_limit,
+				input_data.to_vec(),
+			)
+			.map_err(runtime_error)?;
+		let changes = self.child_changes(&api, at)?;
+		let writes = Self::storage_writes(changes, &Vec::new());
+		Self::into_rpc_summary(summary, writes)
+	}
+
+	fn debug_extrinsic(
+		&self,
+		block: Block::Hash,
+		index: u32,
+	) -> RpcResult<ContractCallSummary<AccountId, Block::Hash>> {
+		let (header, extrinsics) = self
+			.client
+			.block(block)
+			.map_err(runtime_error)?
+			.ok_or_else(|| error(Error::UnknownBlock, "Block not found.", None))?
+			.block
+			.deconstruct();
+		let index = index as usize;
+		let extrinsic = extrinsics
+			.get(index)
+			.cloned()
+			.ok_or_else(|| error(Error::UnknownExtrinsic, "Extrinsic not found.", None))?;
+		let parent = *header.parent_hash();
+
+		// Changes made by the extrinsics before the debugged one are recorded separately and
+		// left out of the storage writes.
+		let baseline_api = self.client.runtime_api();
+		Self::replay(&baseline_api, &header, &extrinsics[..index])?;
+		let baseline = self.child_changes(&baseline_api, parent)?;
+
+		let api = self.client.runtime_api();
+		Self::replay(&api, &header, &extrinsics[..index])?;
+		let summary = api
+			.debug_extrinsic(parent, extrinsic)
+			.map_err(runtime_error)?
+			.ok_or_else(|| {
+				error(Error::NotAContractCall, "Extrinsic is not a signed contract call.", None)
+			})?;
+		let changes = self.child_changes(&api, parent)?;
+		let writes = Self::storage_writes(changes, &baseline);
+		Self::into_rpc_summary(summary, writes)
+	}
+}
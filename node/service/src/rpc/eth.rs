@@ -0,0 +1,184 @@
+//! The Ethereum JSON-RPC served by Frontier: `eth_*`, `net_*` and `web3_*`.
+
+use std::{collections::BTreeMap, sync::Arc};
+
+use fc_mapping_sync::{EthereumBlockNotification, EthereumBlockNotificationSinks};
+pub use fc_rpc::{EthBlockDataCacheTask, OverrideHandle};
+pub use fc_rpc_core::types::{FeeHistoryCache, FeeHistoryCacheLimit, FilterPool};
+use fp_rpc::{ConvertTransaction, ConvertTransactionRuntimeApi, EthereumRuntimeRPCApi};
+use jsonrpsee::RpcModule;
+use sc_client_api::{
+	backend::{Backend, StorageProvider},
+	client::BlockchainEvents,
+	AuxStore,
+};
+use sc_network::NetworkService;
+use sc_network_sync::SyncingService;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_transaction_pool::{ChainApi, Pool};
+use sc_transaction_pool_api::TransactionPool;
+use sp_api::ProvideRuntimeApi;
+use sp_block_builder::BlockBuilder as BlockBuilderApi;
+use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::H256;
+use sp_runtime::traits::Block as BlockT;
+
+use crate::{Block, Hash};
+
+/// Extra dependencies for the Ethereum RPC.
+pub struct EthDeps<C, P, A: ChainApi, CT> {
+	/// The client instance to use.
+	pub client: Arc<C>,
+	/// Transaction pool instance.
+	pub pool: Arc<P>,
+	/// Graph pool instance.
+	pub graph: Arc<Pool<A>>,
+	/// Converts Ethereum transactions into extrinsics, if the runtime can't.
+	pub converter: Option<CT>,
+	/// Whether the node is an authority.
+	pub is_authority: bool,
+	/// Whether to sign transactions with the well-known development keys.
+	pub enable_dev_signer: bool,
+	/// Network service.
+	pub network: Arc<NetworkService<Block, Hash>>,
+	/// Chain syncing service.
+	pub sync: Arc<SyncingService<Block>>,
+	/// The mapping between Ethereum and Substrate blocks and transactions.
+	pub frontier_backend: Arc<dyn fc_db::BackendReader<Block> + Send + Sync>,
+	/// Reads Ethereum data from runtime storage, bypassing the runtime API where possible.
+	pub overrides: Arc<OverrideHandle<Block>>,
+	/// Cache of Ethereum blocks and transaction statuses.
+	pub block_data_cache: Arc<EthBlockDataCacheTask<Block>>,
+	/// The filters installed with `eth_newFilter`, if filters are enabled.
+	pub filter_pool: Option<FilterPool>,
+	/// Maximum number of logs `eth_getLogs` returns.
+	pub max_past_logs: u32,
+	/// Fee history cache.
+	pub fee_history_cache: FeeHistoryCache,
+	/// Maximum number of blocks kept in the fee history cache.
+	pub fee_history_cache_limit: FeeHistoryCacheLimit,
+	/// `eth_call` and `eth_estimateGas` may use up to this many times the block gas limit.
+	pub execute_gas_limit_multiplier: u64,
+	/// Ethereum parent hashes to use instead of the ones in the blocks.
+	pub forced_parent_hashes: Option<BTreeMap<H256, H256>>,
+	/// Notified of every block the mapping worker has synced, for `eth_subscribe`.
+	pub pubsub_notification_sinks:
+		Arc<EthereumBlockNotificationSinks<EthereumBlockNotification<Block>>>,
+}
+
+/// Merge the Ethereum RPC into `io`.
+pub fn create_eth<C, BE, P, A, CT>(
+	mut io: RpcModule<()>,
+	deps: EthDeps<C, P, A, CT>,
+	subscription_task_executor: SubscriptionTaskExecutor,
+) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
+where
+	C: ProvideRuntimeApi<Block>
+		+ BlockchainEvents<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = BlockChainError>
+		+ StorageProvider<Block, BE>
+		+ AuxStore
+		+ Send
+		+ Sync
+		+ 'static,
+	C::Api: BlockBuilderApi<Block>
+		+ EthereumRuntimeRPCApi<Block>
+		+ ConvertTransactionRuntimeApi<Block>,
+	BE: Backend<Block> + 'static,
+	P: TransactionPool<Block = Block> + 'static,
+	A: ChainApi<Block = Block> + 'static,
+	CT: ConvertTransaction<<Block as BlockT>::Extrinsic> + Send + Sync + 'static,
+{
+	use fc_rpc::{
+		Eth, EthApiServer, EthDevSigner, EthFilter, EthFilterApiServer, EthPubSub,
+		EthPubSubApiServer, EthSigner, Net, NetApiServer, Web3, Web3ApiServer,
+	};
+
+	let EthDeps {
+		client,
+		pool,
+		graph,
+		converter,
+		is_authority,
+		enable_dev_signer,
+		network,
+		sync,
+		frontier_backend,
+		overrides,
+		block_data_cache,
+		filter_pool,
+		max_past_logs,
+		fee_history_cache,
+		fee_history_cache_limit,
+		execute_gas_limit_multiplier,
+		forced_parent_hashes,
+		pubsub_notification_sinks,
+	} = deps;
+
+	let mut signers = Vec::new();
+	if enable_dev_signer {
+		signers.push(Box::new(EthDevSigner::new()) as Box<dyn EthSigner>);
+	}
+
+	io.merge(
+		Eth::new(
+			client.clone(),
+			pool.clone(),
+			graph.clone(),
+			converter,
+			sync.clone(),
+			signers,
+			overrides.clone(),
+			frontier_backend.clone(),
+			is_authority,
+			block_data_cache.clone(),
+			fee_history_cache,
+			fee_history_cache_limit,
+			execute_gas_limit_multiplier,
+			forced_parent_hashes,
+		)
+		.into_rpc(),
+	)?;
+
+	if let Some(filter_pool) = filter_pool {
+		io.merge(
+			EthFilter::new(
+				client.clone(),
+				frontier_backend,
+				graph,
+				filter_pool,
+				500_usize, // max stored filters
+				max_past_logs,
+				block_data_cache,
+			)
+			.into_rpc(),
+		)?;
+	}
+
+	io.merge(
+		EthPubSub::new(
+			pool,
+			client.clone(),
+			sync,
+			subscription_task_executor,
+			overrides,
+			pubsub_notification_sinks,
+		)
+		.into_rpc(),
+	)?;
+
+	io.merge(
+		Net::new(
+			client.clone(),
+			network,
+			// Whether to format the `peer_count` response as Hex (default) or not.
+			true,
+		)
+		.into_rpc(),
+	)?;
+
+	io.merge(Web3::new(client).into_rpc())?;
+
+	Ok(io)
+}
@@ -0,0 +1,163 @@
+//! RPC for quoting the fee of a call before it is signed.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use fee_estimation_runtime_api::{FeeEstimate, FeeEstimationApi as FeeEstimationRuntimeApi};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::Codec;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	traits::{Block as BlockT, MaybeDisplay},
+	FixedPointNumber,
+};
+
+/// Fee breakdown returned to RPC callers.
+///
+/// Balances are encoded as `NumberOrHex` so that values above `u64::MAX` survive JSON.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeBreakdown {
+	/// The fixed fee every signed extrinsic pays.
+	pub base_fee: NumberOrHex,
+	/// The fee for the encoded length of the signed extrinsic.
+	pub len_fee: NumberOrHex,
+	/// The weight fee after `multiplier` has been applied.
+	pub adjusted_weight_fee: NumberOrHex,
+	/// The fee multiplier, as its inner fixed-point value (`1.0 == 10^18`).
+	pub multiplier: NumberOrHex,
+	/// The tip the estimate was computed with.
+	pub tip: NumberOrHex,
+	/// The sum of all of the above.
+	pub total: NumberOrHex,
+}
+
+impl FeeBreakdown {
+	fn try_from_estimate<Balance: TryInto<NumberOrHex>>(
+		estimate: FeeEstimate<Balance>,
+	) -> Result<Self, ()> {
+		let into = |b: Balance| b.try_into().map_err(|_| ());
+		Ok(Self {
+			base_fee: into(estimate.base_fee)?,
+			len_fee: into(estimate.len_fee)?,
+			adjusted_weight_fee: into(estimate.adjusted_weight_fee)?,
+			multiplier: NumberOrHex::from(estimate.multiplier.into_inner()),
+			tip: into(estimate.tip)?,
+			total: into(estimate.total)?,
+		})
+	}
+}
+
+#[rpc(client, server)]
+pub trait FeeEstimationApi<BlockHash, Balance> {
+	/// Quote the fee of a SCALE-encoded `RuntimeCall` as if it were signed and submitted
+	/// with `tip`.
+	#[method(name = "nativex_estimateFee")]
+	fn estimate_fee(
+		&self,
+		call: Bytes,
+		tip: Option<NumberOrHex>,
+		at: Option<BlockHash>,
+	) -> RpcResult<FeeBreakdown>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call could not be decoded.
+	DecodeError,
+	/// The tip could not be converted into the runtime balance type.
+	InvalidTip,
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::DecodeError => 2,
+			Error::InvalidTip => 3,
+		}
+	}
+}
+
+/// Provides RPC methods to quote call fees.
+pub struct FeeEstimation<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> FeeEstimation<C, B> {
+	/// Create new `FeeEstimation` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, Balance> FeeEstimationApiServer<<Block as BlockT>::Hash, Balance>
+	for FeeEstimation<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: FeeEstimationRuntimeApi<Block, Balance>,
+	Balance: Codec + MaybeDisplay + Copy + Default + TryInto<NumberOrHex> + TryFrom<u128>
+		+ Send + Sync + 'static,
+{
+	fn estimate_fee(
+		&self,
+		call: Bytes,
+		tip: Option<NumberOrHex>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<FeeBreakdown> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let tip = match tip {
+			Some(tip) => tip
+				.into_u256()
+				.try_into()
+				.ok()
+				.and_then(|tip: u128| Balance::try_from(tip).ok())
+				.ok_or_else(|| {
+					CallError::Custom(ErrorObject::owned(
+						Error::InvalidTip.into(),
+						"Tip does not fit into the balance type.",
+						Some(format!("{:?}", tip)),
+					))
+				})?,
+			None => Default::default(),
+		};
+
+		let estimate = api
+			.estimate_call_fee(at_hash, call.to_vec(), tip)
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::RuntimeError.into(),
+					"Unable to estimate fee.",
+					Some(e.to_string()),
+				))
+			})?
+			.ok_or_else(|| {
+				CallError::Custom(ErrorObject::owned(
+					Error::DecodeError.into(),
+					"Unable to decode call.",
+					None::<()>,
+				))
+			})?;
+
+		FeeBreakdown::try_from_estimate(estimate).map_err(|_| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Fee estimate does not fit into NumberOrHex.",
+				None::<()>,
+			)))
+		})
+	}
+}
@@ -0,0 +1,117 @@
+//! RPC for querying the events of finalized blocks kept by the node's event indexer.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use serde::{Deserialize, Serialize};
+use sp_core::{
+	crypto::{AccountId32, Ss58Codec},
+	H256,
+};
+
+use crate::indexer::{EventCursor, EventFilter, EventPage, IndexerDb};
+
+/// Which events [`IndexerApiServer::query_events`] returns. Every field that is set must match.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct EventQuery {
+	/// Events that name this account in their fields, SS58 or `0x` and 64 hex digits.
+	pub account: Option<String>,
+	/// Events of this pallet, e.g. `Balances`.
+	pub pallet: Option<String>,
+	/// Events with this name, e.g. `Transfer`.
+	pub event: Option<String>,
+	/// Events of this block or later ones.
+	pub from_block: Option<u32>,
+	/// Events of this block or earlier ones.
+	pub to_block: Option<u32>,
+	/// Events of the extrinsic with this hash.
+	pub extrinsic_hash: Option<H256>,
+	/// The `next` cursor of the previous page.
+	pub after: Option<EventCursor>,
+	/// Most events to return: 100 if not set, at most 1000.
+	pub limit: Option<u32>,
+}
+
+#[rpc(client, server)]
+pub trait IndexerApi {
+	/// The indexed events that match `query`, oldest first.
+	#[method(name = "nativex_indexer")]
+	fn query_events(&self, query: EventQuery) -> RpcResult<EventPage>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The account of the query is not an account.
+	InvalidAccount,
+	/// The event index failed.
+	DatabaseError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::DatabaseError => 1,
+			Error::InvalidAccount => 2,
+		}
+	}
+}
+
+/// Parse an SS58 account or `0x` and 64 hex digits.
+fn parse_account(account: &str) -> Option<[u8; 32]> {
+	let account = account.trim();
+	match account.strip_prefix("0x") {
+		Some(hex) => sp_core::bytes::from_hex(hex).ok()?.try_into().ok(),
+		None => AccountId32::from_ss58check(account).ok().map(Into::into),
+	}
+}
+
+/// Provides RPC methods to query the event index.
+pub struct Indexer {
+	db: Arc<IndexerDb>,
+}
+
+impl Indexer {
+	/// Create new `Indexer` with the given reference to the event index.
+	pub fn new(db: Arc<IndexerDb>) -> Self {
+		Self { db }
+	}
+}
+
+impl IndexerApiServer for Indexer {
+	fn query_events(&self, query: EventQuery) -> RpcResult<EventPage> {
+		let account = match query.account {
+			Some(account) => Some(parse_account(&account).ok_or_else(|| {
+				CallError::Custom(ErrorObject::owned(
+					Error::InvalidAccount.into(),
+					"Account is neither SS58 nor 32 bytes of hex.",
+					Some(account),
+				))
+			})?),
+			None => None,
+		};
+		let filter = EventFilter {
+			account,
+			pallet: query.pallet,
+			event: query.event,
+			from_block: query.from_block,
+			to_block: query.to_block,
+			extrinsic_hash: query.extrinsic_hash,
+			after: query.after,
+			limit: query.limit,
+		};
+
+		self.db.query(&filter).map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::DatabaseError.into(),
+				"Unable to query the event index.",
+				Some(e.to_string()),
+			))
+			.into()
+		})
+	}
+}
@@ -0,0 +1,67 @@
+//! RPC for fetching the data stored with `TransactionStorage::store` by its content hash.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::BlockBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+#[rpc(client, server)]
+pub trait TransactionStorageApi<Hash> {
+	/// The data whose blake2-256 hash is `content_hash`, if it was stored or renewed recently
+	/// enough for the node to still keep it.
+	#[method(name = "nativex_storedData")]
+	fn stored_data(&self, content_hash: Hash) -> RpcResult<Option<Bytes>>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The client failed to read the indexed transactions.
+	ClientError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::ClientError => 1,
+		}
+	}
+}
+
+/// Provides RPC methods to fetch stored data.
+pub struct TransactionStorage<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> TransactionStorage<C, B> {
+	/// Create new `TransactionStorage` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block> TransactionStorageApiServer<<Block as BlockT>::Hash> for TransactionStorage<C, Block>
+where
+	Block: BlockT,
+	C: BlockBackend<Block> + Send + Sync + 'static,
+{
+	fn stored_data(&self, content_hash: Block::Hash) -> RpcResult<Option<Bytes>> {
+		self.client
+			.indexed_transaction(content_hash)
+			.map(|data| data.map(Into::into))
+			.map_err(|e| {
+				CallError::Custom(ErrorObject::owned(
+					Error::ClientError.into(),
+					"Unable to read the stored data.",
+					Some(e.to_string()),
+				))
+				.into()
+			})
+	}
+}
@@ -0,0 +1,135 @@
+//! RPC for resolving an account from its native or its Ethereum address.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use jsonrpsee::{
+	core::RpcResult,
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use parity_scale_codec::{Codec, Decode};
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{crypto::Ss58Codec, H160};
+use sp_runtime::traits::Block as BlockT;
+use unified_accounts_runtime_api::{
+	ResolvedAccount, UnifiedAccountsApi as UnifiedAccountsRuntimeApi,
+};
+
+/// An account and its H160, as returned to RPC callers.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAddresses {
+	/// The native account, SS58-encoded.
+	pub account: String,
+	/// The H160 that maps to `account`, if there is one.
+	pub evm_address: Option<H160>,
+	/// Whether `account` and `evm_address` are bound to each other.
+	pub bound: bool,
+}
+
+#[rpc(client, server)]
+pub trait UnifiedAccountsApi<BlockHash> {
+	/// Resolve `address`, either an H160 (`0x` and 40 hex digits) or a native account (SS58 or
+	/// `0x` and 64 hex digits), to the account and H160 that belong together.
+	#[method(name = "nativex_resolveAccount")]
+	fn resolve_account(
+		&self,
+		address: String,
+		at: Option<BlockHash>,
+	) -> RpcResult<AccountAddresses>;
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The address is neither an H160 nor a native account.
+	InvalidAddress,
+	/// The call to the runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::InvalidAddress => 2,
+		}
+	}
+}
+
+/// An address given to [`UnifiedAccountsApiServer::resolve_account`].
+enum Address<AccountId> {
+	Evm(H160),
+	Native(AccountId),
+}
+
+impl<AccountId: Decode + Ss58Codec> Address<AccountId> {
+	fn parse(address: &str) -> Option<Self> {
+		let address = address.trim();
+		match address.strip_prefix("0x") {
+			Some(hex) if hex.len() == 40 => sp_core::bytes::from_hex(hex)
+				.ok()
+				.map(|bytes| Self::Evm(H160::from_slice(&bytes))),
+			Some(hex) if hex.len() == 64 => sp_core::bytes::from_hex(hex)
+				.ok()
+				.and_then(|bytes| AccountId::decode(&mut &bytes[..]).ok())
+				.map(Self::Native),
+			Some(_) => None,
+			None => AccountId::from_string(address).ok().map(Self::Native),
+		}
+	}
+}
+
+/// Provides RPC methods to resolve unified accounts.
+pub struct UnifiedAccounts<C, B, AccountId> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, AccountId)>,
+}
+
+impl<C, B, AccountId> UnifiedAccounts<C, B, AccountId> {
+	/// Create new `UnifiedAccounts` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+impl<C, Block, AccountId> UnifiedAccountsApiServer<<Block as BlockT>::Hash>
+	for UnifiedAccounts<C, Block, AccountId>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: UnifiedAccountsRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Ss58Codec + Send + Sync + 'static,
+{
+	fn resolve_account(
+		&self,
+		address: String,
+		at: Option<Block::Hash>,
+	) -> RpcResult<AccountAddresses> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let resolved = match Address::<AccountId>::parse(&address) {
+			Some(Address::Evm(evm_address)) => api.resolve_evm_address(at_hash, evm_address),
+			Some(Address::Native(account)) => api.resolve_account(at_hash, account),
+			None =>
+				return Err(CallError::Custom(ErrorObject::owned(
+					Error::InvalidAddress.into(),
+					"Address is neither an H160 nor an account.",
+					Some(address),
+				))
+				.into()),
+		}
+		.map_err(|e| {
+			CallError::Custom(ErrorObject::owned(
+				Error::RuntimeError.into(),
+				"Unable to resolve account.",
+				Some(e.to_string()),
+			))
+		})?;
+
+		let ResolvedAccount { account, evm_address, bound } = resolved;
+		Ok(AccountAddresses { account: account.to_ss58check(), evm_address, bound })
+	}
+}
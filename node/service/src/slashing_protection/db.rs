@@ -0,0 +1,318 @@
+//! The SQLite database of the slashing protection.
+
+use std::{
+	path::Path,
+	sync::{Mutex, MutexGuard},
+};
+
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+use sp_core::H256;
+
+const SCHEMA: &str = "
+	CREATE TABLE IF NOT EXISTS babe_blocks (
+		authority BLOB NOT NULL,
+		slot INTEGER NOT NULL,
+		header_hash BLOB NOT NULL,
+		PRIMARY KEY (authority, slot)
+	);
+	CREATE TABLE IF NOT EXISTS grandpa_votes (
+		authority BLOB NOT NULL,
+		set_id INTEGER NOT NULL,
+		round INTEGER NOT NULL,
+		kind TEXT NOT NULL,
+		target_hash BLOB NOT NULL,
+		target_number INTEGER NOT NULL,
+		PRIMARY KEY (authority, set_id, round, kind)
+	);
+";
+
+/// A block authored with a BABE key.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct BabeBlock {
+	/// The BABE key that sealed the block.
+	pub authority: H256,
+	/// The slot of the block.
+	pub slot: u64,
+	/// The hash of the header without its seal, which is what the key signed.
+	pub header_hash: H256,
+}
+
+/// The kind of a GRANDPA vote.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum VoteKind {
+	Prevote,
+	Precommit,
+	PrimaryPropose,
+}
+
+impl VoteKind {
+	fn as_str(&self) -> &'static str {
+		match self {
+			VoteKind::Prevote => "prevote",
+			VoteKind::Precommit => "precommit",
+			VoteKind::PrimaryPropose => "primaryPropose",
+		}
+	}
+
+	fn parse(kind: &str) -> Option<Self> {
+		[VoteKind::Prevote, VoteKind::Precommit, VoteKind::PrimaryPropose]
+			.into_iter()
+			.find(|known| known.as_str() == kind)
+	}
+}
+
+/// A GRANDPA vote signed with a GRANDPA key.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GrandpaVote {
+	/// The GRANDPA key that signed the vote.
+	pub authority: H256,
+	/// The authority set the vote was cast in.
+	pub set_id: u64,
+	/// The round the vote was cast in.
+	pub round: u64,
+	/// The kind of the vote.
+	pub kind: VoteKind,
+	/// The hash of the block voted for.
+	pub target_hash: H256,
+	/// The number of the block voted for.
+	pub target_number: u32,
+}
+
+/// What the keys of the node signed, as exported and imported.
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct History {
+	/// The id of the chain the history was recorded on.
+	pub chain: String,
+	/// The authored blocks, by key and slot.
+	pub babe: Vec<BabeBlock>,
+	/// The signed votes, by key, set, round and kind.
+	pub grandpa: Vec<GrandpaVote>,
+}
+
+/// The history of what the keys of the node signed.
+pub struct SlashingProtectionDb {
+	connection: Mutex<Connection>,
+}
+
+impl SlashingProtectionDb {
+	/// Open the history at `path`, creating it if needed.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		Self::new(Connection::open(path)?)
+	}
+
+	/// Open a history that lives in memory.
+	#[cfg(test)]
+	pub fn open_in_memory() -> rusqlite::Result<Self> {
+		Self::new(Connection::open_in_memory()?)
+	}
+
+	fn new(connection: Connection) -> rusqlite::Result<Self> {
+		connection.execute_batch(SCHEMA)?;
+		Ok(Self { connection: Mutex::new(connection) })
+	}
+
+	fn connection(&self) -> MutexGuard<Connection> {
+		self.connection.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+	}
+
+	/// Record `block`, unless its key already authored another block at its slot.
+	///
+	/// Returns the header hash of the other block in that case.
+	pub fn record_babe_block(&self, block: &BabeBlock) -> rusqlite::Result<Option<H256>> {
+		let mut connection = self.connection();
+		let transaction = connection.transaction()?;
+		transaction.execute(
+			"INSERT OR IGNORE INTO babe_blocks (authority, slot, header_hash) VALUES (?1, ?2, ?3)",
+			params![&block.authority.0[..], block.slot, &block.header_hash.0[..]],
+		)?;
+		let recorded = transaction.query_row(
+			"SELECT header_hash FROM babe_blocks WHERE authority = ?1 AND slot = ?2",
+			params![&block.authority.0[..], block.slot],
+			|row| Ok(H256::from_slice(&row.get::<_, Vec<u8>>(0)?)),
+		)?;
+		transaction.commit()?;
+		Ok((recorded != block.header_hash).then_some(recorded))
+	}
+
+	/// Record `vote`, unless its key already cast a vote of its kind for another block in its
+	/// round.
+	///
+	/// Returns the hash of the block of the other vote in that case.
+	pub fn record_grandpa_vote(&self, vote: &GrandpaVote) -> rusqlite::Result<Option<H256>> {
+		let mut connection = self.connection();
+		let transaction = connection.transaction()?;
+		transaction.execute(
+			"INSERT OR IGNORE INTO grandpa_votes
+				(authority, set_id, round, kind, target_hash, target_number)
+			VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+			params![
+				&vote.authority.0[..],
+				vote.set_id,
+				vote.round,
+				vote.kind.as_str(),
+				&vote.target_hash.0[..],
+				vote.target_number,
+			],
+		)?;
+		let recorded = transaction.query_row(
+			"SELECT target_hash FROM grandpa_votes
+			WHERE authority = ?1 AND set_id = ?2 AND round = ?3 AND kind = ?4",
+			params![&vote.authority.0[..], vote.set_id, vote.round, vote.kind.as_str()],
+			|row| Ok(H256::from_slice(&row.get::<_, Vec<u8>>(0)?)),
+		)?;
+		transaction.commit()?;
+		Ok((recorded != vote.target_hash).then_some(recorded))
+	}
+
+	/// The whole history, recorded on chain `chain`.
+	pub fn export(&self, chain: &str) -> rusqlite::Result<History> {
+		let connection = self.connection();
+		let babe = connection
+			.prepare(
+				"SELECT authority, slot, header_hash FROM babe_blocks ORDER BY slot, authority",
+			)?
+			.query_map([], |row| {
+				Ok(BabeBlock {
+					authority: H256::from_slice(&row.get::<_, Vec<u8>>(0)?),
+					slot: row.get(1)?,
+					header_hash: H256::from_slice(&row.get::<_, Vec<u8>>(2)?),
+				})
+			})?
+			.collect::<rusqlite::Result<_>>()?;
+		let grandpa = connection
+			.prepare(
+				"SELECT authority, set_id, round, kind, target_hash, target_number
+				FROM grandpa_votes ORDER BY set_id, round, authority, kind",
+			)?
+			.query_map([], |row| {
+				let kind: String = row.get(3)?;
+				Ok(GrandpaVote {
+					authority: H256::from_slice(&row.get::<_, Vec<u8>>(0)?),
+					set_id: row.get(1)?,
+					round: row.get(2)?,
+					kind: VoteKind::parse(&kind).ok_or_else(|| {
+						rusqlite::Error::InvalidColumnType(
+							3,
+							"kind".into(),
+							rusqlite::types::Type::Text,
+						)
+					})?,
+					target_hash: H256::from_slice(&row.get::<_, Vec<u8>>(4)?),
+					target_number: row.get(5)?,
+				})
+			})?
+			.collect::<rusqlite::Result<_>>()?;
+		Ok(History { chain: chain.to_string(), babe, grandpa })
+	}
+
+	/// Add `history` to the recorded one.
+	///
+	/// A block or a vote of `history` that conflicts with a recorded one is not added: the key
+	/// signed both, and either is enough to refuse signing anything else in its place.
+	/// Returns how many blocks and votes were added.
+	pub fn import(&self, history: &History) -> rusqlite::Result<(usize, usize)> {
+		let mut connection = self.connection();
+		let transaction = connection.transaction()?;
+		let mut blocks = 0;
+		for block in &history.babe {
+			blocks += transaction.execute(
+				"INSERT OR IGNORE INTO babe_blocks (authority, slot, header_hash)
+				VALUES (?1, ?2, ?3)",
+				params![&block.authority.0[..], block.slot, &block.header_hash.0[..]],
+			)?;
+		}
+		let mut votes = 0;
+		for vote in &history.grandpa {
+			votes += transaction.execute(
+				"INSERT OR IGNORE INTO grandpa_votes
+					(authority, set_id, round, kind, target_hash, target_number)
+				VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+				params![
+					&vote.authority.0[..],
+					vote.set_id,
+					vote.round,
+					vote.kind.as_str(),
+					&vote.target_hash.0[..],
+					vote.target_number,
+				],
+			)?;
+		}
+		transaction.commit()?;
+		Ok((blocks, votes))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn block(slot: u64, header_hash: u8) -> BabeBlock {
+		BabeBlock {
+			authority: H256::repeat_byte(1),
+			slot,
+			header_hash: H256::repeat_byte(header_hash),
+		}
+	}
+
+	fn vote(round: u64, kind: VoteKind, target_hash: u8) -> GrandpaVote {
+		GrandpaVote {
+			authority: H256::repeat_byte(2),
+			set_id: 0,
+			round,
+			kind,
+			target_hash: H256::repeat_byte(target_hash),
+			target_number: target_hash as u32,
+		}
+	}
+
+	#[test]
+	fn conflicting_blocks_are_refused() {
+		let db = SlashingProtectionDb::open_in_memory().unwrap();
+		assert_eq!(db.record_babe_block(&block(10, 1)).unwrap(), None);
+		// Signing the same header again is fine.
+		assert_eq!(db.record_babe_block(&block(10, 1)).unwrap(), None);
+		assert_eq!(db.record_babe_block(&block(10, 2)).unwrap(), Some(H256::repeat_byte(1)));
+		assert_eq!(db.record_babe_block(&block(11, 2)).unwrap(), None);
+		let other_key = BabeBlock { authority: H256::repeat_byte(3), ..block(10, 2) };
+		assert_eq!(db.record_babe_block(&other_key).unwrap(), None);
+	}
+
+	#[test]
+	fn conflicting_votes_are_refused() {
+		let db = SlashingProtectionDb::open_in_memory().unwrap();
+		assert_eq!(db.record_grandpa_vote(&vote(1, VoteKind::Prevote, 1)).unwrap(), None);
+		assert_eq!(db.record_grandpa_vote(&vote(1, VoteKind::Prevote, 1)).unwrap(), None);
+		assert_eq!(
+			db.record_grandpa_vote(&vote(1, VoteKind::Prevote, 2)).unwrap(),
+			Some(H256::repeat_byte(1)),
+		);
+		assert_eq!(db.record_grandpa_vote(&vote(1, VoteKind::Precommit, 2)).unwrap(), None);
+		assert_eq!(db.record_grandpa_vote(&vote(2, VoteKind::Prevote, 2)).unwrap(), None);
+	}
+
+	#[test]
+	fn history_moves_between_databases() {
+		let old = SlashingProtectionDb::open_in_memory().unwrap();
+		old.record_babe_block(&block(10, 1)).unwrap();
+		old.record_grandpa_vote(&vote(1, VoteKind::Precommit, 1)).unwrap();
+		let history = old.export("dev").unwrap();
+		assert_eq!(history.babe, vec![block(10, 1)]);
+		assert_eq!(history.grandpa, vec![vote(1, VoteKind::Precommit, 1)]);
+
+		let new = SlashingProtectionDb::open_in_memory().unwrap();
+		new.record_babe_block(&block(11, 3)).unwrap();
+		assert_eq!(new.import(&history).unwrap(), (1, 1));
+		assert_eq!(new.import(&history).unwrap(), (0, 0));
+		assert_eq!(new.record_babe_block(&block(10, 2)).unwrap(), Some(H256::repeat_byte(1)));
+		assert_eq!(
+			new.record_grandpa_vote(&vote(1, VoteKind::Precommit, 2)).unwrap(),
+			Some(H256::repeat_byte(1)),
+		);
+		assert_eq!(new.export("dev").unwrap().babe, vec![block(10, 1), block(11, 3)]);
+	}
+}
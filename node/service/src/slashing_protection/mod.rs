@@ -0,0 +1,420 @@
+//! Protection of the BABE and GRANDPA keys of an authority against equivocating, kept in an
+//! SQLite database next to the chain database.
+//!
+//! Running the same keys on two nodes, when failing a validator over, gets them to author two
+//! blocks at a slot or to cast two votes in a round, which `pallet_offences` slashes. The node
+//! records every block its keys author and every vote they sign, and refuses to do it again for
+//! another block. The history moves with the keys using `nativex slashing-protection`.
+//!
+//! GRANDPA signs its votes with the keystore, which [`ProtectedKeystore`] guards. BABE only signs
+//! the hash of the header it authors, so [`ProtectedBlockImport`] guards the blocks authored by
+//! the node before they are imported and announced.
+
+mod db;
+
+use std::{
+	collections::HashMap,
+	fmt,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+
+use grandpa_primitives::{RoundNumber, SetId};
+use parity_scale_codec::DecodeAll;
+use sc_cli::{CliConfiguration, SharedParams};
+use sc_consensus::{BlockCheckParams, BlockImport, BlockImportParams, ImportResult};
+use sc_service::config::Configuration;
+use sp_consensus::{BlockOrigin, Error as ConsensusError};
+use sp_consensus_babe::digests::CompatibleDigestItem;
+use sp_core::{
+	crypto::{ByteArray, KeyTypeId},
+	ecdsa, ed25519, sr25519, Pair, H256,
+};
+use sp_keystore::{Keystore, KeystorePtr};
+use sp_runtime::traits::{Block as BlockT, Header as HeaderT};
+
+use crate::Block;
+
+pub use db::{BabeBlock, GrandpaVote, History, SlashingProtectionDb, VoteKind};
+
+const LOG_TARGET: &str = "slashing-protection";
+
+type Header = <Block as BlockT>::Header;
+type Message = grandpa_primitives::Message<Header>;
+
+/// Where the slashing protection history of `config`'s chain lives.
+pub fn db_path(config: &Configuration) -> PathBuf {
+	crate::eth::db_config_dir(config).join("slashing-protection").join("history.sqlite")
+}
+
+/// Open the slashing protection history at `path`, creating it if needed.
+pub fn open(path: &Path) -> Result<Arc<SlashingProtectionDb>, Error> {
+	if let Some(dir) = path.parent() {
+		std::fs::create_dir_all(dir).map_err(|e| Error::Io(e.to_string()))?;
+	}
+	Ok(Arc::new(SlashingProtectionDb::open(path)?))
+}
+
+/// Error of the slashing protection.
+#[derive(Debug)]
+pub enum Error {
+	/// The history database failed.
+	Database(rusqlite::Error),
+	/// The history database or file could not be read or written.
+	Io(String),
+}
+
+impl fmt::Display for Error {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Error::Database(e) => write!(f, "Slashing protection database error: {e}"),
+			Error::Io(e) => write!(f, "Slashing protection history can't be accessed: {e}"),
+		}
+	}
+}
+
+impl std::error::Error for Error {}
+
+impl From<rusqlite::Error> for Error {
+	fn from(e: rusqlite::Error) -> Self {
+		Error::Database(e)
+	}
+}
+
+impl From<Error> for sc_service::Error {
+	fn from(e: Error) -> Self {
+		sc_service::Error::Other(e.to_string())
+	}
+}
+
+/// A keystore that refuses to sign a GRANDPA vote conflicting with one it signed before.
+pub struct ProtectedKeystore {
+	inner: KeystorePtr,
+	db: Arc<SlashingProtectionDb>,
+}
+
+impl ProtectedKeystore {
+	/// Guard the GRANDPA signatures of `inner` with the history in `db`.
+	pub fn new(inner: KeystorePtr, db: Arc<SlashingProtectionDb>) -> Self {
+		Self { inner, db }
+	}
+
+	/// Record the vote `msg` is the signing payload of, failing if it conflicts with a recorded
+	/// one. Payloads that are not votes are not recorded.
+	fn record_grandpa_vote(&self, public: &ed25519::Public, msg: &[u8]) -> Result<(), String> {
+		let payload = <(Message, RoundNumber, SetId)>::decode_all(&mut &msg[..]);
+		let Ok((message, round, set_id)) = payload else { return Ok(()) };
+		let kind = match message {
+			Message::Prevote(_) => VoteKind::Prevote,
+			Message::Precommit(_) => VoteKind::Precommit,
+			Message::PrimaryPropose(_) => VoteKind::PrimaryPropose,
+		};
+		let (target_hash, target_number) = message.target();
+		let vote = GrandpaVote {
+			authority: H256::from_slice(public.as_slice()),
+			set_id,
+			round,
+			kind,
+			target_hash: *target_hash,
+			target_number,
+		};
+		match self.db.record_grandpa_vote(&vote) {
+			Ok(None) => Ok(()),
+			Ok(Some(signed)) => Err(format!(
+				"GRANDPA key {public} already signed a {kind:?} for {signed:?} in round {round} of \
+				 set {set_id}, refusing to sign one for {target_hash:?}",
+			)),
+			Err(e) => Err(format!("Slashing protection database error: {e}")),
+		}
+	}
+}
+
+impl Keystore for ProtectedKeystore {
+	fn sr25519_public_keys(&self, key_type: KeyTypeId) -> Vec<sr25519::Public> {
+		self.inner.sr25519_public_keys(key_type)
+	}
+
+	fn sr25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<sr25519::Public, sp_keystore::Error> {
+		self.inner.sr25519_generate_new(key_type, seed)
+	}
+
+	fn sr25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		msg: &[u8],
+	) -> Result<Option<sr25519::Signature>, sp_keystore::Error> {
+		self.inner.sr25519_sign(key_type, public, msg)
+	}
+
+	fn sr25519_vrf_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &sr25519::Public,
+		data: &sr25519::vrf::VrfSignData,
+	) -> Result<Option<sr25519::vrf::VrfSignature>, sp_keystore::Error> {
+		self.inner.sr25519_vrf_sign(key_type, public, data)
+	}
+
+	fn ed25519_public_keys(&self, key_type: KeyTypeId) -> Vec<ed25519::Public> {
+		self.inner.ed25519_public_keys(key_type)
+	}
+
+	fn ed25519_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ed25519::Public, sp_keystore::Error> {
+		self.inner.ed25519_generate_new(key_type, seed)
+	}
+
+	fn ed25519_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ed25519::Public,
+		msg: &[u8],
+	) -> Result<Option<ed25519::Signature>, sp_keystore::Error> {
+		if key_type == grandpa_primitives::KEY_TYPE {
+			self.record_grandpa_vote(public, msg).map_err(|e| {
+				log::warn!(target: LOG_TARGET, "{e}");
+				sp_keystore::Error::Other(e)
+			})?;
+		}
+		self.inner.ed25519_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_public_keys(&self, key_type: KeyTypeId) -> Vec<ecdsa::Public> {
+		self.inner.ecdsa_public_keys(key_type)
+	}
+
+	fn ecdsa_generate_new(
+		&self,
+		key_type: KeyTypeId,
+		seed: Option<&str>,
+	) -> Result<ecdsa::Public, sp_keystore::Error> {
+		self.inner.ecdsa_generate_new(key_type, seed)
+	}
+
+	fn ecdsa_sign(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8],
+	) -> Result<Option<ecdsa::Signature>, sp_keystore::Error> {
+		self.inner.ecdsa_sign(key_type, public, msg)
+	}
+
+	fn ecdsa_sign_prehashed(
+		&self,
+		key_type: KeyTypeId,
+		public: &ecdsa::Public,
+		msg: &[u8; 32],
+	) -> Result<Option<ecdsa::Signature>, sp_keystore::Error> {
+		self.inner.ecdsa_sign_prehashed(key_type, public, msg)
+	}
+
+	fn insert(&self, key_type: KeyTypeId, suri: &str, public: &[u8]) -> Result<(), ()> {
+		self.inner.insert(key_type, suri, public)
+	}
+
+	fn keys(&self, key_type: KeyTypeId) -> Result<Vec<Vec<u8>>, sp_keystore::Error> {
+		self.inner.keys(key_type)
+	}
+
+	fn has_keys(&self, public_keys: &[(Vec<u8>, KeyTypeId)]) -> bool {
+		self.inner.has_keys(public_keys)
+	}
+}
+
+/// A block import that refuses the blocks authored by the node that conflict with a block its
+/// BABE key authored before.
+pub struct ProtectedBlockImport<I> {
+	inner: I,
+	keystore: KeystorePtr,
+	db: Arc<SlashingProtectionDb>,
+}
+
+impl<I: Clone> Clone for ProtectedBlockImport<I> {
+	fn clone(&self) -> Self {
+		Self { inner: self.inner.clone(), keystore: self.keystore.clone(), db: self.db.clone() }
+	}
+}
+
+impl<I> ProtectedBlockImport<I> {
+	/// Guard the blocks authored with the BABE keys of `keystore` before `inner` imports them.
+	pub fn new(inner: I, keystore: KeystorePtr, db: Arc<SlashingProtectionDb>) -> Self {
+		Self { inner, keystore, db }
+	}
+
+	/// Record the block authored by the node, failing if it conflicts with a recorded one.
+	fn record_babe_block<Transaction>(
+		&self,
+		block: &BlockImportParams<Block, Transaction>,
+	) -> Result<(), ConsensusError> {
+		let Ok(pre_digest) = sc_consensus_babe::find_pre_digest::<Block>(&block.header) else {
+			return Ok(())
+		};
+		let Some(signature) = block.post_digests.iter().find_map(|item| item.as_babe_seal())
+		else {
+			return Ok(())
+		};
+		// The seal signs the header without it, so it tells which of the keys authored it.
+		let header_hash = block.header.hash();
+		let Some(authority) = self
+			.keystore
+			.sr25519_public_keys(sp_consensus_babe::KEY_TYPE)
+			.into_iter()
+			.find(|public| sr25519::Pair::verify(signature.as_ref(), header_hash, public))
+		else {
+			return Ok(())
+		};
+
+		let slot = u64::from(pre_digest.slot());
+		let authored =
+			BabeBlock { authority: H256::from_slice(authority.as_slice()), slot, header_hash };
+		let error = match self.db.record_babe_block(&authored) {
+			Ok(None) => return Ok(()),
+			Ok(Some(signed)) => format!(
+				"BABE key {authority} already authored {signed:?} at slot {slot}, refusing to \
+				 author {header_hash:?}",
+			),
+			Err(e) => format!("Slashing protection database error: {e}"),
+		};
+		log::warn!(target: LOG_TARGET, "{error}");
+		Err(ConsensusError::ClientImport(error))
+	}
+}
+
+#[async_trait::async_trait]
+impl<I> BlockImport<Block> for ProtectedBlockImport<I>
+where
+	I: BlockImport<Block, Error = ConsensusError> + Send + Sync,
+	I::Transaction: Send + 'static,
+{
+	type Error = ConsensusError;
+	type Transaction = I::Transaction;
+
+	async fn check_block(
+		&mut self,
+		block: BlockCheckParams<Block>,
+	) -> Result<ImportResult, Self::Error> {
+		self.inner.check_block(block).await
+	}
+
+	async fn import_block(
+		&mut self,
+		block: BlockImportParams<Block, Self::Transaction>,
+		cache: HashMap<sp_consensus::CacheKeyId, Vec<u8>>,
+	) -> Result<ImportResult, Self::Error> {
+		if block.origin == BlockOrigin::Own {
+			self.record_babe_block(&block)?;
+		}
+		self.inner.import_block(block, cache).await
+	}
+}
+
+/// Whether to export or to import the slashing protection history.
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum HistoryAction {
+	/// Write the history of this node to the file.
+	Export,
+	/// Add the history of another node, read from the file it exported, to this node's.
+	Import,
+}
+
+/// Move the slashing protection history of the BABE and GRANDPA keys between nodes.
+///
+/// Import the history of the old node before starting the new one with its keys.
+#[derive(Debug, clap::Parser)]
+pub struct SlashingProtectionCmd {
+	/// Export or import.
+	#[arg(value_enum)]
+	pub action: HistoryAction,
+
+	/// The JSON file of the history.
+	#[arg(value_name = "FILE")]
+	pub file: PathBuf,
+
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+}
+
+impl SlashingProtectionCmd {
+	/// Run the command.
+	pub fn run(&self, config: &Configuration) -> sc_cli::Result<()> {
+		let db = open(&db_path(config)).map_err(sc_service::Error::from)?;
+		let chain = config.chain_spec.id();
+		let db_error = |e| sc_service::Error::from(Error::Database(e));
+		match self.action {
+			HistoryAction::Export => {
+				let history = db.export(chain).map_err(db_error)?;
+				let json = serde_json::to_string_pretty(&history)
+					.map_err(|e| sc_cli::Error::Application(Box::new(e)))?;
+				std::fs::write(&self.file, json)?;
+				log::info!(
+					"Exported {} blocks and {} votes to {}",
+					history.babe.len(),
+					history.grandpa.len(),
+					self.file.display(),
+				);
+			},
+			HistoryAction::Import => {
+				let json = std::fs::read(&self.file)?;
+				let history: History = serde_json::from_slice(&json).map_err(|e| {
+					sc_cli::Error::Input(format!("Invalid history {}: {e}", self.file.display()))
+				})?;
+				if history.chain != chain {
+					return Err(sc_cli::Error::Input(format!(
+						"The history was recorded on chain `{}`, not on `{chain}`",
+						history.chain,
+					)))
+				}
+				let (blocks, votes) = db.import(&history).map_err(db_error)?;
+				log::info!("Imported {blocks} new blocks and {votes} new votes");
+			},
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for SlashingProtectionCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_scale_codec::Encode;
+	use sp_keystore::testing::MemoryKeystore;
+
+	fn prevote(target_hash: H256, target_number: u32) -> Message {
+		Message::Prevote(grandpa_primitives::Prevote::<Header> { target_hash, target_number })
+	}
+
+	#[test]
+	fn keystore_refuses_conflicting_grandpa_votes() {
+		let inner: KeystorePtr = Arc::new(MemoryKeystore::new());
+		let public = inner.ed25519_generate_new(grandpa_primitives::KEY_TYPE, None).unwrap();
+		let db = Arc::new(SlashingProtectionDb::open_in_memory().unwrap());
+		let keystore = ProtectedKeystore::new(inner, db);
+		let sign = |message: Message, round: RoundNumber| {
+			let payload = grandpa_primitives::localized_payload(round, 0, &message);
+			keystore.ed25519_sign(grandpa_primitives::KEY_TYPE, &public, &payload)
+		};
+
+		assert!(sign(prevote(H256::repeat_byte(1), 1), 1).unwrap().is_some());
+		assert!(sign(prevote(H256::repeat_byte(1), 1), 1).unwrap().is_some());
+		assert!(sign(prevote(H256::repeat_byte(2), 1), 1).is_err());
+		assert!(sign(prevote(H256::repeat_byte(2), 1), 2).unwrap().is_some());
+
+		// Other payloads are signed as they are.
+		let other = (1u8, 2u8).encode();
+		assert!(keystore.ed25519_sign(grandpa_primitives::KEY_TYPE, &public, &other).is_ok());
+	}
+}
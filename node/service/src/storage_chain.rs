@@ -0,0 +1,40 @@
+//! Options for running the node as a storage chain: a node that keeps the data stored with
+//! `TransactionStorage::store` for the whole storage period and serves it to other nodes.
+
+use sc_service::config::{BlocksPruning, Configuration};
+use sp_transaction_storage_proof::DEFAULT_STORAGE_PERIOD;
+
+/// Options of the storage chain.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct StorageChainConfiguration {
+	/// Keep the stored data for the whole storage period and serve it over IPFS bitswap, as well
+	/// as with the `nativex_storedData` RPC.
+	///
+	/// Block authors need the data stored a storage period ago to provide the storage proof, so
+	/// they should run with this flag.
+	#[arg(long)]
+	pub storage_chain: bool,
+}
+
+/// Adjust `config` to `storage_chain`.
+///
+/// Stored data is kept with the block bodies, so block pruning must keep the bodies of at least
+/// a storage period.
+pub fn configure(
+	config: &mut Configuration,
+	storage_chain: &StorageChainConfiguration,
+) -> Result<(), sc_service::Error> {
+	if !storage_chain.storage_chain {
+		return Ok(())
+	}
+	if let BlocksPruning::Some(blocks) = config.blocks_pruning {
+		if blocks < DEFAULT_STORAGE_PERIOD {
+			return Err(sc_service::Error::Other(format!(
+				"--storage-chain needs --blocks-pruning to keep at least {DEFAULT_STORAGE_PERIOD} \
+				 blocks, the storage period, not {blocks}",
+			)))
+		}
+	}
+	config.network.ipfs_server = true;
+	Ok(())
+}
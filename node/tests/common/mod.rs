@@ -0,0 +1,60 @@
+//! Helpers shared by the tests that run a `nativex` dev node and talk to it over RPC.
+
+use std::{
+	io::{Read, Write},
+	net::{TcpListener, TcpStream},
+	process::Child,
+	sync::OnceLock,
+	thread,
+	time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+
+/// The RPC port of the node, a free one so that the test doesn't clash with other nodes.
+pub fn rpc_port() -> u16 {
+	static PORT: OnceLock<u16> = OnceLock::new();
+	*PORT.get_or_init(|| {
+		let listener = TcpListener::bind("127.0.0.1:0").expect("no free port");
+		listener.local_addr().expect("bound listener has an address").port()
+	})
+}
+
+/// Kills the node when dropped, so a failing assertion does not leak the process.
+pub struct KillOnDrop(pub Child);
+
+impl Drop for KillOnDrop {
+	fn drop(&mut self) {
+		let _ = self.0.kill();
+		let _ = self.0.wait();
+	}
+}
+
+/// Wait up to `timeout` for the node to accept RPC connections.
+pub fn wait_for_rpc(timeout: Duration) {
+	let start = Instant::now();
+	while TcpStream::connect(("127.0.0.1", rpc_port())).is_err() {
+		assert!(start.elapsed() < timeout, "node RPC did not come up within {:?}", timeout);
+		thread::sleep(Duration::from_millis(500));
+	}
+}
+
+/// Call `method` over HTTP and return its result.
+pub fn rpc(method: &str, params: Value) -> Value {
+	let body = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }).to_string();
+	let mut stream = TcpStream::connect(("127.0.0.1", rpc_port())).expect("node RPC is up");
+	write!(
+		stream,
+		"POST / HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\n\
+		 Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+		body.len(),
+		body,
+	)
+	.expect("failed to send RPC request");
+	let mut response = String::new();
+	stream.read_to_string(&mut response).expect("failed to read RPC response");
+	let (_, body) = response.split_once("\r\n\r\n").expect("RPC response is HTTP");
+	let mut response: Value = serde_json::from_str(body).expect("RPC response is JSON");
+	assert!(response.get("error").is_none(), "{method} failed: {}", response["error"]);
+	response["result"].take()
+}
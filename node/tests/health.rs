@@ -0,0 +1,90 @@
+//! Probe the health endpoints of an in-process `--dev` node.
+
+#![cfg(feature = "with-development-runtime")]
+
+use std::{
+	io::{Read, Write},
+	net::{TcpListener, TcpStream},
+	thread,
+	time::{Duration, Instant},
+};
+
+use nativex_cli::Cli;
+use nativex_service::{development_runtime::RuntimeApi, DevelopmentExecutor};
+use sc_cli::{CliConfiguration, SubstrateCli};
+
+/// A port nothing listens on, so that the test doesn't clash with other nodes.
+fn free_port() -> u16 {
+	let listener = TcpListener::bind("127.0.0.1:0").expect("no free port");
+	listener.local_addr().expect("bound listener has an address").port()
+}
+
+/// `GET path` on the health server at `port`, as the status code and the body.
+fn get(port: u16, path: &str) -> Option<(u16, String)> {
+	let mut stream = TcpStream::connect(("127.0.0.1", port)).ok()?;
+	write!(stream, "GET {path} HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n\r\n").ok()?;
+	let mut response = String::new();
+	stream.read_to_string(&mut response).ok()?;
+	let status = response.split(' ').nth(1)?.parse().ok()?;
+	let (_, body) = response.split_once("\r\n\r\n")?;
+	Some((status, body.to_string()))
+}
+
+/// Poll `path` until it answers `200`, and return the body.
+fn wait_for_ok(port: u16, path: &str, timeout: Duration) -> String {
+	let start = Instant::now();
+	loop {
+		let response = get(port, path);
+		match &response {
+			Some((200, body)) => return body.clone(),
+			_ if start.elapsed() > timeout =>
+				panic!("{path} did not answer 200 within {timeout:?}, last answer: {response:?}"),
+			_ => thread::sleep(Duration::from_millis(500)),
+		}
+	}
+}
+
+#[test]
+fn dev_node_is_healthy_ready_and_validating() {
+	let port = free_port();
+	let health_port = format!("--health-port={port}");
+	let cli = Cli::from_iter([
+		"nativex",
+		"--dev",
+		"--tmp",
+		"--no-telemetry",
+		"--no-prometheus",
+		// Let the OS pick the ports the test doesn't connect to.
+		"--port=0",
+		"--rpc-port=0",
+		health_port.as_str(),
+		// A lone dev node has no peers.
+		"--health-min-peers=0",
+	]);
+	let runtime = sc_cli::build_runtime().expect("failed to build the tokio runtime");
+	let config = cli
+		.run
+		.create_configuration(&cli, runtime.handle().clone())
+		.expect("failed to create the node configuration");
+	let service = {
+		let _guard = runtime.enter();
+		nativex_service::new_full_base::<RuntimeApi, DevelopmentExecutor>(
+			config,
+			cli.eth.clone(),
+			cli.indexer.clone(),
+			cli.storage_chain.clone(),
+			cli.health.clone(),
+		)
+		.expect("failed to start the node")
+	};
+
+	assert_eq!(wait_for_ok(port, "/health", Duration::from_secs(10)), "OK");
+	let readiness = wait_for_ok(port, "/readiness", Duration::from_secs(60));
+	assert!(readiness.contains(r#""synced":true"#), "{readiness}");
+	let validator = wait_for_ok(port, "/validator", Duration::from_secs(10));
+	assert_eq!(validator, r#"{"babe":true,"grandpa":true}"#);
+	assert_eq!(get(port, "/metrics").map(|(status, _)| status), Some(404));
+
+	drop(service);
+	runtime.shutdown_timeout(Duration::from_secs(10));
+}
@@ -0,0 +1,115 @@
+//! Store a blob on a `--storage-chain` dev node, renew it and fetch it back by its content hash.
+
+#![cfg(feature = "with-development-runtime")]
+
+mod common;
+
+use std::{
+	process::Command,
+	thread,
+	time::{Duration, Instant},
+};
+
+use common::{rpc, rpc_port, wait_for_rpc, KillOnDrop};
+use nativex_service::development_runtime::{
+	signed_extra, Address, RuntimeCall, SignedPayload, UncheckedExtrinsic,
+};
+use parity_scale_codec::Encode;
+use serde_json::{json, Value};
+use sp_core::H256;
+use sp_keyring::AccountKeyring;
+use sp_runtime::generic::Era;
+
+fn hex(bytes: &[u8]) -> String {
+	format!("0x{}", sp_core::hexdisplay::HexDisplay::from(&bytes))
+}
+
+/// Sign `call` by Alice with `nonce`, immortal and without tip, and return it hex-encoded.
+fn signed_by_alice(call: RuntimeCall, nonce: u32) -> String {
+	let genesis: H256 = serde_json::from_value(rpc("chain_getBlockHash", json!([0]))).unwrap();
+	let version = rpc("state_getRuntimeVersion", json!([]));
+	let spec_version = version["specVersion"].as_u64().unwrap() as u32;
+	let transaction_version = version["transactionVersion"].as_u64().unwrap() as u32;
+
+	let extra = signed_extra(nonce, Era::Immortal, 0);
+	let payload = SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		((), spec_version, transaction_version, genesis, genesis, (), (), (), ()),
+	);
+	let signature = payload.using_encoded(|payload| AccountKeyring::Alice.sign(payload));
+	let extrinsic = UncheckedExtrinsic::new_signed(
+		call,
+		Address::Id(AccountKeyring::Alice.to_account_id()),
+		signature.into(),
+		extra,
+	);
+	hex(&extrinsic.encode())
+}
+
+/// Submit `extrinsic` and return the number of the block that includes it.
+fn submit_and_wait(extrinsic: &str, timeout: Duration) -> u32 {
+	rpc("author_submitExtrinsic", json!([extrinsic]));
+	let start = Instant::now();
+	let mut next = 1;
+	loop {
+		assert!(start.elapsed() < timeout, "extrinsic not included within {:?}", timeout);
+		let best = rpc("chain_getHeader", json!([]));
+		let best = u32::from_str_radix(best["number"].as_str().unwrap().trim_start_matches("0x"), 16)
+			.unwrap();
+		while next <= best {
+			let hash = rpc("chain_getBlockHash", json!([next]));
+			let block = rpc("chain_getBlock", json!([hash]));
+			let extrinsics = block["block"]["extrinsics"].as_array().unwrap();
+			if extrinsics.iter().any(|included| included.as_str() == Some(extrinsic)) {
+				return next
+			}
+			next += 1;
+		}
+		thread::sleep(Duration::from_secs(1));
+	}
+}
+
+#[test]
+fn stored_data_is_renewed_and_retrieved() {
+	let _node = KillOnDrop(
+		Command::new(env!("CARGO_BIN_EXE_nativex"))
+			.args([
+				"--dev",
+				"--tmp",
+				"--no-telemetry",
+				"--no-prometheus",
+				"--storage-chain",
+				"--port=0",
+			])
+			.arg(format!("--rpc-port={}", rpc_port()))
+			.spawn()
+			.expect("failed to start dev node"),
+	);
+	wait_for_rpc(Duration::from_secs(60));
+
+	let data = b"nativex storage chain blob".repeat(64);
+	let content_hash = hex(&sp_core::blake2_256(&data));
+	assert_eq!(rpc("nativex_storedData", json!([content_hash])), Value::Null);
+
+	let store = signed_by_alice(
+		RuntimeCall::TransactionStorage(pallet_transaction_storage::Call::store {
+			data: data.clone(),
+		}),
+		0,
+	);
+	let stored_at = submit_and_wait(&store, Duration::from_secs(60));
+	assert_eq!(rpc("nativex_storedData", json!([content_hash])), json!(hex(&data)));
+
+	// The blob is the only stored transaction of its block, so its index is 0.
+	let renew = signed_by_alice(
+		RuntimeCall::TransactionStorage(pallet_transaction_storage::Call::renew {
+			block: stored_at,
+			index: 0,
+		}),
+		1,
+	);
+	let renewed_at = submit_and_wait(&renew, Duration::from_secs(60));
+	assert!(renewed_at > stored_at);
+	assert_eq!(rpc("nativex_storedData", json!([content_hash])), json!(hex(&data)));
+}
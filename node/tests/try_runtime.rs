@@ -0,0 +1,93 @@
+//! Run the runtime's migrations through `try-runtime` against a snapshot of a dev chain.
+//!
+//! The snapshot is built offline from the raw `--chain=dev` genesis, so no node is started and
+//! no port is bound; the upgrade then runs from the snapshot file with all pre/post upgrade
+//! checks enabled.
+
+#![cfg(all(feature = "try-runtime", feature = "with-development-runtime"))]
+
+use std::process::Command;
+
+use parity_scale_codec::Encode;
+use sp_core::{
+	bytes::from_hex,
+	storage::{ChildInfo, StateVersion, StorageData, StorageKey},
+	H256,
+};
+
+type KeyValues = Vec<(StorageKey, StorageData)>;
+
+/// The snapshot layout `frame-remote-externalities` reads back with `snap --path`.
+#[derive(Encode)]
+struct Snapshot {
+	state_version: StateVersion,
+	block_hash: H256,
+	top: KeyValues,
+	child: Vec<(ChildInfo, KeyValues)>,
+}
+
+fn nativex() -> Command {
+	Command::new(env!("CARGO_BIN_EXE_nativex"))
+}
+
+/// The hex-encoded `key => value` pairs of a raw chain spec section.
+fn key_values(section: &serde_json::Value) -> KeyValues {
+	let decode = |hex: &str| from_hex(hex).expect("raw chain spec holds hex");
+	section
+		.as_object()
+		.expect("raw chain spec section is an object")
+		.iter()
+		.map(|(key, value)| {
+			let value = value.as_str().expect("raw chain spec values are strings");
+			(StorageKey(decode(key)), StorageData(decode(value)))
+		})
+		.collect()
+}
+
+/// The dev genesis state, encoded as a snapshot.
+fn dev_genesis_snapshot() -> Vec<u8> {
+	let output = nativex()
+		.args(["build-spec", "--chain=dev", "--raw", "--disable-default-bootnode"])
+		.output()
+		.expect("failed to run build-spec");
+	assert!(output.status.success(), "build-spec failed");
+	let spec: serde_json::Value =
+		serde_json::from_slice(&output.stdout).expect("build-spec prints JSON");
+	let raw = &spec["genesis"]["raw"];
+
+	let child = raw["childrenDefault"]
+		.as_object()
+		.into_iter()
+		.flatten()
+		.map(|(key, section)| {
+			let key = from_hex(key).expect("raw chain spec holds hex");
+			(ChildInfo::new_default(&key), key_values(section))
+		})
+		.collect();
+	Snapshot {
+		state_version: StateVersion::V1,
+		// Only used to fetch more state from a live node, which never happens here.
+		block_hash: H256::zero(),
+		top: key_values(&raw["top"]),
+		child,
+	}
+	.encode()
+}
+
+#[test]
+fn migrations_pass_try_runtime_checks_on_snapshot() {
+	let snapshot = std::env::temp_dir()
+		.join(format!("nativex-try-runtime-{}.snap", std::process::id()))
+		.to_string_lossy()
+		.into_owned();
+	std::fs::write(&snapshot, dev_genesis_snapshot()).expect("failed to write the snapshot");
+
+	let status = nativex()
+		.args(["try-runtime", "--chain=dev", "--runtime=existing", "on-runtime-upgrade"])
+		.args(["--checks=all", "snap", "--path"])
+		.arg(&snapshot)
+		.status()
+		.expect("failed to run on-runtime-upgrade");
+	let _ = std::fs::remove_file(&snapshot);
+	assert!(status.success(), "on-runtime-upgrade checks failed");
+}
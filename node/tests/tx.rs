@@ -0,0 +1,83 @@
+//! Build and sign a remark with `nativex tx` without a node, then submit it to a dev node.
+
+#![cfg(feature = "with-development-runtime")]
+
+mod common;
+
+use std::{
+	process::Command,
+	thread,
+	time::{Duration, Instant},
+};
+
+use common::{rpc, rpc_port, wait_for_rpc, KillOnDrop};
+use serde_json::json;
+
+fn nativex() -> Command {
+	Command::new(env!("CARGO_BIN_EXE_nativex"))
+}
+
+/// Run `nativex` with `args` and return what it printed on its first line.
+fn run(args: &[&str]) -> String {
+	let output = nativex().args(args).output().expect("failed to run nativex");
+	let stderr = String::from_utf8_lossy(&output.stderr);
+	assert!(output.status.success(), "{args:?} failed: {stderr}");
+	String::from_utf8(output.stdout).unwrap().lines().next().unwrap_or_default().to_string()
+}
+
+/// Whether a block up to the best one includes `extrinsic`.
+fn is_included(extrinsic: &str) -> bool {
+	let best = rpc("chain_getHeader", json!([]));
+	let best = u32::from_str_radix(best["number"].as_str().unwrap().trim_start_matches("0x"), 16)
+		.unwrap();
+	(1..=best).any(|number| {
+		let hash = rpc("chain_getBlockHash", json!([number]));
+		let block = rpc("chain_getBlock", json!([hash]));
+		let extrinsics = block["block"]["extrinsics"].as_array().unwrap();
+		extrinsics.iter().any(|included| included.as_str() == Some(extrinsic))
+	})
+}
+
+#[test]
+fn offline_signed_remark_is_included() {
+	let description = std::env::temp_dir().join(format!("nativex-tx-{}.json", std::process::id()));
+	std::fs::write(
+		&description,
+		json!({ "pallet": "System", "call": "remark", "args": { "remark": "0x6e6174697665" } })
+			.to_string(),
+	)
+	.unwrap();
+	let call = run(&["tx", "build", description.to_str().unwrap()]);
+	std::fs::remove_file(&description).unwrap();
+	assert_eq!(call, "0x0000186e6174697665");
+
+	let _node = KillOnDrop(
+		nativex()
+			.args(["--dev", "--tmp", "--no-telemetry", "--no-prometheus", "--port=0"])
+			.arg(format!("--rpc-port={}", rpc_port()))
+			.spawn()
+			.expect("failed to start dev node"),
+	);
+	wait_for_rpc(Duration::from_secs(60));
+	let genesis_hash = rpc("chain_getBlockHash", json!([0]));
+
+	let extrinsic = run(&[
+		"tx",
+		"sign",
+		"--call",
+		&call,
+		"--nonce=0",
+		"--genesis-hash",
+		genesis_hash.as_str().unwrap(),
+		"--suri=//Alice",
+	]);
+	let url = format!("http://127.0.0.1:{}", rpc_port());
+	let hash = run(&["tx", "submit", &extrinsic, "--url", &url]);
+	assert!(hash.starts_with("0x"), "{hash}");
+
+	let start = Instant::now();
+	while !is_included(&extrinsic) {
+		assert!(start.elapsed() < Duration::from_secs(60), "extrinsic not included");
+		thread::sleep(Duration::from_secs(1));
+	}
+}
@@ -0,0 +1,84 @@
+//! Benchmarking setup for pallet-asset-treasury
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as AssetTreasury;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+
+fn create_spend<T: Config>() -> (T::AssetKind, AssetBalanceOf<T>, T::AccountId) {
+	let amount: AssetBalanceOf<T> = 100u32.into();
+	let asset_kind = T::BenchmarkHelper::create_asset_kind(&Pallet::<T>::account_id(), amount);
+	let beneficiary: T::AccountId = account("beneficiary", 0, 0);
+	(asset_kind, amount, beneficiary)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn spend() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary) = create_spend::<T>();
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, asset_kind, amount, beneficiary, None);
+
+		assert!(Spends::<T>::get(0).is_some());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn payout() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary) = create_spend::<T>();
+		Pallet::<T>::spend(origin, asset_kind, amount, beneficiary, None)?;
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert!(Spends::<T>::get(0).is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn void_spend() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary) = create_spend::<T>();
+		Pallet::<T>::spend(origin, asset_kind, amount, beneficiary, None)?;
+		let reject_origin =
+			T::RejectOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(reject_origin as T::RuntimeOrigin, 0);
+
+		assert!(Spends::<T>::get(0).is_none());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn check_status() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SpendOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let (asset_kind, amount, beneficiary) = create_spend::<T>();
+		Pallet::<T>::spend(origin, asset_kind, amount, beneficiary, None)?;
+		let expire_at = Spends::<T>::get(0).ok_or(BenchmarkError::Weightless)?.expire_at;
+		frame_system::Pallet::<T>::set_block_number(expire_at);
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller), 0);
+
+		assert!(Spends::<T>::get(0).is_none());
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(AssetTreasury, crate::mock::new_test_ext(), crate::mock::Test);
+}
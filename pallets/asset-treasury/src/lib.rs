@@ -0,0 +1,317 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Asset Treasury Pallet
+//!
+//! Lets the treasury approve spends denominated in `pallet_assets` tokens.
+//!
+//! - [`Pallet::spend`] approves a spend. The amount is converted into the native currency with
+//!   `Config::BalanceConverter` (e.g. `pallet_asset_rate`) and checked against the limit granted
+//!   by `Config::SpendOrigin`, so spend limits can be tiered by origin.
+//! - [`Pallet::payout`] pays an approved spend from the treasury account once it is valid. A
+//!   failed transfer does not drop the spend: its status records the number of failed attempts
+//!   and anyone may retry until the spend expires.
+//! - [`Pallet::void_spend`] cancels a spend that has not been paid yet.
+//! - [`Pallet::check_status`] lets anyone remove a spend that expired without being paid.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::traits::tokens::fungibles;
+use sp_runtime::traits::AccountIdConversion;
+
+/// Index of an approved spend.
+pub type SpendIndex = u32;
+
+type AssetBalanceOf<T> =
+	<<T as Config>::Assets as fungibles::Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::with_storage_layer,
+		traits::tokens::{fungibles::Mutate, ConversionFromAssetBalance, Preservation},
+		PalletId,
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::Saturating;
+
+	/// Payout progress of an approved spend.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+	pub enum PaymentState {
+		/// No payout has been attempted yet.
+		Pending,
+		/// The last payout attempt failed; it may be retried.
+		Failed {
+			/// Number of failed attempts so far.
+			attempts: u32,
+		},
+	}
+
+	/// An approved spend waiting to be paid out.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+	pub struct SpendStatus<AssetKind, AssetBalance, AccountId, BlockNumber> {
+		/// The asset to pay.
+		pub asset_kind: AssetKind,
+		/// The amount of `asset_kind` to pay.
+		pub amount: AssetBalance,
+		/// The account receiving the payment.
+		pub beneficiary: AccountId,
+		/// The block from which the spend can be paid out.
+		pub valid_from: BlockNumber,
+		/// The block from which the spend can no longer be paid out.
+		pub expire_at: BlockNumber,
+		/// Payout progress.
+		pub status: PaymentState,
+	}
+
+	pub type SpendStatusOf<T> = SpendStatus<
+		<T as Config>::AssetKind,
+		AssetBalanceOf<T>,
+		<T as frame_system::Config>::AccountId,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The identifier of the assets the treasury can spend.
+		type AssetKind: Parameter + MaxEncodedLen + Copy;
+
+		/// The assets held by the treasury account.
+		type Assets: Mutate<Self::AccountId, AssetId = Self::AssetKind>;
+
+		/// The native balance type, used for spend limits.
+		type NativeBalance: Parameter + MaxEncodedLen + Copy + PartialOrd;
+
+		/// Converts an asset amount into the native balance to check it against spend limits.
+		type BalanceConverter: ConversionFromAssetBalance<
+			AssetBalanceOf<Self>,
+			Self::AssetKind,
+			Self::NativeBalance,
+		>;
+
+		/// The origin allowed to approve spends; its success value is the maximum native value
+		/// of a single spend.
+		type SpendOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::NativeBalance>;
+
+		/// The origin allowed to void approved spends.
+		type RejectOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The treasury's pallet id, used for deriving the account holding the assets.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// The number of blocks after `valid_from` during which a spend can be paid out.
+		#[pallet::constant]
+		type PayoutPeriod: Get<Self::BlockNumber>;
+
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+
+		/// Helper to set up the assets used by the benchmarks.
+		#[cfg(feature = "runtime-benchmarks")]
+		type BenchmarkHelper: BenchmarkHelper<Self::AssetKind, Self::AccountId, AssetBalanceOf<Self>>;
+	}
+
+	/// The number of spends that have been approved.
+	#[pallet::storage]
+	#[pallet::getter(fn spend_count)]
+	pub type SpendCount<T> = StorageValue<_, SpendIndex, ValueQuery>;
+
+	/// Approved spends that have not been paid out, voided or removed yet.
+	#[pallet::storage]
+	#[pallet::getter(fn spends)]
+	pub type Spends<T: Config> =
+		StorageMap<_, Twox64Concat, SpendIndex, SpendStatusOf<T>, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A spend was approved.
+		AssetSpendApproved {
+			index: SpendIndex,
+			asset_kind: T::AssetKind,
+			amount: AssetBalanceOf<T>,
+			beneficiary: T::AccountId,
+			valid_from: T::BlockNumber,
+			expire_at: T::BlockNumber,
+		},
+		/// A spend was voided.
+		AssetSpendVoided { index: SpendIndex },
+		/// A spend was paid out.
+		Paid { index: SpendIndex },
+		/// A payout attempt failed; the spend stays approved and can be retried.
+		PaymentFailed { index: SpendIndex, attempts: u32, error: DispatchError },
+		/// An expired spend was removed.
+		SpendProcessed { index: SpendIndex },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The origin's spend limit is lower than the native value of the spend.
+		InsufficientPermission,
+		/// The asset amount could not be converted into the native balance.
+		FailedToConvertBalance,
+		/// No approved spend with this index.
+		InvalidIndex,
+		/// The spend is not valid yet.
+		EarlyPayout,
+		/// The spend has expired and can no longer be paid out.
+		SpendExpired,
+		/// The spend has not expired yet, so it can still be paid out.
+		SpendNotExpired,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Approve a spend of `amount` of `asset_kind` to `beneficiary`.
+		///
+		/// The native value of the spend, as given by `BalanceConverter`, must not exceed the
+		/// limit of `SpendOrigin`. The spend can be paid out from `valid_from` (defaults to now)
+		/// for `PayoutPeriod` blocks.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::spend())]
+		pub fn spend(
+			origin: OriginFor<T>,
+			asset_kind: T::AssetKind,
+			#[pallet::compact] amount: AssetBalanceOf<T>,
+			beneficiary: T::AccountId,
+			valid_from: Option<T::BlockNumber>,
+		) -> DispatchResult {
+			let max_amount = T::SpendOrigin::ensure_origin(origin)?;
+			let native_amount = T::BalanceConverter::from_asset_balance(amount, asset_kind)
+				.map_err(|_| Error::<T>::FailedToConvertBalance)?;
+			ensure!(native_amount <= max_amount, Error::<T>::InsufficientPermission);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			let valid_from = valid_from.unwrap_or(now);
+			let expire_at = valid_from.saturating_add(T::PayoutPeriod::get());
+			ensure!(expire_at > now, Error::<T>::SpendExpired);
+
+			let index = SpendCount::<T>::get();
+			Spends::<T>::insert(
+				index,
+				SpendStatus {
+					asset_kind,
+					amount,
+					beneficiary: beneficiary.clone(),
+					valid_from,
+					expire_at,
+					status: PaymentState::Pending,
+				},
+			);
+			SpendCount::<T>::put(index.saturating_add(1));
+
+			Self::deposit_event(Event::AssetSpendApproved {
+				index,
+				asset_kind,
+				amount,
+				beneficiary,
+				valid_from,
+				expire_at,
+			});
+			Ok(())
+		}
+
+		/// Pay out approved spend `index` from the treasury account.
+		///
+		/// A failed transfer is recorded in the spend's status and does not fail the call, so
+		/// the payout can be retried until the spend expires.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::payout())]
+		pub fn payout(origin: OriginFor<T>, index: SpendIndex) -> DispatchResult {
+			ensure_signed(origin)?;
+			let mut spend = Spends::<T>::get(index).ok_or(Error::<T>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= spend.valid_from, Error::<T>::EarlyPayout);
+			ensure!(now < spend.expire_at, Error::<T>::SpendExpired);
+
+			// Roll back whatever a failed transfer wrote, but keep the call itself successful so
+			// the failure is recorded.
+			let transfer = with_storage_layer(|| {
+				T::Assets::transfer(
+					spend.asset_kind,
+					&Self::account_id(),
+					&spend.beneficiary,
+					spend.amount,
+					Preservation::Expendable,
+				)
+			});
+			match transfer {
+				Ok(_) => {
+					Spends::<T>::remove(index);
+					Self::deposit_event(Event::Paid { index });
+				},
+				Err(error) => {
+					let attempts = match spend.status {
+						PaymentState::Pending => 1,
+						PaymentState::Failed { attempts } => attempts.saturating_add(1),
+					};
+					spend.status = PaymentState::Failed { attempts };
+					Spends::<T>::insert(index, spend);
+					Self::deposit_event(Event::PaymentFailed { index, attempts, error });
+				},
+			}
+			Ok(())
+		}
+
+		/// Void approved spend `index` before it is paid out.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::void_spend())]
+		pub fn void_spend(origin: OriginFor<T>, index: SpendIndex) -> DispatchResult {
+			T::RejectOrigin::ensure_origin(origin)?;
+			ensure!(Spends::<T>::contains_key(index), Error::<T>::InvalidIndex);
+
+			Spends::<T>::remove(index);
+			Self::deposit_event(Event::AssetSpendVoided { index });
+			Ok(())
+		}
+
+		/// Remove spend `index` once it has expired, whether or not a payout was attempted.
+		///
+		/// Anyone may call this; the fee is waived when a spend is removed.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::check_status())]
+		pub fn check_status(origin: OriginFor<T>, index: SpendIndex) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let spend = Spends::<T>::get(index).ok_or(Error::<T>::InvalidIndex)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now >= spend.expire_at, Error::<T>::SpendNotExpired);
+
+			Spends::<T>::remove(index);
+			Self::deposit_event(Event::SpendProcessed { index });
+			Ok(Pays::No.into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account holding the treasury's assets.
+		pub fn account_id() -> T::AccountId {
+			T::PalletId::get().into_account_truncating()
+		}
+	}
+}
+
+/// Sets up the assets used by the benchmarks.
+#[cfg(feature = "runtime-benchmarks")]
+pub trait BenchmarkHelper<AssetKind, AccountId, AssetBalance> {
+	/// Create an asset with a conversion rate to the native balance, and fund `who` with
+	/// `amount` of it.
+	fn create_asset_kind(who: &AccountId, amount: AssetBalance) -> AssetKind;
+}
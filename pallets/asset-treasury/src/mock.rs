@@ -0,0 +1,183 @@
+use crate as pallet_asset_treasury;
+use frame_support::{
+	parameter_types,
+	traits::{
+		tokens::ConversionFromAssetBalance, AsEnsureOriginWithArg, ConstU16, ConstU32, ConstU64,
+		EnsureOrigin, GenesisBuild,
+	},
+	PalletId,
+};
+use frame_system::{EnsureRoot, EnsureSigned, RawOrigin};
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Assets: pallet_assets,
+		AssetTreasury: pallet_asset_treasury,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+impl pallet_assets::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Balance = u64;
+	type AssetId = u32;
+	type AssetIdParameter = u32;
+	type Currency = Balances;
+	type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<u64>>;
+	type ForceOrigin = EnsureRoot<u64>;
+	type AssetDeposit = ConstU64<1>;
+	type AssetAccountDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type MetadataDepositPerByte = ConstU64<1>;
+	type ApprovalDeposit = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type CallbackHandle = ();
+	type WeightInfo = ();
+	type RemoveItemsLimit = ConstU32<1000>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = ();
+}
+
+/// The asset with a conversion rate: one unit is worth two native units.
+pub const ASSET: u32 = 1;
+/// An asset without a conversion rate.
+pub const UNRATED_ASSET: u32 = 2;
+
+pub struct MockBalanceConverter;
+impl ConversionFromAssetBalance<u64, u32, u64> for MockBalanceConverter {
+	type Error = ();
+	fn from_asset_balance(balance: u64, asset_id: u32) -> Result<u64, ()> {
+		match asset_id {
+			ASSET => Ok(balance * 2),
+			_ => Err(()),
+		}
+	}
+}
+
+/// Root may spend anything; accounts 10, 11 and 12 stand in for the small, medium and big
+/// spender tiers.
+pub struct TestSpendOrigin;
+impl EnsureOrigin<RuntimeOrigin> for TestSpendOrigin {
+	type Success = u64;
+	fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+		Result::<RawOrigin<_>, RuntimeOrigin>::from(o).and_then(|o| match o {
+			RawOrigin::Root => Ok(u64::max_value()),
+			RawOrigin::Signed(10) => Ok(10),
+			RawOrigin::Signed(11) => Ok(100),
+			RawOrigin::Signed(12) => Ok(1_000),
+			r => Err(RuntimeOrigin::from(r)),
+		})
+	}
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		Ok(RuntimeOrigin::root())
+	}
+}
+
+parameter_types! {
+	pub const TreasuryPalletId: PalletId = PalletId(*b"py/trsry");
+}
+
+impl pallet_asset_treasury::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetKind = u32;
+	type Assets = Assets;
+	type NativeBalance = u64;
+	type BalanceConverter = MockBalanceConverter;
+	type SpendOrigin = TestSpendOrigin;
+	type RejectOrigin = EnsureRoot<u64>;
+	type PalletId = TreasuryPalletId;
+	type PayoutPeriod = ConstU64<5>;
+	type WeightInfo = ();
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = BenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct BenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl crate::BenchmarkHelper<u32, u64, u64> for BenchmarkHelper {
+	fn create_asset_kind(who: &u64, amount: u64) -> u32 {
+		use frame_support::traits::tokens::fungibles::Mutate;
+		// `ASSET` is created at genesis, see `new_test_ext`.
+		Assets::mint_into(ASSET, who, amount).unwrap();
+		ASSET
+	}
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(0, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(ASSET, 0, true, 1), (UNRATED_ASSET, 0, true, 1)],
+		metadata: vec![],
+		accounts: vec![(ASSET, AssetTreasury::account_id(), 100)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
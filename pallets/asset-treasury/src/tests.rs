@@ -0,0 +1,156 @@
+use crate::{mock::*, Error, Event, PaymentState, SpendStatus};
+use frame_support::{assert_noop, assert_ok, dispatch::Pays, traits::fungibles::Inspect};
+use sp_runtime::traits::BadOrigin;
+
+#[test]
+fn spend_limits_are_tiered_by_origin() {
+	new_test_ext().execute_with(|| {
+		// 6 units of `ASSET` are worth 12 native units.
+		assert_noop!(
+			AssetTreasury::spend(RuntimeOrigin::signed(10), ASSET, 6, 1, None),
+			Error::<Test>::InsufficientPermission
+		);
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::signed(10), ASSET, 5, 1, None));
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::signed(11), ASSET, 6, 1, None));
+		assert_noop!(
+			AssetTreasury::spend(RuntimeOrigin::signed(11), ASSET, 51, 1, None),
+			Error::<Test>::InsufficientPermission
+		);
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::signed(12), ASSET, 51, 1, None));
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 1_000, 1, None));
+		assert_noop!(AssetTreasury::spend(RuntimeOrigin::signed(1), ASSET, 1, 1, None), BadOrigin);
+
+		assert_eq!(AssetTreasury::spend_count(), 4);
+		System::assert_last_event(
+			Event::AssetSpendApproved {
+				index: 3,
+				asset_kind: ASSET,
+				amount: 1_000,
+				beneficiary: 1,
+				valid_from: 1,
+				expire_at: 6,
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn spend_requires_conversion_rate() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			AssetTreasury::spend(RuntimeOrigin::root(), UNRATED_ASSET, 1, 1, None),
+			Error::<Test>::FailedToConvertBalance
+		);
+	});
+}
+
+#[test]
+fn payout_respects_validity_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 10, 1, Some(3)));
+		assert_noop!(
+			AssetTreasury::payout(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::EarlyPayout
+		);
+
+		System::set_block_number(8);
+		assert_noop!(
+			AssetTreasury::payout(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::SpendExpired
+		);
+		assert_noop!(
+			AssetTreasury::payout(RuntimeOrigin::signed(1), 1),
+			Error::<Test>::InvalidIndex
+		);
+	});
+}
+
+#[test]
+fn payout_transfers_asset() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 10, 1, None));
+		assert_ok!(AssetTreasury::payout(RuntimeOrigin::signed(2), 0));
+
+		assert_eq!(Assets::balance(ASSET, 1), 10);
+		assert_eq!(Assets::balance(ASSET, AssetTreasury::account_id()), 90);
+		assert_eq!(AssetTreasury::spends(0), None);
+		System::assert_last_event(Event::Paid { index: 0 }.into());
+	});
+}
+
+#[test]
+fn failed_payout_is_tracked_and_can_be_retried() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 150, 1, None));
+
+		// The treasury only holds 100 units.
+		assert_ok!(AssetTreasury::payout(RuntimeOrigin::signed(2), 0));
+		assert_ok!(AssetTreasury::payout(RuntimeOrigin::signed(2), 0));
+		assert_eq!(
+			AssetTreasury::spends(0),
+			Some(SpendStatus {
+				asset_kind: ASSET,
+				amount: 150,
+				beneficiary: 1,
+				valid_from: 1,
+				expire_at: 6,
+				status: PaymentState::Failed { attempts: 2 },
+			})
+		);
+		assert_eq!(Assets::balance(ASSET, 1), 0);
+
+		assert_ok!(Assets::mint(RuntimeOrigin::signed(0), ASSET, AssetTreasury::account_id(), 50));
+		assert_ok!(AssetTreasury::payout(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Assets::balance(ASSET, 1), 150);
+		assert_eq!(AssetTreasury::spends(0), None);
+	});
+}
+
+#[test]
+fn void_spend_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 10, 1, None));
+		assert_noop!(AssetTreasury::void_spend(RuntimeOrigin::signed(10), 0), BadOrigin);
+		assert_ok!(AssetTreasury::void_spend(RuntimeOrigin::root(), 0));
+		System::assert_last_event(Event::AssetSpendVoided { index: 0 }.into());
+
+		assert_noop!(
+			AssetTreasury::payout(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::InvalidIndex
+		);
+	});
+}
+
+#[test]
+fn expired_spends_are_removed_by_anyone() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 150, 1, None));
+		assert_ok!(AssetTreasury::spend(RuntimeOrigin::root(), ASSET, 10, 1, Some(3)));
+		// The treasury only holds 100 units.
+		assert_ok!(AssetTreasury::payout(RuntimeOrigin::signed(2), 0));
+		assert_noop!(
+			AssetTreasury::check_status(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::SpendNotExpired
+		);
+
+		System::set_block_number(6);
+		let info = AssetTreasury::check_status(RuntimeOrigin::signed(3), 0).unwrap();
+		assert_eq!(info.pays_fee, Pays::No);
+		System::assert_last_event(Event::SpendProcessed { index: 0 }.into());
+		assert_eq!(AssetTreasury::spends(0), None);
+		assert_noop!(
+			AssetTreasury::check_status(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::InvalidIndex
+		);
+
+		// The second spend is valid from block 3, so it only expires at block 8.
+		assert_noop!(
+			AssetTreasury::check_status(RuntimeOrigin::signed(3), 1),
+			Error::<Test>::SpendNotExpired
+		);
+		System::set_block_number(8);
+		assert_ok!(AssetTreasury::check_status(RuntimeOrigin::signed(3), 1));
+		assert_eq!(AssetTreasury::spends(1), None);
+	});
+}
@@ -0,0 +1,94 @@
+//! Weights for pallet_asset_treasury
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=pallet_asset_treasury` before relying on them.
+//!
+//! The database weight counts the storage accesses listed above each function. The proof size
+//! adds up, for every item read, its `MaxEncodedLen` in the runtime plus the trie proof budget the
+//! benchmarks use: 2475 bytes for a map entry and 495 bytes for a plain value. The execution time
+//! is given with its justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_asset_treasury.
+pub trait WeightInfo {
+	fn spend() -> Weight;
+	fn payout() -> Weight;
+	fn void_spend() -> Weight;
+	fn check_status() -> Weight;
+}
+
+/// Weights for pallet_asset_treasury using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: AssetRate ConversionRateToNative (r:1 w:0)
+	/// Proof: AssetRate ConversionRateToNative (max_size: 36, added: 2511)
+	/// Storage: AssetTreasury SpendCount (r:1 w:1)
+	/// Proof: AssetTreasury SpendCount (max_size: 4, added: 499)
+	/// Storage: AssetTreasury Spends (r:0 w:1)
+	/// Execution: one fixed-point conversion and one small insert, 20 µs.
+	fn spend() -> Weight {
+		Weight::from_parts(20_000_000, 3_010)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: AssetTreasury Spends (r:1 w:1)
+	/// Proof: AssetTreasury Spends (max_size: 77, added: 2552)
+	/// Storage: Assets Asset (r:1 w:1)
+	/// Proof: Assets Asset (max_size: 210, added: 2685)
+	/// Storage: Assets Account (r:2 w:2)
+	/// Proof: Assets Account (max_size: 134, added: 2609)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a `pallet_assets` transfer in a storage layer, 60 µs.
+	fn payout() -> Weight {
+		Weight::from_parts(60_000_000, 13_058)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: AssetTreasury Spends (r:1 w:1)
+	/// Proof: AssetTreasury Spends (max_size: 77, added: 2552)
+	/// Execution: one origin check and one removal, 15 µs.
+	fn void_spend() -> Weight {
+		Weight::from_parts(15_000_000, 2_552)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: AssetTreasury Spends (r:1 w:1)
+	/// Proof: AssetTreasury Spends (max_size: 77, added: 2552)
+	/// Execution: one comparison and one removal, as `void_spend`, 15 µs.
+	fn check_status() -> Weight {
+		Weight::from_parts(15_000_000, 2_552)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn spend() -> Weight {
+		Weight::from_parts(20_000_000, 3_010)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn payout() -> Weight {
+		Weight::from_parts(60_000_000, 13_058)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	fn void_spend() -> Weight {
+		Weight::from_parts(15_000_000, 2_552)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn check_status() -> Weight {
+		Weight::from_parts(15_000_000, 2_552)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
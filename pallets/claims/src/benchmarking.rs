@@ -0,0 +1,123 @@
+//! Benchmarking setup for pallet-claims
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+use frame_benchmarking::v2::*;
+use frame_support::{
+	pallet_prelude::{Get, TransactionSource, ValidateUnsigned},
+	traits::{Currency, UnfilteredDispatchable},
+	BoundedVec,
+};
+use frame_system::RawOrigin;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::traits::Saturating;
+
+const KEY_TYPE: KeyTypeId = KeyTypeId(*b"clam");
+const STATEMENT: StatementId = 0;
+
+/// A statement of the maximum length.
+fn statement<T: Config>() -> BoundedVec<u8, T::MaxStatementLength> {
+	sp_std::vec![b'x'; T::MaxStatementLength::get() as usize]
+		.try_into()
+		.expect("the statement has the maximum length; qed")
+}
+
+/// Allocate to `claimant` an amount that vests and requires attesting to [`statement`].
+fn mint<T: Config>(claimant: Claimant) -> Result<(), BenchmarkError> {
+	let amount = T::Currency::minimum_balance().saturating_mul(1_000u32.into());
+	let per_block = T::Currency::minimum_balance();
+	Pallet::<T>::set_statement(RawOrigin::Root.into(), STATEMENT, statement::<T>())?;
+	Pallet::<T>::mint_claim(
+		RawOrigin::Root.into(),
+		claimant,
+		amount,
+		Some((amount, per_block, 1u32.into())),
+		Some(STATEMENT),
+	)?;
+	Ok(())
+}
+
+/// Validate and dispatch `call`, as the block author would an unsigned extrinsic.
+fn validate_and_dispatch<T: Config>(call: Call<T>) -> Result<(), BenchmarkError> {
+	Pallet::<T>::validate_unsigned(TransactionSource::External, &call)
+		.map_err(|_| BenchmarkError::Stop("the claim is invalid"))?;
+	call.dispatch_bypass_filter(RawOrigin::None.into())?;
+	Ok(())
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn claim_ethereum() -> Result<(), BenchmarkError> {
+		let dest: T::AccountId = account("dest", 0, 0);
+		let statement = statement::<T>().into_inner();
+		let message = Pallet::<T>::claim_message(&dest, &statement);
+		let hash = eth_message_primitives::eth_message_hash(&message);
+		let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &hash)
+			.expect("the key was just generated; qed");
+		let signature = ClaimSignature::Ethereum(signature.0);
+		let claimant = signature.signer(&message).expect("the signature was just made; qed");
+		mint::<T>(claimant)?;
+		let call = Call::<T>::claim_attest { dest, signature, statement };
+
+		#[block]
+		{
+			validate_and_dispatch::<T>(call)?;
+		}
+
+		assert!(!Claims::<T>::contains_key(claimant));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn claim_sr25519() -> Result<(), BenchmarkError> {
+		let dest: T::AccountId = account("dest", 0, 0);
+		let statement = statement::<T>().into_inner();
+		// Raw signatures are checked after wrapped ones fail, so they cost the most.
+		let message = Pallet::<T>::claim_message(&dest, &statement);
+		let signer = sp_io::crypto::sr25519_generate(KEY_TYPE, None);
+		let signature = sp_io::crypto::sr25519_sign(KEY_TYPE, &signer, &message)
+			.expect("the key was just generated; qed");
+		let claimant = Claimant::Sr25519(signer);
+		mint::<T>(claimant)?;
+		let call = Call::<T>::claim_attest {
+			dest,
+			signature: ClaimSignature::Sr25519 { signer, signature },
+			statement,
+		};
+
+		#[block]
+		{
+			validate_and_dispatch::<T>(call)?;
+		}
+
+		assert!(!Claims::<T>::contains_key(claimant));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn mint_claim() -> Result<(), BenchmarkError> {
+		let claimant = Claimant::Ethereum(H160::repeat_byte(1));
+		let amount = T::Currency::minimum_balance();
+		Pallet::<T>::set_statement(RawOrigin::Root.into(), STATEMENT, statement::<T>())?;
+
+		#[extrinsic_call]
+		_(RawOrigin::Root, claimant, amount, Some((amount, amount, 1u32.into())), Some(STATEMENT));
+
+		assert_eq!(Claims::<T>::get(claimant), Some(amount));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn set_statement() {
+		#[extrinsic_call]
+		_(RawOrigin::Root, STATEMENT, statement::<T>());
+
+		assert_eq!(Statements::<T>::get(STATEMENT), Some(statement::<T>()));
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,433 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Claims Pallet
+//!
+//! Pays out allocations made at genesis to the holders of Ethereum (ecdsa) or sr25519 keys, who
+//! have no native account to receive them yet.
+//!
+//! - Each [`Claimant`] is allocated an amount, optionally locked by a vesting schedule of
+//!   `Config::VestingSchedule` once claimed, and optionally a statement it must attest to.
+//! - [`Pallet::claim`] pays an allocation out to any account that exists or that the allocation
+//!   creates, being at least the existential deposit. It is an unsigned extrinsic, since
+//!   the destination may hold nothing to pay fees with; the proof is a signature of
+//!   [`Pallet::claim_message`] for the destination made with the key of the claimant, checked
+//!   in `ValidateUnsigned`. Ethereum keys sign it with `personal_sign` (EIP-191); sr25519 keys
+//!   sign it raw or wrapped in `<Bytes>` as wallets do.
+//! - [`Pallet::claim_attest`] does the same for claimants that must attest to a statement, whose
+//!   text is then part of the signed message.
+//! - Root adds allocations with [`Pallet::mint_claim`] and statements with
+//!   [`Pallet::set_statement`].
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use eth_message_primitives::{eth_signer, hex};
+use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_core::{sr25519, H160};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The key that an allocation is paid out to the holder of.
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum Claimant {
+	/// An Ethereum address.
+	Ethereum(H160),
+	/// An sr25519 public key.
+	Sr25519(sr25519::Public),
+}
+
+/// A signature of [`Pallet::claim_message`] made with the key of a [`Claimant`].
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum ClaimSignature {
+	/// A `personal_sign` signature: `r`, `s` and the recovery id `v` (0, 1, 27 or 28).
+	Ethereum([u8; 65]),
+	/// An sr25519 signature and the key that made it.
+	Sr25519 { signer: sr25519::Public, signature: sr25519::Signature },
+}
+
+impl ClaimSignature {
+	/// The claimant whose key made this signature of `message`, if it is valid.
+	pub fn signer(&self, message: &[u8]) -> Option<Claimant> {
+		match self {
+			Self::Ethereum(signature) => eth_signer(message, signature).map(Claimant::Ethereum),
+			Self::Sr25519 { signer, signature } => {
+				let wrapped = [&b"<Bytes>"[..], message, &b"</Bytes>"[..]].concat();
+				let valid = sp_io::crypto::sr25519_verify(signature, &wrapped, signer) ||
+					sp_io::crypto::sr25519_verify(signature, message, signer);
+				valid.then_some(Claimant::Sr25519(*signer))
+			},
+		}
+	}
+}
+
+/// Identifies a statement claimants may have to attest to.
+pub type StatementId = u32;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, Imbalance, VestingSchedule},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{CheckedSub, Saturating, Zero};
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
+	/// A vesting schedule: the amount locked, the amount unlocked per block and the block the
+	/// unlocking starts at.
+	pub type VestingOf<T> = (BalanceOf<T>, BalanceOf<T>, BlockNumberFor<T>);
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The native currency, minted to the destination of a claim.
+		type Currency: Currency<Self::AccountId>;
+
+		/// Locks the claimed balance of allocations that vest.
+		type VestingSchedule: VestingSchedule<
+			Self::AccountId,
+			Moment = BlockNumberFor<Self>,
+			Currency = Self::Currency,
+		>;
+
+		/// The start of every claim message, telling the claimant what they sign.
+		#[pallet::constant]
+		type Prefix: Get<&'static [u8]>;
+
+		/// The maximum length of a statement.
+		#[pallet::constant]
+		type MaxStatementLength: Get<u32>;
+
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The amount each claimant may claim.
+	#[pallet::storage]
+	pub type Claims<T: Config> = StorageMap<_, Blake2_128Concat, Claimant, BalanceOf<T>>;
+
+	/// The sum of all amounts that are yet to be claimed.
+	#[pallet::storage]
+	pub type Total<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The vesting schedule the claimed amount of a claimant is locked by.
+	#[pallet::storage]
+	pub type Vesting<T: Config> = StorageMap<_, Blake2_128Concat, Claimant, VestingOf<T>>;
+
+	/// The statement each claimant must attest to.
+	#[pallet::storage]
+	pub type Signing<T: Config> = StorageMap<_, Blake2_128Concat, Claimant, StatementId>;
+
+	/// The text of each statement.
+	#[pallet::storage]
+	pub type Statements<T: Config> =
+		StorageMap<_, Twox64Concat, StatementId, BoundedVec<u8, T::MaxStatementLength>>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// The amount of each claimant, its vesting schedule and the statement it must attest
+		/// to, if any.
+		pub claims: Vec<(Claimant, BalanceOf<T>, Option<VestingOf<T>>, Option<StatementId>)>,
+		/// The text of each statement.
+		pub statements: Vec<(StatementId, Vec<u8>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { claims: Vec::new(), statements: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (id, statement) in &self.statements {
+				let statement: BoundedVec<_, _> =
+					statement.clone().try_into().expect("statement exceeds MaxStatementLength");
+				Statements::<T>::insert(id, statement);
+			}
+			for (claimant, amount, vesting, statement) in &self.claims {
+				assert!(
+					statement.map_or(true, |id| Statements::<T>::contains_key(id)),
+					"claim of {claimant:?} requires an unknown statement",
+				);
+				Pallet::<T>::insert_claim(*claimant, *amount, *vesting, *statement);
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The allocation of `claimant` was paid out to `who`.
+		Claimed { who: T::AccountId, claimant: Claimant, amount: BalanceOf<T> },
+		/// `claimant` was allocated `amount`.
+		ClaimMinted { claimant: Claimant, amount: BalanceOf<T> },
+		/// The text of statement `id` was set.
+		StatementSet { id: StatementId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The signature is not a valid signature of the claim message.
+		InvalidSignature,
+		/// The key that signed the claim has nothing to claim.
+		SignerHasNoClaim,
+		/// The statement is not the one the claimant must attest to.
+		InvalidStatement,
+		/// The statement does not exist.
+		UnknownStatement,
+		/// The destination can't take another vesting schedule.
+		CannotVest,
+		/// The amount claimed exceeds the total left to claim.
+		PotUnderflow,
+		/// The amount is below the existential deposit and the destination does not exist.
+		BelowMinimumBalance,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Pay the allocation of the claimant that signed `signature` out to `dest`.
+		///
+		/// `signature` is a signature of [`Pallet::claim_message`] for `dest`, without a
+		/// statement. Claimants that must attest to a statement use [`Pallet::claim_attest`].
+		#[pallet::call_index(0)]
+		#[pallet::weight(claim_weight::<T>(signature))]
+		pub fn claim(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			signature: ClaimSignature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let claimant = Self::check_claim(&dest, &signature, &[])?;
+			Self::process_claim(claimant, dest)
+		}
+
+		/// Allocate `value` to `claimant`, replacing the allocation it has.
+		///
+		/// The claimed amount is locked by `vesting_schedule`, and the claimant must attest to
+		/// `statement`, if they are given.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::mint_claim())]
+		pub fn mint_claim(
+			origin: OriginFor<T>,
+			claimant: Claimant,
+			value: BalanceOf<T>,
+			vesting_schedule: Option<VestingOf<T>>,
+			statement: Option<StatementId>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				statement.map_or(true, |id| Statements::<T>::contains_key(id)),
+				Error::<T>::UnknownStatement
+			);
+			Self::insert_claim(claimant, value, vesting_schedule, statement);
+			Self::deposit_event(Event::ClaimMinted { claimant, amount: value });
+			Ok(())
+		}
+
+		/// Pay the allocation of the claimant that signed `signature` out to `dest`, attesting
+		/// to `statement`.
+		///
+		/// `signature` is a signature of [`Pallet::claim_message`] for `dest` and `statement`,
+		/// which must be the text of the statement the claimant must attest to.
+		#[pallet::call_index(2)]
+		#[pallet::weight(claim_weight::<T>(signature))]
+		pub fn claim_attest(
+			origin: OriginFor<T>,
+			dest: T::AccountId,
+			signature: ClaimSignature,
+			statement: Vec<u8>,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+			let claimant = Self::check_claim(&dest, &signature, &statement)?;
+			Self::process_claim(claimant, dest)
+		}
+
+		/// Set the text of statement `id`.
+		///
+		/// Claimants that must attest to it have to sign the new text.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::set_statement())]
+		pub fn set_statement(
+			origin: OriginFor<T>,
+			id: StatementId,
+			statement: BoundedVec<u8, T::MaxStatementLength>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			Statements::<T>::insert(id, statement);
+			Self::deposit_event(Event::StatementSet { id });
+			Ok(())
+		}
+	}
+
+	/// The weight of a claim, whose signature check depends on the kind of key.
+	fn claim_weight<T: Config>(signature: &ClaimSignature) -> Weight {
+		match signature {
+			ClaimSignature::Ethereum(_) => T::WeightInfo::claim_ethereum(),
+			ClaimSignature::Sr25519 { .. } => T::WeightInfo::claim_sr25519(),
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			const PRIORITY: u64 = 100;
+
+			let claimant = match call {
+				Call::claim { dest, signature } => Self::check_claim(dest, signature, &[]),
+				Call::claim_attest { dest, signature, statement } =>
+					Self::check_claim(dest, signature, statement),
+				_ => return Err(InvalidTransaction::Call.into()),
+			}
+			.map_err(|error| {
+				let error = match error {
+					Error::InvalidSignature => ValidityError::InvalidSignature,
+					Error::SignerHasNoClaim => ValidityError::SignerHasNoClaim,
+					Error::BelowMinimumBalance => ValidityError::BelowMinimumBalance,
+					_ => ValidityError::InvalidStatement,
+				};
+				InvalidTransaction::Custom(error.into())
+			})?;
+
+			ValidTransaction::with_tag_prefix("Claims")
+				.priority(PRIORITY)
+				.and_provides(claimant)
+				.longevity(TransactionLongevity::max_value())
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The message a claimant signs to pay its allocation out to `dest`, attesting to
+		/// `statement`, which is empty for claimants without one.
+		///
+		/// It names the destination and the genesis hash, so a proof can't be replayed for
+		/// another destination or on another chain.
+		pub fn claim_message(dest: &T::AccountId, statement: &[u8]) -> Vec<u8> {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			let mut message = T::Prefix::get().to_vec();
+			message.extend(hex(&dest.encode()));
+			message.extend_from_slice(b" on chain 0x");
+			message.extend(hex(genesis_hash.as_ref()));
+			message.extend_from_slice(statement);
+			message
+		}
+
+		/// The claimant that signed a claim for `dest`, if it has an allocation `dest` can take
+		/// and attests to the statement it must.
+		pub(crate) fn check_claim(
+			dest: &T::AccountId,
+			signature: &ClaimSignature,
+			statement: &[u8],
+		) -> Result<Claimant, Error<T>> {
+			let claimant = signature
+				.signer(&Self::claim_message(dest, statement))
+				.ok_or(Error::<T>::InvalidSignature)?;
+			let amount = Claims::<T>::get(claimant).ok_or(Error::<T>::SignerHasNoClaim)?;
+			ensure!(
+				amount >= T::Currency::minimum_balance() ||
+					!T::Currency::total_balance(dest).is_zero(),
+				Error::<T>::BelowMinimumBalance
+			);
+			let attested = match Signing::<T>::get(claimant) {
+				Some(id) => Statements::<T>::get(id).map_or(false, |text| text[..] == *statement),
+				None => statement.is_empty(),
+			};
+			ensure!(attested, Error::<T>::InvalidStatement);
+			Ok(claimant)
+		}
+
+		fn insert_claim(
+			claimant: Claimant,
+			value: BalanceOf<T>,
+			vesting: Option<VestingOf<T>>,
+			statement: Option<StatementId>,
+		) {
+			let replaced = Claims::<T>::get(claimant).unwrap_or_default();
+			Total::<T>::mutate(|total| {
+				*total = total.saturating_sub(replaced).saturating_add(value)
+			});
+			Claims::<T>::insert(claimant, value);
+			Vesting::<T>::set(claimant, vesting);
+			Signing::<T>::set(claimant, statement);
+		}
+
+		fn process_claim(claimant: Claimant, dest: T::AccountId) -> DispatchResult {
+			let amount = Claims::<T>::get(claimant).ok_or(Error::<T>::SignerHasNoClaim)?;
+			let total = Total::<T>::get().checked_sub(&amount).ok_or(Error::<T>::PotUnderflow)?;
+			let vesting = Vesting::<T>::get(claimant);
+			if let Some((locked, per_block, starting_block)) = vesting {
+				T::VestingSchedule::can_add_vesting_schedule(
+					&dest,
+					locked,
+					per_block,
+					starting_block,
+				)
+				.map_err(|_| Error::<T>::CannotVest)?;
+			}
+
+			// The balance is deposited first, so the account exists to take the vesting lock.
+			let deposited = T::Currency::deposit_creating(&dest, amount);
+			ensure!(deposited.peek() == amount, Error::<T>::BelowMinimumBalance);
+			if let Some((locked, per_block, starting_block)) = vesting {
+				T::VestingSchedule::add_vesting_schedule(&dest, locked, per_block, starting_block)
+					.expect("the schedule can be added, as checked above; qed");
+			}
+
+			Total::<T>::put(total);
+			Claims::<T>::remove(claimant);
+			Vesting::<T>::remove(claimant);
+			Signing::<T>::remove(claimant);
+
+			Self::deposit_event(Event::Claimed { who: dest, claimant, amount });
+			Ok(())
+		}
+	}
+}
+
+/// Custom [`InvalidTransaction`](sp_runtime::transaction_validity::InvalidTransaction) codes
+/// returned when validating claims.
+#[repr(u8)]
+pub enum ValidityError {
+	/// The signature is not a valid signature of the claim message.
+	InvalidSignature = 0,
+	/// The key that signed the claim has nothing to claim.
+	SignerHasNoClaim = 1,
+	/// The statement is not the one the claimant must attest to.
+	InvalidStatement = 2,
+	/// The amount is below the existential deposit and the destination does not exist.
+	BelowMinimumBalance = 3,
+}
+
+impl From<ValidityError> for u8 {
+	fn from(err: ValidityError) -> Self {
+		err as u8
+	}
+}
@@ -0,0 +1,169 @@
+use crate as pallet_claims;
+use crate::{ClaimSignature, Claimant};
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild, WithdrawReasons},
+};
+use sp_core::{ecdsa, sr25519, Pair, H160, H256};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		Vesting: pallet_vesting,
+		Claims: pallet_claims,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ConstU32<50>;
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<10>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+parameter_types! {
+	pub UnvestedFundsAllowedWithdrawReasons: WithdrawReasons =
+		WithdrawReasons::except(WithdrawReasons::TRANSFER | WithdrawReasons::RESERVE);
+	pub Prefix: &'static [u8] = b"Pay to the test account 0x";
+}
+
+impl pallet_vesting::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type BlockNumberToBalance = ConvertInto;
+	type MinVestedTransfer = ConstU64<1>;
+	type WeightInfo = ();
+	type UnvestedFundsAllowedWithdrawReasons = UnvestedFundsAllowedWithdrawReasons;
+	const MAX_VESTING_SCHEDULES: u32 = 3;
+}
+
+impl pallet_claims::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type VestingSchedule = Vesting;
+	type Prefix = Prefix;
+	type MaxStatementLength = ConstU32<64>;
+	type WeightInfo = ();
+}
+
+/// The statement `charlie` must attest to.
+pub const STATEMENT: &[u8] = b"I accept the terms of the allocation.";
+
+/// Claims 100.
+pub fn alice() -> ecdsa::Pair {
+	ecdsa::Pair::from_seed(&[1u8; 32])
+}
+
+/// Claims 200, of which 150 vest by 10 per block from block 10.
+pub fn bob() -> sr25519::Pair {
+	sr25519::Pair::from_seed(&[2u8; 32])
+}
+
+/// Claims 300, attesting to [`STATEMENT`].
+pub fn charlie() -> ecdsa::Pair {
+	ecdsa::Pair::from_seed(&[3u8; 32])
+}
+
+/// Has no allocation.
+pub fn dave() -> ecdsa::Pair {
+	ecdsa::Pair::from_seed(&[4u8; 32])
+}
+
+/// The Ethereum address of `pair`, computed independently of the pallet.
+pub fn eth_address(pair: &ecdsa::Pair) -> Claimant {
+	let hash = [7u8; 32];
+	let signature = pair.sign_prehashed(&hash);
+	let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &hash).unwrap();
+	Claimant::Ethereum(H160::from_slice(&sp_io::hashing::keccak_256(&public)[12..]))
+}
+
+/// `personal_sign` the claim message for `dest` and `statement` with `pair`.
+pub fn sign_eth(pair: &ecdsa::Pair, dest: u64, statement: &[u8]) -> ClaimSignature {
+	let message = Claims::claim_message(&dest, statement);
+	let hash = eth_message_primitives::eth_message_hash(&message);
+	ClaimSignature::Ethereum(pair.sign_prehashed(&hash).0)
+}
+
+/// Sign the claim message for `dest` and `statement` with `pair`, wrapped in `<Bytes>` if
+/// `wrap`.
+pub fn sign_sr25519(
+	pair: &sr25519::Pair,
+	dest: u64,
+	statement: &[u8],
+	wrap: bool,
+) -> ClaimSignature {
+	let mut message = Claims::claim_message(&dest, statement);
+	if wrap {
+		message = [&b"<Bytes>"[..], &message, &b"</Bytes>"[..]].concat();
+	}
+	ClaimSignature::Sr25519 { signer: pair.public(), signature: pair.sign(&message) }
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_claims::GenesisConfig::<Test> {
+		claims: vec![
+			(eth_address(&alice()), 100, None, None),
+			(Claimant::Sr25519(bob().public()), 200, Some((150, 10, 10)), None),
+			(eth_address(&charlie()), 300, None, Some(0)),
+		],
+		statements: vec![(0, STATEMENT.to_vec())],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
@@ -0,0 +1,234 @@
+use crate::{mock::*, Call, Claimant, Error, Event, Total, ValidityError};
+use frame_support::{
+	assert_noop, assert_ok,
+	pallet_prelude::{InvalidTransaction, TransactionSource},
+	traits::{Currency, VestingSchedule},
+	unsigned::ValidateUnsigned,
+};
+use sp_core::{Pair, H256};
+use sp_runtime::{transaction_validity::TransactionValidityError, DispatchError};
+
+#[test]
+fn genesis_allocations_are_stored() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(Total::<Test>::get(), 600);
+		assert_eq!(crate::Claims::<Test>::get(eth_address(&alice())), Some(100));
+		let bob = Claimant::Sr25519(bob().public());
+		assert_eq!(crate::Vesting::<Test>::get(bob), Some((150, 10, 10)));
+		assert_eq!(crate::Signing::<Test>::get(eth_address(&charlie())), Some(0));
+	});
+}
+
+#[test]
+fn ethereum_claim_pays_out() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&alice(), 42, &[])));
+
+		assert_eq!(Balances::free_balance(42), 100);
+		assert_eq!(Total::<Test>::get(), 500);
+		assert_eq!(crate::Claims::<Test>::get(eth_address(&alice())), None);
+		System::assert_last_event(
+			Event::Claimed { who: 42, claimant: eth_address(&alice()), amount: 100 }.into(),
+		);
+		// An allocation is paid out once.
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&alice(), 42, &[])),
+			Error::<Test>::SignerHasNoClaim
+		);
+	});
+}
+
+#[test]
+fn sr25519_claim_vests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Claims::claim(RuntimeOrigin::none(), 42, sign_sr25519(&bob(), 42, &[], false)));
+
+		assert_eq!(Balances::free_balance(42), 200);
+		assert_eq!(<Vesting as VestingSchedule<u64>>::vesting_balance(&42), Some(150));
+		assert_eq!(crate::Vesting::<Test>::get(Claimant::Sr25519(bob().public())), None);
+	});
+}
+
+#[test]
+fn claim_is_signed_for_dest() {
+	new_test_ext().execute_with(|| {
+		// Recovers another address from a signature made for another destination.
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 2, sign_eth(&alice(), 1, &[])),
+			Error::<Test>::SignerHasNoClaim
+		);
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 2, sign_sr25519(&bob(), 1, &[], true)),
+			Error::<Test>::InvalidSignature
+		);
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::signed(1), 1, sign_eth(&alice(), 1, &[])),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn claim_is_signed_for_the_chain() {
+	new_test_ext().execute_with(|| {
+		let signature = sign_eth(&alice(), 1, &[]);
+		// Another chain, with another genesis hash.
+		frame_system::BlockHash::<Test>::insert(0, H256::repeat_byte(1));
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 1, signature),
+			Error::<Test>::SignerHasNoClaim
+		);
+	});
+}
+
+#[test]
+fn claims_below_the_existential_deposit_need_an_existing_dest() {
+	new_test_ext().execute_with(|| {
+		let claimant = eth_address(&dave());
+		assert_ok!(Claims::mint_claim(RuntimeOrigin::root(), claimant, 5, None, None));
+
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&dave(), 42, &[])),
+			Error::<Test>::BelowMinimumBalance
+		);
+		assert_eq!(
+			Claims::validate_unsigned(
+				TransactionSource::External,
+				&Call::claim { dest: 42, signature: sign_eth(&dave(), 42, &[]) },
+			),
+			Err(InvalidTransaction::Custom(ValidityError::BelowMinimumBalance.into()).into()),
+		);
+
+		Balances::make_free_balance_be(&42, 10);
+		assert_ok!(Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&dave(), 42, &[])));
+		assert_eq!(Balances::free_balance(42), 15);
+	});
+}
+
+#[test]
+fn attested_claims_require_the_statement() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&charlie(), 42, &[])),
+			Error::<Test>::InvalidStatement
+		);
+		let other = b"Something else.".to_vec();
+		assert_noop!(
+			Claims::claim_attest(
+				RuntimeOrigin::none(),
+				42,
+				sign_eth(&charlie(), 42, &other),
+				other.clone(),
+			),
+			Error::<Test>::InvalidStatement
+		);
+		// Claimants without a statement can't attest to one.
+		assert_noop!(
+			Claims::claim_attest(
+				RuntimeOrigin::none(),
+				42,
+				sign_eth(&alice(), 42, STATEMENT),
+				STATEMENT.to_vec(),
+			),
+			Error::<Test>::InvalidStatement
+		);
+
+		assert_ok!(Claims::claim_attest(
+			RuntimeOrigin::none(),
+			42,
+			sign_eth(&charlie(), 42, STATEMENT),
+			STATEMENT.to_vec(),
+		));
+		assert_eq!(Balances::free_balance(42), 300);
+		assert_eq!(crate::Signing::<Test>::get(eth_address(&charlie())), None);
+	});
+}
+
+#[test]
+fn validate_unsigned_checks_the_proof() {
+	new_test_ext().execute_with(|| {
+		let validate =
+			|call: Call<Test>| Claims::validate_unsigned(TransactionSource::External, &call);
+		let custom = |error: ValidityError| -> TransactionValidityError {
+			InvalidTransaction::Custom(error.into()).into()
+		};
+
+		assert_ok!(validate(Call::claim { dest: 1, signature: sign_eth(&alice(), 1, &[]) }));
+		assert_ok!(validate(Call::claim {
+			dest: 1,
+			signature: sign_sr25519(&bob(), 1, &[], true)
+		}));
+		assert_eq!(
+			validate(Call::claim { dest: 2, signature: sign_sr25519(&bob(), 1, &[], true) }),
+			Err(custom(ValidityError::InvalidSignature)),
+		);
+		assert_eq!(
+			validate(Call::claim { dest: 1, signature: sign_eth(&dave(), 1, &[]) }),
+			Err(custom(ValidityError::SignerHasNoClaim)),
+		);
+		assert_eq!(
+			validate(Call::claim { dest: 1, signature: sign_eth(&charlie(), 1, &[]) }),
+			Err(custom(ValidityError::InvalidStatement)),
+		);
+		assert_eq!(
+			validate(Call::set_statement { id: 0, statement: Default::default() }),
+			Err(InvalidTransaction::Call.into()),
+		);
+	});
+}
+
+#[test]
+fn root_mints_claims() {
+	new_test_ext().execute_with(|| {
+		let claimant = eth_address(&dave());
+		assert_noop!(
+			Claims::mint_claim(RuntimeOrigin::signed(1), claimant, 50, None, None),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Claims::mint_claim(RuntimeOrigin::root(), claimant, 50, None, Some(1)),
+			Error::<Test>::UnknownStatement
+		);
+
+		assert_ok!(Claims::mint_claim(RuntimeOrigin::root(), claimant, 50, None, None));
+		assert_eq!(Total::<Test>::get(), 650);
+		System::assert_last_event(Event::ClaimMinted { claimant, amount: 50 }.into());
+		// Minting again replaces the allocation.
+		assert_ok!(Claims::mint_claim(RuntimeOrigin::root(), claimant, 70, None, None));
+		assert_eq!(Total::<Test>::get(), 670);
+
+		assert_ok!(Claims::claim(RuntimeOrigin::none(), 42, sign_eth(&dave(), 42, &[])));
+		assert_eq!(Balances::free_balance(42), 70);
+	});
+}
+
+#[test]
+fn root_sets_statements() {
+	new_test_ext().execute_with(|| {
+		let text = b"New terms.".to_vec();
+		assert_noop!(
+			Claims::set_statement(RuntimeOrigin::signed(1), 0, text.clone().try_into().unwrap()),
+			DispatchError::BadOrigin
+		);
+		let statement = text.clone().try_into().unwrap();
+		assert_ok!(Claims::set_statement(RuntimeOrigin::root(), 0, statement));
+		System::assert_last_event(Event::StatementSet { id: 0 }.into());
+
+		// The old text no longer attests.
+		assert_noop!(
+			Claims::claim_attest(
+				RuntimeOrigin::none(),
+				42,
+				sign_eth(&charlie(), 42, STATEMENT),
+				STATEMENT.to_vec(),
+			),
+			Error::<Test>::InvalidStatement
+		);
+		assert_ok!(Claims::claim_attest(
+			RuntimeOrigin::none(),
+			42,
+			sign_eth(&charlie(), 42, &text),
+			text,
+		));
+	});
+}
@@ -0,0 +1,131 @@
+//! Weights for pallet_claims
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=pallet_claims` before relying on them.
+//!
+//! The database weight counts the storage accesses listed above each function. The proof size
+//! adds up, for every item read, its `MaxEncodedLen` in the runtime plus the trie proof budget the
+//! benchmarks use: 2475 bytes for a map entry and 495 bytes for a plain value. The execution time
+//! is given with its justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_claims.
+pub trait WeightInfo {
+	fn claim_ethereum() -> Weight;
+	fn claim_sr25519() -> Weight;
+	fn mint_claim() -> Weight;
+	fn set_statement() -> Weight;
+}
+
+/// Weights for pallet_claims using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_size: 44, added: 2519)
+	/// Storage: Claims Claims (r:1 w:1)
+	/// Proof: Claims Claims (max_size: 65, added: 2540)
+	/// Storage: Claims Signing (r:1 w:1)
+	/// Proof: Claims Signing (max_size: 53, added: 2528)
+	/// Storage: Claims Statements (r:1 w:0)
+	/// Proof: Claims Statements (max_size: 1038, added: 3513)
+	/// Storage: Claims Total (r:1 w:1)
+	/// Proof: Claims Total (max_size: 16, added: 511)
+	/// Storage: Claims Vesting (r:1 w:1)
+	/// Proof: Claims Vesting (max_size: 85, added: 2560)
+	/// Storage: Vesting Vesting (r:1 w:1)
+	/// Proof: Vesting Vesting (max_size: 1057, added: 3532)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_size: 1299, added: 3774)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Proof: Balances TotalIssuance (max_size: 16, added: 511)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a secp256k1 key recovery (about 50 µs), hashing a message of up to the maximum
+	/// statement length and adding a vesting schedule with its lock (about 40 µs, as benchmarked
+	/// for `pallet_vesting`), 100 µs.
+	fn claim_ethereum() -> Weight {
+		Weight::from_parts(100_000_000, 24_591)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_size: 44, added: 2519)
+	/// Storage: Claims Claims (r:1 w:1)
+	/// Proof: Claims Claims (max_size: 65, added: 2540)
+	/// Storage: Claims Signing (r:1 w:1)
+	/// Proof: Claims Signing (max_size: 53, added: 2528)
+	/// Storage: Claims Statements (r:1 w:0)
+	/// Proof: Claims Statements (max_size: 1038, added: 3513)
+	/// Storage: Claims Total (r:1 w:1)
+	/// Proof: Claims Total (max_size: 16, added: 511)
+	/// Storage: Claims Vesting (r:1 w:1)
+	/// Proof: Claims Vesting (max_size: 85, added: 2560)
+	/// Storage: Vesting Vesting (r:1 w:1)
+	/// Proof: Vesting Vesting (max_size: 1057, added: 3532)
+	/// Storage: Balances Locks (r:1 w:1)
+	/// Proof: Balances Locks (max_size: 1299, added: 3774)
+	/// Storage: Balances TotalIssuance (r:1 w:1)
+	/// Proof: Balances TotalIssuance (max_size: 16, added: 511)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: up to two sr25519 verifications, of the message wrapped in `<Bytes>` and raw
+	/// (about 45 µs each), and adding a vesting schedule with its lock (about 40 µs, as
+	/// benchmarked for `pallet_vesting`), 130 µs.
+	fn claim_sr25519() -> Weight {
+		Weight::from_parts(130_000_000, 24_591)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Claims Statements (r:1 w:0)
+	/// Proof: Claims Statements (max_size: 1038, added: 3513)
+	/// Storage: Claims Claims (r:1 w:1)
+	/// Proof: Claims Claims (max_size: 65, added: 2540)
+	/// Storage: Claims Total (r:1 w:1)
+	/// Proof: Claims Total (max_size: 16, added: 511)
+	/// Storage: Claims Vesting (r:0 w:1)
+	/// Proof: Claims Vesting (max_size: 85, added: 2560)
+	/// Storage: Claims Signing (r:0 w:1)
+	/// Proof: Claims Signing (max_size: 53, added: 2528)
+	/// Execution: one origin check and a few fixed-size reads and writes, 15 µs.
+	fn mint_claim() -> Weight {
+		Weight::from_parts(15_000_000, 6_564)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Claims Statements (r:0 w:1)
+	/// Proof: Claims Statements (max_size: 1038, added: 3513)
+	/// Execution: one origin check and one insert of up to the maximum statement length, 10 µs.
+	fn set_statement() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn claim_ethereum() -> Weight {
+		Weight::from_parts(100_000_000, 24_591)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn claim_sr25519() -> Weight {
+		Weight::from_parts(130_000_000, 24_591)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	fn mint_claim() -> Weight {
+		Weight::from_parts(15_000_000, 6_564)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	fn set_statement() -> Weight {
+		Weight::from_parts(10_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
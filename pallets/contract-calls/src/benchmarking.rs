@@ -0,0 +1,42 @@
+//! Benchmarking setup for pallet-contract-calls
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as ContractCalls;
+use frame_benchmarking::v2::*;
+use frame_support::traits::EnsureOrigin;
+
+const CALL: CallIndex = (0, 0);
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn allow_call() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, CALL);
+
+		assert!(AllowedCalls::<T>::contains_key(CALL));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn disallow_call() -> Result<(), BenchmarkError> {
+		let origin =
+			T::UpdateOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		AllowedCalls::<T>::insert(CALL, ());
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, CALL);
+
+		assert!(!AllowedCalls::<T>::contains_key(CALL));
+		Ok(())
+	}
+
+	impl_benchmark_test_suite!(ContractCalls, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,151 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Contract Calls Pallet
+//!
+//! Holds the allowlist of runtime calls that contracts may dispatch through `call_runtime`.
+//!
+//! - Calls are allowed by [`CallIndex`], the pallet index and call index they are encoded with.
+//!   Deployed contracts encode calls that way, so an allowed call must never move in
+//!   `construct_runtime!`.
+//! - [`Pallet::allow_call`] and [`Pallet::disallow_call`] change the allowlist; both require
+//!   `Config::UpdateOrigin`, so governance decides what contracts may do.
+//! - [`Pallet`] implements `Contains` for the runtime call. It only checks the outer call: the
+//!   runtime's `CallFilter` must still look into calls that dispatch other calls, like batches.
+//! - [`migration::v1::AllowCalls`] fills the allowlist of a chain that did not have the pallet at
+//!   genesis.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod migration;
+pub mod weights;
+pub use weights::*;
+
+use frame_support::traits::Contains;
+use parity_scale_codec::Encode;
+
+/// The pallet index and call index a runtime call is encoded with.
+pub type CallIndex = (u8, u8);
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	/// The in-code storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The origin allowed to change the allowlist.
+		type UpdateOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The calls contracts may dispatch.
+	#[pallet::storage]
+	pub type AllowedCalls<T> = StorageMap<_, Twox64Concat, CallIndex, (), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		/// The calls contracts may dispatch from genesis.
+		pub allowed_calls: Vec<CallIndex>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { allowed_calls: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for call in &self.allowed_calls {
+				AllowedCalls::<T>::insert(call, ());
+			}
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Contracts may now dispatch `call`.
+		CallAllowed { call: CallIndex },
+		/// Contracts may no longer dispatch `call`.
+		CallDisallowed { call: CallIndex },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is already allowed.
+		AlreadyAllowed,
+		/// The call is not allowed.
+		NotAllowed,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Let contracts dispatch `call`.
+		///
+		/// The origin must be `Config::UpdateOrigin`.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::allow_call())]
+		pub fn allow_call(origin: OriginFor<T>, call: CallIndex) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(!AllowedCalls::<T>::contains_key(call), Error::<T>::AlreadyAllowed);
+
+			AllowedCalls::<T>::insert(call, ());
+			Self::deposit_event(Event::CallAllowed { call });
+			Ok(())
+		}
+
+		/// Stop contracts from dispatching `call`.
+		///
+		/// The origin must be `Config::UpdateOrigin`.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::disallow_call())]
+		pub fn disallow_call(origin: OriginFor<T>, call: CallIndex) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(AllowedCalls::<T>::contains_key(call), Error::<T>::NotAllowed);
+
+			AllowedCalls::<T>::remove(call);
+			Self::deposit_event(Event::CallDisallowed { call });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// The [`CallIndex`] of an encoded runtime call.
+	pub fn call_index(call: &impl Encode) -> Option<CallIndex> {
+		call.using_encoded(|encoded| match encoded {
+			[pallet, call, ..] => Some((*pallet, *call)),
+			_ => None,
+		})
+	}
+}
+
+impl<T: Config> Contains<<T as frame_system::Config>::RuntimeCall> for Pallet<T> {
+	fn contains(call: &<T as frame_system::Config>::RuntimeCall) -> bool {
+		Self::call_index(call).map_or(false, AllowedCalls::<T>::contains_key)
+	}
+}
@@ -0,0 +1,32 @@
+//! Storage migrations of the contract calls pallet.
+
+/// Version 1: the allowlist is filled.
+pub mod v1 {
+	use crate::{AllowedCalls, CallIndex, Config, Pallet};
+	use frame_support::{
+		traits::{Get, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		weights::Weight,
+	};
+	use sp_std::{marker::PhantomData, vec::Vec};
+
+	/// Allow `Calls` on a chain that added the pallet after genesis, whose allowlist is empty.
+	///
+	/// Does nothing once the pallet is at version 1, so it never undoes a later
+	/// [`Pallet::disallow_call`].
+	pub struct AllowCalls<T, Calls>(PhantomData<(T, Calls)>);
+
+	impl<T: Config, Calls: Get<Vec<CallIndex>>> OnRuntimeUpgrade for AllowCalls<T, Calls> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() >= 1 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let calls = Calls::get();
+			for call in &calls {
+				AllowedCalls::<T>::insert(call, ());
+			}
+			StorageVersion::new(1).put::<Pallet<T>>();
+			T::DbWeight::get().reads_writes(1, calls.len() as u64 + 1)
+		}
+	}
+}
@@ -0,0 +1,74 @@
+use crate::{mock::*, migration, AllowedCalls, Error, Event};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{Contains, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+};
+use sp_runtime::traits::BadOrigin;
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![1, 2, 3] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![1, 2, 3] })
+}
+
+#[test]
+fn contains_the_calls_allowed_at_genesis() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ContractCalls::call_index(&remark()), Some(REMARK));
+		assert_eq!(ContractCalls::call_index(&remark_with_event()), Some(REMARK_WITH_EVENT));
+		assert!(ContractCalls::contains(&remark()));
+		assert!(!ContractCalls::contains(&remark_with_event()));
+		assert_eq!(ContractCalls::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn update_origin_changes_the_allowlist() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ContractCalls::allow_call(RuntimeOrigin::signed(1), REMARK_WITH_EVENT),
+			BadOrigin
+		);
+		assert_ok!(ContractCalls::allow_call(RuntimeOrigin::root(), REMARK_WITH_EVENT));
+		System::assert_last_event(Event::CallAllowed { call: REMARK_WITH_EVENT }.into());
+		assert!(ContractCalls::contains(&remark_with_event()));
+		assert_noop!(
+			ContractCalls::allow_call(RuntimeOrigin::root(), REMARK_WITH_EVENT),
+			Error::<Test>::AlreadyAllowed
+		);
+
+		assert_noop!(ContractCalls::disallow_call(RuntimeOrigin::signed(1), REMARK), BadOrigin);
+		assert_ok!(ContractCalls::disallow_call(RuntimeOrigin::root(), REMARK));
+		System::assert_last_event(Event::CallDisallowed { call: REMARK }.into());
+		assert!(!ContractCalls::contains(&remark()));
+		assert_noop!(
+			ContractCalls::disallow_call(RuntimeOrigin::root(), REMARK),
+			Error::<Test>::NotAllowed
+		);
+	});
+}
+
+#[test]
+fn migration_fills_the_allowlist_once() {
+	new_test_ext().execute_with(|| {
+		// A chain that added the pallet after genesis.
+		let _ = AllowedCalls::<Test>::clear(u32::MAX, None);
+		StorageVersion::new(0).put::<ContractCalls>();
+
+		frame_support::parameter_types! {
+			pub Calls: Vec<(u8, u8)> = vec![REMARK, REMARK_WITH_EVENT];
+		}
+		type AllowCalls = migration::v1::AllowCalls<Test, Calls>;
+
+		AllowCalls::on_runtime_upgrade();
+		assert!(ContractCalls::contains(&remark()));
+		assert!(ContractCalls::contains(&remark_with_event()));
+		assert_eq!(ContractCalls::on_chain_storage_version(), 1);
+
+		assert_ok!(ContractCalls::disallow_call(RuntimeOrigin::root(), REMARK));
+		AllowCalls::on_runtime_upgrade();
+		assert!(!ContractCalls::contains(&remark()));
+	});
+}
@@ -0,0 +1,57 @@
+//! Weights for pallet_contract_calls
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=pallet_contract_calls` before relying on them.
+//!
+//! The database weight counts the storage accesses listed above each function. The proof size
+//! adds up, for every item read, its `MaxEncodedLen` in the runtime plus the trie proof budget the
+//! benchmarks use: 2475 bytes for a map entry and 495 bytes for a plain value. The execution time
+//! is given with its justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_contract_calls.
+pub trait WeightInfo {
+	fn allow_call() -> Weight;
+	fn disallow_call() -> Weight;
+}
+
+/// Weights for pallet_contract_calls using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: ContractCalls AllowedCalls (r:1 w:1)
+	/// Proof: ContractCalls AllowedCalls (max_size: 10, added: 2485)
+	/// Execution: one origin check and one insert, 10 µs.
+	fn allow_call() -> Weight {
+		Weight::from_parts(10_000_000, 2_485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: ContractCalls AllowedCalls (r:1 w:1)
+	/// Proof: ContractCalls AllowedCalls (max_size: 10, added: 2485)
+	/// Execution: one origin check and one removal, 10 µs.
+	fn disallow_call() -> Weight {
+		Weight::from_parts(10_000_000, 2_485)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn allow_call() -> Weight {
+		Weight::from_parts(10_000_000, 2_485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	fn disallow_call() -> Weight {
+		Weight::from_parts(10_000_000, 2_485)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+}
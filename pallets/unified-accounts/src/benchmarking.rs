@@ -0,0 +1,44 @@
+//! Benchmarking setup for pallet-unified-accounts
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+
+#[allow(unused)]
+use crate::Pallet as UnifiedAccounts;
+use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
+use sp_core::crypto::KeyTypeId;
+
+/// Sign the binding message of `who` with a fresh key from the keystore, as `personal_sign`
+/// would.
+fn sign_binding<T: Config>(who: &T::AccountId) -> (H160, EvmSignature) {
+	const KEY_TYPE: KeyTypeId = KeyTypeId(*b"unif");
+
+	let message = Pallet::<T>::binding_message(who);
+	let hash = eth_message_primitives::eth_message_hash(&message);
+
+	let public = sp_io::crypto::ecdsa_generate(KEY_TYPE, None);
+	let signature = sp_io::crypto::ecdsa_sign_prehashed(KEY_TYPE, &public, &hash)
+		.expect("the key was just generated; qed")
+		.0;
+	let evm_address = eth_signer(&message, &signature)
+		.expect("the signature was just made; qed");
+	(evm_address, signature)
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn claim_evm_address() {
+		let caller: T::AccountId = whitelisted_caller();
+		let (evm_address, signature) = sign_binding::<T>(&caller);
+
+		#[extrinsic_call]
+		_(RawOrigin::Signed(caller.clone()), evm_address, signature);
+
+		assert_eq!(UnifiedAccounts::<T>::evm_address(&caller), Some(evm_address));
+	}
+
+	impl_benchmark_test_suite!(UnifiedAccounts, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,302 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! # Unified Accounts Pallet
+//!
+//! Binds Ethereum (H160) addresses to native accounts, so that an Ethereum wallet controls the
+//! same account as the Substrate wallet it is bound to.
+//!
+//! - [`Pallet::claim_evm_address`] binds the caller to an H160. The proof is an EIP-191
+//!   (`personal_sign`) signature of [`Pallet::binding_message`] made with the key of the H160.
+//!   The native balance the H160's default account already holds is moved to the caller, whose
+//!   nonce is raised to the default account's so its Ethereum transactions can't be replayed.
+//!   Bindings are permanent.
+//! - [`Pallet::native_account`] maps an H160 to the account bound to it, or to its default
+//!   account given by `Config::DefaultAccount`. The runtime uses it as the EVM address mapping.
+//! - [`UnifiedLookup`] resolves `MultiAddress::Address20` through the bindings, so extrinsics can
+//!   target H160 addresses.
+//! - [`PrevalidateBinding`] checks the proof of a claim when it is validated, so claims with a
+//!   bad proof never enter the transaction pool.
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+use eth_message_primitives::{eth_signer, hex};
+use frame_support::traits::IsSubType;
+use parity_scale_codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::{
+	traits::{DispatchInfoOf, LookupError, SignedExtension, StaticLookup},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+	MultiAddress,
+};
+use sp_std::{marker::PhantomData, prelude::*};
+
+/// A `personal_sign` signature: `r`, `s` and the recovery id `v` (0, 1, 27 or 28).
+pub type EvmSignature = eth_message_primitives::EthSignature;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement},
+	};
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Convert, Zero};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// Because this pallet emits events, it depends on the runtime's definition of an event.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+
+		/// The native currency, moved from the default account of an H160 when it is claimed.
+		type Currency: Currency<Self::AccountId>;
+
+		/// The account an H160 maps to while it is not bound.
+		type DefaultAccount: Convert<H160, Self::AccountId>;
+
+		/// Type representing the weight of this pallet
+		type WeightInfo: WeightInfo;
+	}
+
+	/// The account each bound H160 maps to.
+	#[pallet::storage]
+	pub type EvmToNative<T: Config> =
+		StorageMap<_, Blake2_128Concat, H160, T::AccountId, OptionQuery>;
+
+	/// The H160 each bound account maps to.
+	#[pallet::storage]
+	pub type NativeToEvm<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, H160, OptionQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// `account` was bound to `evm_address`.
+		AccountBound { account: T::AccountId, evm_address: H160 },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The signature was not made with the key of the H160, or not for the caller.
+		InvalidSignature,
+		/// The caller is already bound to an H160.
+		AlreadyBound,
+		/// The H160 is already bound to an account.
+		EvmAddressAlreadyBound,
+		/// The default account of the H160 still has state that can't be moved, like reserved
+		/// or locked balance.
+		EvmAccountInUse,
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Bind the caller to `evm_address`.
+		///
+		/// `signature` is the `personal_sign` signature of [`Pallet::binding_message`] for the
+		/// caller, made with the key of `evm_address`. The free balance of the default account
+		/// of `evm_address` is moved to the caller; the claim fails if anything else is left in
+		/// that account.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::claim_evm_address())]
+		pub fn claim_evm_address(
+			origin: OriginFor<T>,
+			evm_address: H160,
+			signature: EvmSignature,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::check_claim(&who, &evm_address, &signature)?;
+
+			let default = T::DefaultAccount::convert(evm_address);
+			// The EVM checks transactions of `evm_address` against the nonce of the account it
+			// maps to, so the caller's nonce must not be lower than the one of the default
+			// account, or the transactions the default account executed could be replayed.
+			let nonce = frame_system::Pallet::<T>::account_nonce(&default);
+			let balance = T::Currency::free_balance(&default);
+			if !balance.is_zero() {
+				T::Currency::transfer(&default, &who, balance, ExistenceRequirement::AllowDeath)?;
+			}
+			ensure!(
+				!frame_system::Pallet::<T>::account_exists(&default),
+				Error::<T>::EvmAccountInUse
+			);
+
+			frame_system::Account::<T>::mutate(&who, |account| {
+				account.nonce = account.nonce.max(nonce)
+			});
+			EvmToNative::<T>::insert(evm_address, &who);
+			NativeToEvm::<T>::insert(&who, evm_address);
+
+			Self::deposit_event(Event::AccountBound { account: who, evm_address });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// The account `evm_address` maps to: the one bound to it, or its default account.
+		pub fn native_account(evm_address: &H160) -> T::AccountId {
+			EvmToNative::<T>::get(evm_address)
+				.unwrap_or_else(|| T::DefaultAccount::convert(*evm_address))
+		}
+
+		/// The H160 bound to `who`, if any.
+		pub fn evm_address(who: &T::AccountId) -> Option<H160> {
+			NativeToEvm::<T>::get(who)
+		}
+
+		/// The message the key of an H160 signs to bind it to `who`.
+		///
+		/// It names the account and the genesis hash, so a proof can't be replayed for another
+		/// account or on another chain.
+		pub fn binding_message(who: &T::AccountId) -> Vec<u8> {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			let mut message = b"Bind to NativeX account 0x".to_vec();
+			message.extend(hex(&who.encode()));
+			message.extend_from_slice(b" on chain 0x");
+			message.extend(hex(genesis_hash.as_ref()));
+			message
+		}
+
+		/// Check that `who` may bind `evm_address` with `signature`.
+		pub(crate) fn check_claim(
+			who: &T::AccountId,
+			evm_address: &H160,
+			signature: &EvmSignature,
+		) -> Result<(), Error<T>> {
+			ensure!(!NativeToEvm::<T>::contains_key(who), Error::<T>::AlreadyBound);
+			ensure!(
+				!EvmToNative::<T>::contains_key(evm_address),
+				Error::<T>::EvmAddressAlreadyBound
+			);
+			let signer = eth_signer(&Self::binding_message(who), signature);
+			ensure!(signer == Some(*evm_address), Error::<T>::InvalidSignature);
+			Ok(())
+		}
+	}
+}
+
+/// A [`StaticLookup`] that resolves `MultiAddress::Address20` with [`Pallet::native_account`]
+/// and every other address with `Inner`.
+pub struct UnifiedLookup<T, Inner>(PhantomData<(T, Inner)>);
+
+impl<T, Inner, AccountIndex> StaticLookup for UnifiedLookup<T, Inner>
+where
+	T: Config,
+	Inner: StaticLookup<Source = MultiAddress<T::AccountId, AccountIndex>, Target = T::AccountId>,
+{
+	type Source = Inner::Source;
+	type Target = T::AccountId;
+
+	fn lookup(address: Self::Source) -> Result<Self::Target, LookupError> {
+		match address {
+			MultiAddress::Address20(evm_address) =>
+				Ok(Pallet::<T>::native_account(&H160(evm_address))),
+			address => Inner::lookup(address),
+		}
+	}
+
+	fn unlookup(account: Self::Target) -> Self::Source {
+		Inner::unlookup(account)
+	}
+}
+
+/// Custom [`InvalidTransaction`] codes returned by [`PrevalidateBinding`].
+#[repr(u8)]
+pub enum ValidityError {
+	/// The signer is already bound to an H160.
+	AlreadyBound = 0,
+	/// The H160 is already bound to an account.
+	EvmAddressAlreadyBound = 1,
+}
+
+impl From<ValidityError> for u8 {
+	fn from(err: ValidityError) -> Self {
+		err as u8
+	}
+}
+
+/// Validates the proof of a [`Call::claim_evm_address`] before it enters the transaction pool.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct PrevalidateBinding<T>(PhantomData<T>);
+
+impl<T> PrevalidateBinding<T> {
+	/// Create the signed extension.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+}
+
+impl<T> Default for PrevalidateBinding<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T> sp_std::fmt::Debug for PrevalidateBinding<T> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "PrevalidateBinding")
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for PrevalidateBinding<T>
+where
+	<T as frame_system::Config>::RuntimeCall: IsSubType<Call<T>>,
+{
+	type AccountId = T::AccountId;
+	type Call = <T as frame_system::Config>::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+	const IDENTIFIER: &'static str = "PrevalidateBinding";
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		_info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		let (evm_address, signature) = match call.is_sub_type() {
+			Some(Call::claim_evm_address { evm_address, signature }) => (evm_address, signature),
+			_ => return Ok(ValidTransaction::default()),
+		};
+
+		Pallet::<T>::check_claim(who, evm_address, signature).map_err(|error| match error {
+			Error::AlreadyBound => InvalidTransaction::Custom(ValidityError::AlreadyBound.into()),
+			Error::EvmAddressAlreadyBound =>
+				InvalidTransaction::Custom(ValidityError::EvmAddressAlreadyBound.into()),
+			_ => InvalidTransaction::BadProof,
+		})?;
+
+		ValidTransaction::with_tag_prefix("UnifiedAccounts").and_provides(evm_address).build()
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		self.validate(who, call, info, len).map(|_| ())
+	}
+}
@@ -0,0 +1,110 @@
+use crate as pallet_unified_accounts;
+use frame_support::traits::{ConstU16, ConstU32, ConstU64};
+use sp_core::{ecdsa, Pair, H160, H256};
+use sp_keystore::{testing::MemoryKeystore, KeystoreExt};
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, Convert, IdentityLookup},
+};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system,
+		Balances: pallet_balances,
+		UnifiedAccounts: pallet_unified_accounts,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Index = u64;
+	type BlockNumber = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Header = Header;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = pallet_balances::AccountData<u64>;
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+	type FreezeIdentifier = ();
+	type MaxFreezes = ();
+	type HoldIdentifier = ();
+	type MaxHolds = ();
+}
+
+/// Maps an unbound H160 to the account made of its last eight bytes.
+pub struct TruncatedAccount;
+impl Convert<H160, u64> for TruncatedAccount {
+	fn convert(evm_address: H160) -> u64 {
+		let mut bytes = [0u8; 8];
+		bytes.copy_from_slice(&evm_address[12..]);
+		u64::from_be_bytes(bytes)
+	}
+}
+
+impl pallet_unified_accounts::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DefaultAccount = TruncatedAccount;
+	type WeightInfo = ();
+}
+
+/// The Ethereum address of `pair`, computed independently of the pallet's binding message.
+pub fn eth_address(pair: &ecdsa::Pair) -> H160 {
+	let hash = [7u8; 32];
+	let signature = pair.sign_prehashed(&hash);
+	let public = sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &hash).unwrap();
+	H160::from_slice(&sp_io::hashing::keccak_256(&public)[12..])
+}
+
+/// `personal_sign` the binding message of `who` with `pair`.
+pub fn sign_binding(pair: &ecdsa::Pair, who: u64) -> [u8; 65] {
+	let message = UnifiedAccounts::binding_message(&who);
+	pair.sign_prehashed(&eth_message_primitives::eth_message_hash(&message)).0
+}
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> { balances: vec![(1, 100), (2, 100)] }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.register_extension(KeystoreExt::new(MemoryKeystore::new()));
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
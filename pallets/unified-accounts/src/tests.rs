@@ -0,0 +1,247 @@
+use crate::{mock::*, Call, Error, Event, PrevalidateBinding, UnifiedLookup, ValidityError};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchInfo};
+use sp_core::{ecdsa, Pair, H160};
+use sp_runtime::{
+	traits::{Convert, IdentityLookup, SignedExtension, StaticLookup},
+	transaction_validity::InvalidTransaction,
+	MultiAddress,
+};
+
+fn alice() -> ecdsa::Pair {
+	ecdsa::Pair::from_seed(&[1u8; 32])
+}
+
+fn bob() -> ecdsa::Pair {
+	ecdsa::Pair::from_seed(&[2u8; 32])
+}
+
+#[test]
+fn claim_binds_both_ways() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		let default = TruncatedAccount::convert(evm_address);
+		assert_eq!(UnifiedAccounts::native_account(&evm_address), default);
+
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+
+		assert_eq!(UnifiedAccounts::native_account(&evm_address), 1);
+		assert_eq!(UnifiedAccounts::evm_address(&1), Some(evm_address));
+		System::assert_last_event(Event::AccountBound { account: 1, evm_address }.into());
+	});
+}
+
+#[test]
+fn claim_requires_proof_for_caller() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		// Signed for another account.
+		assert_noop!(
+			UnifiedAccounts::claim_evm_address(
+				RuntimeOrigin::signed(1),
+				evm_address,
+				sign_binding(&alice(), 2),
+			),
+			Error::<Test>::InvalidSignature
+		);
+		// Signed by another key.
+		assert_noop!(
+			UnifiedAccounts::claim_evm_address(
+				RuntimeOrigin::signed(1),
+				evm_address,
+				sign_binding(&bob(), 1),
+			),
+			Error::<Test>::InvalidSignature
+		);
+	});
+}
+
+#[test]
+fn bindings_are_exclusive() {
+	new_test_ext().execute_with(|| {
+		let alice_address = eth_address(&alice());
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			alice_address,
+			sign_binding(&alice(), 1),
+		));
+
+		assert_noop!(
+			UnifiedAccounts::claim_evm_address(
+				RuntimeOrigin::signed(1),
+				eth_address(&bob()),
+				sign_binding(&bob(), 1),
+			),
+			Error::<Test>::AlreadyBound
+		);
+		assert_noop!(
+			UnifiedAccounts::claim_evm_address(
+				RuntimeOrigin::signed(2),
+				alice_address,
+				sign_binding(&alice(), 2),
+			),
+			Error::<Test>::EvmAddressAlreadyBound
+		);
+	});
+}
+
+#[test]
+fn claim_moves_default_account_balance() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		let default = TruncatedAccount::convert(evm_address);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(2), default, 40));
+
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+
+		assert_eq!(Balances::free_balance(1), 140);
+		assert!(!System::account_exists(&default));
+	});
+}
+
+#[test]
+fn claim_keeps_the_nonce_of_executed_transactions() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		let default = TruncatedAccount::convert(evm_address);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(2), default, 40));
+		// The default account executed three Ethereum transactions, the caller one extrinsic.
+		(0..3).for_each(|_| System::inc_account_nonce(default));
+		System::inc_account_nonce(1);
+
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+
+		// Re-submitting the transaction with nonce 2 is rejected by the EVM as stale.
+		assert_eq!(System::account_nonce(UnifiedAccounts::native_account(&evm_address)), 3);
+	});
+}
+
+#[test]
+fn claim_keeps_a_higher_caller_nonce() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		let default = TruncatedAccount::convert(evm_address);
+		System::inc_account_nonce(default);
+		(0..5).for_each(|_| System::inc_account_nonce(1));
+
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+
+		assert_eq!(System::account_nonce(1), 5);
+	});
+}
+
+#[test]
+fn claim_fails_if_default_account_keeps_state() {
+	new_test_ext().execute_with(|| {
+		let evm_address = eth_address(&alice());
+		let default = TruncatedAccount::convert(evm_address);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(2), default, 40));
+		// Something other than its balance keeps the account alive.
+		System::inc_providers(&default);
+
+		assert_noop!(
+			UnifiedAccounts::claim_evm_address(
+				RuntimeOrigin::signed(1),
+				evm_address,
+				sign_binding(&alice(), 1),
+			),
+			Error::<Test>::EvmAccountInUse
+		);
+	});
+}
+
+#[test]
+fn lookup_resolves_evm_addresses() {
+	new_test_ext().execute_with(|| {
+		type Lookup = UnifiedLookup<Test, MultiLookup>;
+		let evm_address = eth_address(&alice());
+
+		assert_eq!(
+			Lookup::lookup(MultiAddress::Address20(evm_address.0)),
+			Ok(TruncatedAccount::convert(evm_address))
+		);
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+		assert_eq!(Lookup::lookup(MultiAddress::Address20(evm_address.0)), Ok(1));
+		assert_eq!(Lookup::lookup(MultiAddress::Id(2)), Ok(2));
+		assert!(Lookup::lookup(MultiAddress::Index(2)).is_err());
+	});
+}
+
+#[test]
+fn prevalidation_rejects_invalid_claims() {
+	new_test_ext().execute_with(|| {
+		let validate = |who: u64, evm_address: H160, signature: [u8; 65]| {
+			let call = RuntimeCall::UnifiedAccounts(Call::claim_evm_address {
+				evm_address,
+				signature,
+			});
+			PrevalidateBinding::<Test>::new().validate(&who, &call, &DispatchInfo::default(), 0)
+		};
+		let evm_address = eth_address(&alice());
+
+		assert_eq!(
+			validate(1, evm_address, sign_binding(&bob(), 1)),
+			Err(InvalidTransaction::BadProof.into())
+		);
+		assert_ok!(validate(1, evm_address, sign_binding(&alice(), 1)));
+
+		assert_ok!(UnifiedAccounts::claim_evm_address(
+			RuntimeOrigin::signed(1),
+			evm_address,
+			sign_binding(&alice(), 1),
+		));
+		assert_eq!(
+			validate(2, evm_address, sign_binding(&alice(), 2)),
+			Err(InvalidTransaction::Custom(ValidityError::EvmAddressAlreadyBound.into()).into())
+		);
+	});
+}
+
+#[test]
+fn binding_message_names_account_and_chain() {
+	new_test_ext().execute_with(|| {
+		// The mock genesis hash is `[69; 32]`.
+		let message = UnifiedAccounts::binding_message(&0x0102);
+		let expected = [
+			&b"Bind to NativeX account 0x0201000000000000 on chain 0x"[..],
+			&b"45".repeat(32)[..],
+		]
+		.concat();
+		assert_eq!(message, expected);
+	});
+}
+
+/// Resolves `MultiAddress::Id` only, standing in for `pallet_indices`.
+struct MultiLookup;
+impl StaticLookup for MultiLookup {
+	type Source = MultiAddress<u64, u32>;
+	type Target = u64;
+	fn lookup(address: Self::Source) -> Result<u64, sp_runtime::traits::LookupError> {
+		match address {
+			MultiAddress::Id(who) => IdentityLookup::<u64>::lookup(who),
+			_ => Err(sp_runtime::traits::LookupError),
+		}
+	}
+	fn unlookup(who: u64) -> Self::Source {
+		MultiAddress::Id(who)
+	}
+}
@@ -0,0 +1,50 @@
+//! Weights for pallet_unified_accounts
+//!
+//! These weights are estimated by hand, not benchmarked. Replace them with the output of
+//! `nativex benchmark pallet --pallet=pallet_unified_accounts` before relying on them.
+//!
+//! The database weight counts the storage accesses listed above each function. The proof size
+//! adds up, for every item read, its `MaxEncodedLen` in the runtime plus the trie proof budget the
+//! benchmarks use: 2475 bytes for a map entry and 495 bytes for a plain value. The execution time
+//! is given with its justification next to each function.
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for pallet_unified_accounts.
+pub trait WeightInfo {
+	fn claim_evm_address() -> Weight;
+}
+
+/// Weights for pallet_unified_accounts using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage: UnifiedAccounts NativeToEvm (r:1 w:1)
+	/// Proof: UnifiedAccounts NativeToEvm (max_size: 68, added: 2543)
+	/// Storage: UnifiedAccounts EvmToNative (r:1 w:1)
+	/// Proof: UnifiedAccounts EvmToNative (max_size: 68, added: 2543)
+	/// Storage: System BlockHash (r:1 w:0)
+	/// Proof: System BlockHash (max_size: 44, added: 2519)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_size: 128, added: 2603)
+	/// Execution: a secp256k1 key recovery (about 50 µs) and a balance transfer (about 40 µs, as
+	/// benchmarked for `pallet_balances`), 90 µs.
+	fn claim_evm_address() -> Weight {
+		Weight::from_parts(90_000_000, 12_811)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn claim_evm_address() -> Weight {
+		Weight::from_parts(90_000_000, 12_811)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+}
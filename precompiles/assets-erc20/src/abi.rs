@@ -0,0 +1,73 @@
+//! The subset of the Solidity ABI the ERC-20 interface needs.
+
+use fp_evm::{ExitRevert, PrecompileFailure};
+use sp_core::{H160, U256};
+use sp_std::vec::Vec;
+
+/// Selector of `Error(string)`, the revert reason Solidity decodes.
+const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Revert with `reason`, encoded as `Error(string)`.
+pub fn revert(reason: &str) -> PrecompileFailure {
+	let mut output = ERROR_SELECTOR.to_vec();
+	output.extend(encode_bytes(reason.as_bytes()));
+	PrecompileFailure::Revert { exit_status: ExitRevert::Reverted, output }
+}
+
+/// Call input: a selector followed by 32-byte words.
+pub struct Input<'a>(&'a [u8]);
+
+impl<'a> Input<'a> {
+	/// Wrap the call input.
+	pub fn new(input: &'a [u8]) -> Self {
+		Self(input)
+	}
+
+	/// The function selector.
+	pub fn selector(&self) -> Result<[u8; 4], PrecompileFailure> {
+		self.0
+			.get(..4)
+			.and_then(|selector| selector.try_into().ok())
+			.ok_or_else(|| revert("input too short"))
+	}
+
+	fn word(&self, index: usize) -> Result<&'a [u8], PrecompileFailure> {
+		let start = 4 + index * 32;
+		self.0.get(start..start + 32).ok_or_else(|| revert("input too short"))
+	}
+
+	/// The `address` argument at `index`.
+	pub fn address(&self, index: usize) -> Result<H160, PrecompileFailure> {
+		let word = self.word(index)?;
+		if word[..12].iter().any(|byte| *byte != 0) {
+			return Err(revert("invalid address"))
+		}
+		Ok(H160::from_slice(&word[12..]))
+	}
+
+	/// The `uint256` argument at `index`.
+	pub fn uint(&self, index: usize) -> Result<U256, PrecompileFailure> {
+		Ok(U256::from_big_endian(self.word(index)?))
+	}
+}
+
+/// Encode a `uint256`.
+pub fn encode_uint(value: impl Into<U256>) -> Vec<u8> {
+	let mut word = [0u8; 32];
+	value.into().to_big_endian(&mut word);
+	word.to_vec()
+}
+
+/// Encode a `bool`.
+pub fn encode_bool(value: bool) -> Vec<u8> {
+	encode_uint(u8::from(value))
+}
+
+/// Encode a single dynamic `bytes` or `string` return value.
+pub fn encode_bytes(value: &[u8]) -> Vec<u8> {
+	let mut output = encode_uint(32u8);
+	output.extend(encode_uint(value.len()));
+	output.extend_from_slice(value);
+	output.resize(output.len() + (32 - value.len() % 32) % 32, 0);
+	output
+}
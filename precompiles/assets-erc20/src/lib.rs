@@ -0,0 +1,335 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! EVM precompile exposing `pallet_assets` as ERC-20 tokens.
+//!
+//! Every asset gets its own precompile at `0xffffffff` followed by its id as a big-endian
+//! `u128`, so Solidity tooling can treat it as a regular token contract. Transfers and approvals
+//! act as the EVM caller, mapped to a substrate account through `pallet_evm::AddressMapping`.
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+pub mod abi;
+
+use abi::{encode_bool, encode_bytes, encode_uint, revert, Input};
+use fp_evm::{ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult};
+use frame_support::{
+	dispatch::DispatchResult,
+	storage::with_storage_layer,
+	traits::{
+		tokens::fungibles::{approvals, metadata, Inspect},
+		Get,
+	},
+	weights::Weight,
+};
+use frame_system::RawOrigin;
+use pallet_assets::{AssetsCallback, WeightInfo};
+use pallet_evm::{AddressMapping, GasWeightMapping};
+use sp_core::{H160, H256, U256};
+use sp_runtime::traits::{StaticLookup, Zero};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+
+/// The first four bytes of every asset precompile address.
+pub const ASSET_PRECOMPILE_PREFIX: [u8; 4] = [0xff; 4];
+
+/// The code stored at asset precompile addresses.
+///
+/// Solidity checks that a contract has code before calling it. This code only reverts; calls
+/// are answered by the precompile before it is ever executed.
+pub const ASSET_PRECOMPILE_CODE: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+/// Selectors of the ERC-20 functions.
+pub mod selector {
+	pub const TOTAL_SUPPLY: [u8; 4] = [0x18, 0x16, 0x0d, 0xdd];
+	pub const BALANCE_OF: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+	pub const ALLOWANCE: [u8; 4] = [0xdd, 0x62, 0xed, 0x3e];
+	pub const TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+	pub const APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+	pub const TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+	pub const NAME: [u8; 4] = [0x06, 0xfd, 0xde, 0x03];
+	pub const SYMBOL: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+	pub const DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+}
+
+/// `keccak256("Transfer(address,address,uint256)")`.
+pub const TRANSFER_TOPIC: [u8; 32] =
+	hex_literal(b"ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef");
+/// `keccak256("Approval(address,address,uint256)")`.
+pub const APPROVAL_TOPIC: [u8; 32] =
+	hex_literal(b"8c5be1e5ebec7d5bd14f71427d1e84f3dd0314c0f7b2291e5b200ac8c7c3b925");
+
+const fn hex_literal(hex: &[u8; 64]) -> [u8; 32] {
+	const fn nibble(c: u8) -> u8 {
+		match c {
+			b'0'..=b'9' => c - b'0',
+			b'a'..=b'f' => c - b'a' + 10,
+			_ => panic!("invalid hex digit"),
+		}
+	}
+	let mut out = [0u8; 32];
+	let mut i = 0;
+	while i < 32 {
+		out[i] = nibble(hex[2 * i]) << 4 | nibble(hex[2 * i + 1]);
+		i += 1;
+	}
+	out
+}
+
+/// The precompile address of asset `id`.
+pub fn asset_address(id: impl Into<u128>) -> H160 {
+	let mut address = [0u8; 20];
+	address[..4].copy_from_slice(&ASSET_PRECOMPILE_PREFIX);
+	address[4..].copy_from_slice(&id.into().to_be_bytes());
+	H160(address)
+}
+
+/// The asset whose precompile lives at `address`, if it is an asset precompile address.
+pub fn asset_id<AssetId: TryFrom<u128>>(address: H160) -> Option<AssetId> {
+	let (prefix, id) = address.as_bytes().split_at(4);
+	if prefix != ASSET_PRECOMPILE_PREFIX {
+		return None
+	}
+	AssetId::try_from(u128::from_be_bytes(id.try_into().ok()?)).ok()
+}
+
+type AssetIdOf<R, I> = <R as pallet_assets::Config<I>>::AssetId;
+type BalanceOf<R, I> = <R as pallet_assets::Config<I>>::Balance;
+type AccountIdOf<R> = <R as frame_system::Config>::AccountId;
+
+/// ERC-20 precompile for the assets of `pallet_assets` instance `I`.
+pub struct AssetsErc20<R, I = ()>(PhantomData<(R, I)>);
+
+impl<R, I> AssetsErc20<R, I>
+where
+	R: pallet_evm::Config + pallet_assets::Config<I>,
+	I: 'static,
+	AssetIdOf<R, I>: TryFrom<u128>,
+	BalanceOf<R, I>: TryFrom<u128> + Into<u128>,
+{
+	/// Whether `address` is the precompile of an existing asset.
+	pub fn is_asset(address: H160) -> bool {
+		asset_id::<AssetIdOf<R, I>>(address)
+			.map_or(false, |id| <pallet_assets::Pallet<R, I> as Inspect<_>>::asset_exists(id))
+	}
+
+	/// The gas a lookup of `address` costs.
+	pub fn is_asset_cost(address: H160) -> u64 {
+		if address.as_bytes().starts_with(&ASSET_PRECOMPILE_PREFIX) {
+			Self::weight_to_gas(R::DbWeight::get().reads(1))
+		} else {
+			0
+		}
+	}
+
+	/// Charge the lookup of `handle.code_address()` and, if it is the precompile of an existing
+	/// asset, execute the call.
+	///
+	/// Returns `None` if there is no such asset, for the precompile set to try other addresses.
+	pub fn execute_if_asset(handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		let address = handle.code_address();
+		if let Err(error) = handle.record_cost(Self::is_asset_cost(address)) {
+			return Some(Err(error.into()))
+		}
+		Self::is_asset(address).then(|| Self::execute(handle))
+	}
+
+	/// Execute an ERC-20 call to the asset precompile at `handle.code_address()`.
+	pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+		let id = asset_id::<AssetIdOf<R, I>>(handle.code_address())
+			.ok_or_else(|| revert("not an asset"))?;
+		// The caller would be the delegating contract, which must not act on its callers' behalf.
+		if handle.code_address() != handle.context().address {
+			return Err(revert("cannot be called with DELEGATECALL"))
+		}
+
+		let input = handle.input().to_vec();
+		let input = Input::new(&input);
+		let selector = input.selector()?;
+		let mutates =
+			matches!(selector, selector::TRANSFER | selector::TRANSFER_FROM | selector::APPROVE);
+		if mutates && handle.is_static() {
+			return Err(revert("cannot modify state in a static call"))
+		}
+
+		let output = match selector {
+			selector::TOTAL_SUPPLY => {
+				handle.record_cost(Self::reads(1))?;
+				let supply = <pallet_assets::Pallet<R, I> as Inspect<_>>::total_issuance(id);
+				Self::encode_balance(supply)
+			},
+			selector::BALANCE_OF => {
+				handle.record_cost(Self::reads(1))?;
+				let owner = R::AddressMapping::into_account_id(input.address(0)?);
+				let balance = <pallet_assets::Pallet<R, I> as Inspect<_>>::balance(id, &owner);
+				Self::encode_balance(balance)
+			},
+			selector::ALLOWANCE => {
+				handle.record_cost(Self::reads(1))?;
+				let owner = R::AddressMapping::into_account_id(input.address(0)?);
+				let spender = R::AddressMapping::into_account_id(input.address(1)?);
+				let allowance = <pallet_assets::Pallet<R, I> as approvals::Inspect<_>>::allowance(
+					id, &owner, &spender,
+				);
+				Self::encode_balance(allowance)
+			},
+			selector::NAME => {
+				handle.record_cost(Self::reads(1))?;
+				encode_bytes(&<pallet_assets::Pallet<R, I> as metadata::Inspect<_>>::name(id))
+			},
+			selector::SYMBOL => {
+				handle.record_cost(Self::reads(1))?;
+				encode_bytes(&<pallet_assets::Pallet<R, I> as metadata::Inspect<_>>::symbol(id))
+			},
+			selector::DECIMALS => {
+				handle.record_cost(Self::reads(1))?;
+				encode_uint(<pallet_assets::Pallet<R, I> as metadata::Inspect<_>>::decimals(id))
+			},
+			selector::TRANSFER => {
+				handle.record_cost(Self::weight_to_gas(
+					<R as pallet_assets::Config<I>>::WeightInfo::transfer(),
+				))?;
+				let from = handle.context().caller;
+				let to = input.address(0)?;
+				let amount = input.uint(1)?;
+				Self::dispatch(|| {
+					pallet_assets::Pallet::<R, I>::transfer(
+						RawOrigin::Signed(R::AddressMapping::into_account_id(from)).into(),
+						id.into(),
+						R::Lookup::unlookup(R::AddressMapping::into_account_id(to)),
+						Self::balance(amount)?,
+					)
+				})?;
+				Self::log(handle, TRANSFER_TOPIC, from, to, amount)?;
+				encode_bool(true)
+			},
+			selector::TRANSFER_FROM => {
+				handle.record_cost(Self::weight_to_gas(
+					<R as pallet_assets::Config<I>>::WeightInfo::transfer_approved(),
+				))?;
+				let spender = handle.context().caller;
+				let from = input.address(0)?;
+				let to = input.address(1)?;
+				let amount = input.uint(2)?;
+				Self::dispatch(|| {
+					pallet_assets::Pallet::<R, I>::transfer_approved(
+						RawOrigin::Signed(R::AddressMapping::into_account_id(spender)).into(),
+						id.into(),
+						R::Lookup::unlookup(R::AddressMapping::into_account_id(from)),
+						R::Lookup::unlookup(R::AddressMapping::into_account_id(to)),
+						Self::balance(amount)?,
+					)
+				})?;
+				Self::log(handle, TRANSFER_TOPIC, from, to, amount)?;
+				encode_bool(true)
+			},
+			selector::APPROVE => {
+				let cancel = <R as pallet_assets::Config<I>>::WeightInfo::cancel_approval();
+				let approve = <R as pallet_assets::Config<I>>::WeightInfo::approve_transfer();
+				handle.record_cost(Self::weight_to_gas(cancel.saturating_add(approve)))?;
+				let owner = handle.context().caller;
+				let spender = input.address(0)?;
+				let amount = input.uint(1)?;
+				let owner_account = R::AddressMapping::into_account_id(owner);
+				let spender_account = R::AddressMapping::into_account_id(spender);
+				// `approve_transfer` adds to an existing approval, ERC-20 `approve` replaces it.
+				Self::dispatch(|| {
+					let current = <pallet_assets::Pallet<R, I> as approvals::Inspect<_>>::allowance(
+						id.clone(),
+						&owner_account,
+						&spender_account,
+					);
+					if !current.is_zero() {
+						pallet_assets::Pallet::<R, I>::cancel_approval(
+							RawOrigin::Signed(owner_account.clone()).into(),
+							id.clone().into(),
+							R::Lookup::unlookup(spender_account.clone()),
+						)?;
+					}
+					let amount = Self::balance(amount)?;
+					if !amount.is_zero() {
+						pallet_assets::Pallet::<R, I>::approve_transfer(
+							RawOrigin::Signed(owner_account.clone()).into(),
+							id.clone().into(),
+							R::Lookup::unlookup(spender_account.clone()),
+							amount,
+						)?;
+					}
+					Ok(())
+				})?;
+				Self::log(handle, APPROVAL_TOPIC, owner, spender, amount)?;
+				encode_bool(true)
+			},
+			_ => return Err(revert("unknown selector")),
+		};
+
+		Ok(PrecompileOutput { exit_status: ExitSucceed::Returned, output })
+	}
+
+	fn weight_to_gas(weight: Weight) -> u64 {
+		R::GasWeightMapping::weight_to_gas(weight)
+	}
+
+	fn reads(reads: u64) -> u64 {
+		Self::weight_to_gas(R::DbWeight::get().reads(reads))
+	}
+
+	fn encode_balance(balance: BalanceOf<R, I>) -> Vec<u8> {
+		encode_uint(Into::<u128>::into(balance))
+	}
+
+	fn balance(amount: U256) -> Result<BalanceOf<R, I>, sp_runtime::DispatchError> {
+		u128::try_from(amount)
+			.ok()
+			.and_then(|amount| BalanceOf::<R, I>::try_from(amount).ok())
+			.ok_or(sp_runtime::DispatchError::Other("amount does not fit into the balance type"))
+	}
+
+	/// Dispatch in a storage layer, so that a failing call leaves no changes behind even though
+	/// the EVM only reverts its own state.
+	fn dispatch(call: impl FnOnce() -> DispatchResult) -> Result<(), PrecompileFailure> {
+		with_storage_layer(call).map_err(|error| {
+			let reason: &'static str = error.into();
+			revert(reason)
+		})
+	}
+
+	/// Emit a `Transfer` or `Approval` log from the asset precompile.
+	fn log(
+		handle: &mut impl PrecompileHandle,
+		topic: [u8; 32],
+		from: H160,
+		to: H160,
+		amount: U256,
+	) -> Result<(), PrecompileFailure> {
+		// G_log, three topics and one word of data.
+		handle.record_cost(375 + 3 * 375 + 8 * 32)?;
+		let address = handle.code_address();
+		handle.log(address, vec![H256(topic), from.into(), to.into()], encode_uint(amount))?;
+		Ok(())
+	}
+}
+
+/// Stores [`ASSET_PRECOMPILE_CODE`] at the precompile address of every created asset and removes
+/// it again when the asset is destroyed.
+pub struct AssetPrecompileCode<R, I = ()>(PhantomData<(R, I)>);
+
+impl<R, I> AssetsCallback<AssetIdOf<R, I>, AccountIdOf<R>> for AssetPrecompileCode<R, I>
+where
+	R: pallet_evm::Config + pallet_assets::Config<I>,
+	I: 'static,
+	AssetIdOf<R, I>: Into<u128>,
+{
+	fn created(id: &AssetIdOf<R, I>, _owner: &AccountIdOf<R>) {
+		pallet_evm::AccountCodes::<R>::insert(
+			asset_address(id.clone()),
+			Vec::from(ASSET_PRECOMPILE_CODE),
+		);
+	}
+
+	fn destroyed(id: &AssetIdOf<R, I>) {
+		pallet_evm::AccountCodes::<R>::remove(asset_address(id.clone()));
+	}
+}
@@ -0,0 +1,245 @@
+use super::*;
+use crate::mock::*;
+use abi::Input;
+use fp_evm::ExitError;
+use frame_support::assert_ok;
+
+fn call(selector: [u8; 4], words: &[[u8; 32]]) -> Vec<u8> {
+	let mut input = selector.to_vec();
+	words.iter().for_each(|word| input.extend_from_slice(word));
+	input
+}
+
+#[test]
+fn asset_addresses_round_trip() {
+	let address = asset_address(9u32);
+	assert_eq!(
+		address.as_bytes(),
+		&[0xff, 0xff, 0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 9]
+	);
+	assert_eq!(asset_id::<u32>(address), Some(9));
+	// Ids that do not fit into the asset id type are not assets.
+	assert_eq!(asset_id::<u32>(asset_address(u128::from(u32::MAX) + 1)), None);
+	assert_eq!(asset_id::<u32>(H160::repeat_byte(1)), None);
+}
+
+#[test]
+fn topics_are_the_erc20_event_signatures() {
+	assert_eq!(TRANSFER_TOPIC, sp_io::hashing::keccak_256(b"Transfer(address,address,uint256)"));
+	assert_eq!(APPROVAL_TOPIC, sp_io::hashing::keccak_256(b"Approval(address,address,uint256)"));
+}
+
+#[test]
+fn selectors_match_the_erc20_signatures() {
+	let selector = |signature: &[u8]| -> [u8; 4] {
+		sp_io::hashing::keccak_256(signature)[..4].try_into().unwrap()
+	};
+	assert_eq!(selector::TOTAL_SUPPLY, selector(b"totalSupply()"));
+	assert_eq!(selector::BALANCE_OF, selector(b"balanceOf(address)"));
+	assert_eq!(selector::ALLOWANCE, selector(b"allowance(address,address)"));
+	assert_eq!(selector::TRANSFER, selector(b"transfer(address,uint256)"));
+	assert_eq!(selector::APPROVE, selector(b"approve(address,uint256)"));
+	assert_eq!(selector::TRANSFER_FROM, selector(b"transferFrom(address,address,uint256)"));
+	assert_eq!(selector::NAME, selector(b"name()"));
+	assert_eq!(selector::SYMBOL, selector(b"symbol()"));
+	assert_eq!(selector::DECIMALS, selector(b"decimals()"));
+}
+
+#[test]
+fn arguments_decode() {
+	let mut to = [0u8; 32];
+	to[12..].copy_from_slice(&[7u8; 20]);
+	let input = call(selector::TRANSFER, &[to, encode_uint(1_000u32).try_into().unwrap()]);
+	let input = Input::new(&input);
+
+	assert_eq!(input.selector().ok(), Some(selector::TRANSFER));
+	assert_eq!(input.address(0).ok(), Some(H160::repeat_byte(7)));
+	assert_eq!(input.uint(1).ok(), Some(U256::from(1_000)));
+	assert!(input.uint(2).is_err());
+}
+
+#[test]
+fn dirty_addresses_are_rejected() {
+	let input = call(selector::BALANCE_OF, &[[1u8; 32]]);
+	assert!(Input::new(&input).address(0).is_err());
+}
+
+#[test]
+fn strings_are_padded() {
+	let encoded = encode_bytes(b"Token");
+	assert_eq!(encoded.len(), 96);
+	assert_eq!(U256::from_big_endian(&encoded[..32]), U256::from(32));
+	assert_eq!(U256::from_big_endian(&encoded[32..64]), U256::from(5));
+	assert_eq!(&encoded[64..69], b"Token");
+	assert!(encoded[69..].iter().all(|byte| *byte == 0));
+}
+
+fn address(address: H160) -> [u8; 32] {
+	H256::from(address).0
+}
+
+fn uint(value: u128) -> [u8; 32] {
+	encode_uint(value).try_into().unwrap()
+}
+
+/// Call the precompile of [`ASSET`] from `caller` and return the output.
+fn execute(caller: H160, input: Vec<u8>) -> Result<Vec<u8>, PrecompileFailure> {
+	let mut handle = MockHandle::new(asset_address(ASSET), caller, input);
+	Erc20::execute(&mut handle).map(|output| output.output)
+}
+
+fn balance_of(who: H160) -> U256 {
+	U256::from_big_endian(&execute(who, call(selector::BALANCE_OF, &[address(who)])).unwrap())
+}
+
+fn allowance(owner: H160, spender: H160) -> U256 {
+	let input = call(selector::ALLOWANCE, &[address(owner), address(spender)]);
+	U256::from_big_endian(&execute(owner, input).unwrap())
+}
+
+#[test]
+fn transfer_moves_the_caller_balance_and_logs() {
+	new_test_ext().execute_with(|| {
+		let mut handle = MockHandle::new(
+			asset_address(ASSET),
+			ALICE,
+			call(selector::TRANSFER, &[address(BOB), uint(100)]),
+		);
+		let output = Erc20::execute(&mut handle).unwrap().output;
+
+		assert_eq!(output, encode_bool(true));
+		assert_eq!(balance_of(ALICE), U256::from(900));
+		assert_eq!(balance_of(BOB), U256::from(100));
+		assert_eq!(handle.logs.len(), 1);
+		let log = &handle.logs[0];
+		assert_eq!(log.address, asset_address(ASSET));
+		assert_eq!(log.topics, vec![H256(TRANSFER_TOPIC), ALICE.into(), BOB.into()]);
+		assert_eq!(log.data, encode_uint(100u32));
+
+		// A failing transfer reverts and changes nothing.
+		let input = call(selector::TRANSFER, &[address(CHARLIE), uint(1_000)]);
+		assert!(execute(BOB, input).is_err());
+		assert_eq!(balance_of(BOB), U256::from(100));
+	});
+}
+
+#[test]
+fn transfer_from_spends_the_allowance() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(execute(ALICE, call(selector::APPROVE, &[address(BOB), uint(100)])));
+
+		let input = call(selector::TRANSFER_FROM, &[address(ALICE), address(CHARLIE), uint(60)]);
+		let mut handle = MockHandle::new(asset_address(ASSET), BOB, input);
+		assert_eq!(Erc20::execute(&mut handle).unwrap().output, encode_bool(true));
+		assert_eq!(balance_of(ALICE), U256::from(940));
+		assert_eq!(balance_of(CHARLIE), U256::from(60));
+		assert_eq!(allowance(ALICE, BOB), U256::from(40));
+		assert_eq!(
+			handle.logs[0].topics,
+			vec![H256(TRANSFER_TOPIC), ALICE.into(), CHARLIE.into()]
+		);
+
+		// Neither more than the allowance nor without one.
+		let input = call(selector::TRANSFER_FROM, &[address(ALICE), address(CHARLIE), uint(41)]);
+		assert!(execute(BOB, input).is_err());
+		let input = call(selector::TRANSFER_FROM, &[address(ALICE), address(BOB), uint(1)]);
+		assert!(execute(CHARLIE, input).is_err());
+		assert_eq!(balance_of(ALICE), U256::from(940));
+	});
+}
+
+#[test]
+fn approve_replaces_the_allowance() {
+	new_test_ext().execute_with(|| {
+		let mut handle = MockHandle::new(
+			asset_address(ASSET),
+			ALICE,
+			call(selector::APPROVE, &[address(BOB), uint(100)]),
+		);
+		assert_eq!(Erc20::execute(&mut handle).unwrap().output, encode_bool(true));
+		assert_eq!(allowance(ALICE, BOB), U256::from(100));
+		assert_eq!(handle.logs[0].topics, vec![H256(APPROVAL_TOPIC), ALICE.into(), BOB.into()]);
+		assert_eq!(handle.logs[0].data, encode_uint(100u32));
+
+		// `pallet_assets` would add 30 to the 100.
+		assert_ok!(execute(ALICE, call(selector::APPROVE, &[address(BOB), uint(30)])));
+		assert_eq!(allowance(ALICE, BOB), U256::from(30));
+
+		assert_ok!(execute(ALICE, call(selector::APPROVE, &[address(BOB), uint(0)])));
+		assert_eq!(allowance(ALICE, BOB), U256::zero());
+		// Approving nothing twice is fine too.
+		assert_ok!(execute(ALICE, call(selector::APPROVE, &[address(BOB), uint(0)])));
+	});
+}
+
+#[test]
+fn static_calls_cannot_modify_state() {
+	new_test_ext().execute_with(|| {
+		let static_call = |input: Vec<u8>| {
+			let mut handle = MockHandle::new(asset_address(ASSET), ALICE, input);
+			handle.is_static = true;
+			Erc20::execute(&mut handle).map(|output| output.output)
+		};
+
+		for input in [
+			call(selector::TRANSFER, &[address(BOB), uint(1)]),
+			call(selector::APPROVE, &[address(BOB), uint(1)]),
+			call(selector::TRANSFER_FROM, &[address(ALICE), address(BOB), uint(1)]),
+		] {
+			assert_eq!(static_call(input), Err(revert("cannot modify state in a static call")));
+		}
+		assert_eq!(balance_of(BOB), U256::zero());
+
+		let balance = static_call(call(selector::BALANCE_OF, &[address(ALICE)])).unwrap();
+		assert_eq!(U256::from_big_endian(&balance), U256::from(1_000));
+	});
+}
+
+#[test]
+fn delegate_calls_are_rejected() {
+	new_test_ext().execute_with(|| {
+		// `CHARLIE` delegate-calls the precompile: it runs in `CHARLIE`'s context.
+		let mut handle = MockHandle::new(
+			asset_address(ASSET),
+			ALICE,
+			call(selector::TRANSFER, &[address(BOB), uint(1)]),
+		);
+		handle.context.address = CHARLIE;
+
+		let result = Erc20::execute(&mut handle);
+		assert_eq!(result, Err(revert("cannot be called with DELEGATECALL")));
+		assert_eq!(balance_of(BOB), U256::zero());
+	});
+}
+
+#[test]
+fn asset_lookups_are_charged() {
+	new_test_ext().execute_with(|| {
+		let lookup = Erc20::is_asset_cost(asset_address(ASSET));
+		assert!(lookup > 0);
+
+		// Unknown assets are looked up as well.
+		let mut handle = MockHandle::new(asset_address(ASSET + 1), ALICE, vec![]);
+		assert!(Erc20::execute_if_asset(&mut handle).is_none());
+		assert_eq!(handle.gas_used, lookup);
+
+		let input = call(selector::TOTAL_SUPPLY, &[]);
+		let mut handle = MockHandle::new(asset_address(ASSET), ALICE, input);
+		let output = Erc20::execute_if_asset(&mut handle).unwrap().unwrap().output;
+		assert_eq!(U256::from_big_endian(&output), U256::from(1_000));
+		assert!(handle.gas_used > lookup);
+
+		// Other addresses are not looked up.
+		let mut handle = MockHandle::new(H160::repeat_byte(1), ALICE, vec![]);
+		assert!(Erc20::execute_if_asset(&mut handle).is_none());
+		assert_eq!(handle.gas_used, 0);
+
+		// Without the gas for the lookup, the call runs out of gas.
+		let mut handle = MockHandle::new(asset_address(ASSET), ALICE, vec![]);
+		handle.gas_limit = lookup - 1;
+		assert_eq!(
+			Erc20::execute_if_asset(&mut handle),
+			Some(Err(PrecompileFailure::Error { exit_status: ExitError::OutOfGas }))
+		);
+	});
+}
@@ -0,0 +1,201 @@
+//! Runtime API for the state of an account across pallets, so that wallets can show an account
+//! with one call instead of a storage query per pallet.
+//!
+//! Asset, collection, item, pool and era ids are `u32`, as in every runtime of this node.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+use sp_std::vec::Vec;
+
+/// Which withdrawals a balance lock prevents.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Copy, RuntimeDebug, TypeInfo)]
+pub enum LockReasons {
+	/// Paying fees.
+	Fee,
+	/// Anything but paying fees.
+	Misc,
+	/// Any withdrawal.
+	All,
+}
+
+/// A lock on the native balance.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct BalanceLock<Balance> {
+	/// The identifier of the lock, e.g. `staking ` or `vesting `.
+	pub id: [u8; 8],
+	/// The locked amount.
+	pub amount: Balance,
+	/// The withdrawals the lock prevents.
+	pub reasons: LockReasons,
+}
+
+/// An amount of the native balance that is reserved, held or frozen for a named reason.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct NamedAmount<Balance> {
+	/// The SCALE-encoded identifier of the reason.
+	pub id: Vec<u8>,
+	/// The amount.
+	pub amount: Balance,
+}
+
+/// The native balance of an account.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct Balances<Balance> {
+	/// The free balance, including what is locked or frozen.
+	pub free: Balance,
+	/// The reserved balance, including what is held.
+	pub reserved: Balance,
+	/// The part of the free balance that can't be withdrawn.
+	pub frozen: Balance,
+	/// The locks that make up `frozen`.
+	pub locks: Vec<BalanceLock<Balance>>,
+	/// The freezes that make up `frozen`.
+	pub freezes: Vec<NamedAmount<Balance>>,
+	/// The named reserves that are part of `reserved`.
+	pub reserves: Vec<NamedAmount<Balance>>,
+	/// The holds that are part of `reserved`.
+	pub holds: Vec<NamedAmount<Balance>>,
+}
+
+/// A `pallet_assets` asset the account holds.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct AssetBalance<Balance> {
+	/// The asset.
+	pub asset_id: u32,
+	/// The balance of the account.
+	pub balance: Balance,
+	/// The symbol of the asset.
+	pub symbol: Vec<u8>,
+	/// The decimals of the asset.
+	pub decimals: u8,
+}
+
+/// A non-fungible item the account owns.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Copy, RuntimeDebug, TypeInfo)]
+pub struct Item {
+	/// The collection of the item.
+	pub collection: u32,
+	/// The item.
+	pub item: u32,
+}
+
+/// A part of the staked balance that is being unbonded.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct Unbonding<Balance> {
+	/// The amount.
+	pub value: Balance,
+	/// The era at which it can be withdrawn.
+	pub era: u32,
+}
+
+/// The staking ledger the account is the stash or the controller of.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct Staking<AccountId, Balance> {
+	/// The stash account.
+	pub stash: AccountId,
+	/// The controller account.
+	pub controller: AccountId,
+	/// The bonded balance, including what is being unbonded.
+	pub total: Balance,
+	/// The bonded balance that is not being unbonded.
+	pub active: Balance,
+	/// The balance that is being unbonded.
+	pub unlocking: Vec<Unbonding<Balance>>,
+	/// The validators the stash nominates, if it is a nominator.
+	pub nominations: Option<Vec<AccountId>>,
+	/// Whether the stash wants to validate.
+	pub validator: bool,
+}
+
+/// Membership of a nomination pool.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct PoolMembership<Balance> {
+	/// The pool.
+	pub pool_id: u32,
+	/// The points of the member in the pool.
+	pub points: Balance,
+	/// What the points are worth.
+	pub balance: Balance,
+	/// The rewards the member can claim.
+	pub pending_rewards: Balance,
+	/// The points being unbonded, by the era at which they can be withdrawn.
+	pub unbonding: Vec<(u32, Balance)>,
+}
+
+/// A vesting schedule.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct VestingSchedule<Balance, BlockNumber> {
+	/// The amount locked at `starting_block`.
+	pub locked: Balance,
+	/// The amount unlocked at each block from `starting_block` on.
+	pub per_block: Balance,
+	/// The block at which unlocking starts.
+	pub starting_block: BlockNumber,
+}
+
+/// A proxy of the account.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct Proxy<AccountId, BlockNumber> {
+	/// The account that may act for this one.
+	pub delegate: AccountId,
+	/// The name of the calls the delegate may make, e.g. `Any` or `Staking`.
+	pub proxy_type: Vec<u8>,
+	/// The number of blocks a call must be announced for before the delegate may make it.
+	pub delay: BlockNumber,
+}
+
+/// The identity of the account.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct Identity<AccountId, Balance> {
+	/// The display name of the identity, or of the sub-identity if the account is one.
+	pub display: Option<Vec<u8>>,
+	/// The account this is a sub-identity of.
+	pub parent: Option<AccountId>,
+	/// Whether a registrar judged the identity `Reasonable` or `KnownGood`.
+	pub verified: bool,
+	/// The deposit for the identity.
+	pub deposit: Balance,
+	/// The deposit for the sub-identities of the account.
+	pub subs_deposit: Balance,
+}
+
+/// The state of an account across pallets.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct AccountOverview<AccountId, Balance, BlockNumber> {
+	/// The native balance.
+	pub balances: Balances<Balance>,
+	/// The `pallet_assets` assets the account holds.
+	pub assets: Vec<AssetBalance<Balance>>,
+	/// The `pallet_nfts` items the account owns.
+	pub nfts: Vec<Item>,
+	/// The `pallet_uniques` items the account owns.
+	pub uniques: Vec<Item>,
+	/// The staking ledger of the account.
+	pub staking: Option<Staking<AccountId, Balance>>,
+	/// The nomination pool the account is a member of.
+	pub nomination_pool: Option<PoolMembership<Balance>>,
+	/// The vesting schedules of the account.
+	pub vesting: Vec<VestingSchedule<Balance, BlockNumber>>,
+	/// The proxies of the account.
+	pub proxies: Vec<Proxy<AccountId, BlockNumber>>,
+	/// The deposit for the proxies of the account.
+	pub proxy_deposit: Balance,
+	/// The identity of the account.
+	pub identity: Option<Identity<AccountId, Balance>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Gather the state of an account across pallets.
+	pub trait AccountOverviewApi<AccountId, Balance, BlockNumber> where
+		AccountId: Codec,
+		Balance: Codec,
+		BlockNumber: Codec,
+	{
+		/// The state of `account`.
+		fn account_overview(account: AccountId) -> AccountOverview<AccountId, Balance, BlockNumber>;
+	}
+}
@@ -0,0 +1,117 @@
+//! Runtime API for debugging contract calls.
+//!
+//! The API re-executes a contract call with debugging enabled, either as a dry-run against the
+//! state of a block or by replaying a block up to one of its extrinsics, and returns a
+//! [`CallSummary`].
+//!
+//! This is not a call trace. `pallet_contracts` of this Substrate version exposes no hooks into
+//! its call stack, so there is no per-frame gas, input or storage access to report, and the
+//! summary only holds what the call as a whole leaves behind:
+//! - the selector, gas, storage deposit and outcome of the top-level call;
+//! - the debug messages every frame printed, in print order;
+//! - the contract events of the call, in deposit order, see [`ContractEvent`]. A frame deposits
+//!   its events as it completes, and a frame that reverts takes its events and those of the
+//!   frames it called with it, so the events show which calls took effect, not the call tree.
+//!
+//! Contract storage writes are read by the node from the child tries the call changed. Reads
+//! leave nothing behind and are not reported.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use pallet_contracts_primitives::{ExecReturnValue, StorageDeposit};
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{DispatchError, RuntimeDebug};
+use sp_std::vec::Vec;
+use sp_weights::Weight;
+
+/// A `pallet_contracts` event deposited by a debugged call.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub enum ContractEvent<AccountId, Hash> {
+	/// `caller` called `contract`, and the call did not revert.
+	Called {
+		/// The account that made the call.
+		caller: AccountId,
+		/// The called contract.
+		contract: AccountId,
+	},
+	/// `contract` ran the code of `code_hash` in its own context, and did not revert.
+	DelegateCalled {
+		/// The contract whose context the code ran in.
+		contract: AccountId,
+		/// The code that was executed.
+		code_hash: Hash,
+	},
+	/// `deployer` instantiated `contract`.
+	Instantiated {
+		/// The account that instantiated the contract.
+		deployer: AccountId,
+		/// The new contract.
+		contract: AccountId,
+	},
+	/// `contract` terminated itself and sent its balance to `beneficiary`.
+	Terminated {
+		/// The terminated contract.
+		contract: AccountId,
+		/// The account that received the contract's balance.
+		beneficiary: AccountId,
+	},
+	/// `contract` emitted an event with `data`.
+	Emitted {
+		/// The emitting contract.
+		contract: AccountId,
+		/// The event data.
+		data: Vec<u8>,
+	},
+}
+
+/// The outcome of a debugged contract call, as a whole.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct CallSummary<AccountId, Balance, Hash> {
+	/// The selector of the message or constructor the top-level call ran, if its input had one.
+	pub selector: Option<[u8; 4]>,
+	/// The reference time and proof size the whole call consumed.
+	pub gas_consumed: Weight,
+	/// The gas limit the call needs to succeed.
+	pub gas_required: Weight,
+	/// The storage deposit charged or refunded by the call.
+	pub storage_deposit: StorageDeposit<Balance>,
+	/// What the called contract returned, or why the call failed.
+	pub result: Result<ExecReturnValue, DispatchError>,
+	/// The debug messages the contracts printed, in print order.
+	pub debug_message: Vec<u8>,
+	/// The contract events of the call, in deposit order.
+	pub events: Vec<ContractEvent<AccountId, Hash>>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Run contract calls with debugging enabled and summarize them.
+	pub trait ContractsDebugApi<AccountId, Balance, Hash> where
+		AccountId: Codec,
+		Balance: Codec,
+		Hash: Codec,
+	{
+		/// Dry-run a call of `dest` from `origin` and summarize it.
+		///
+		/// `gas_limit` and `storage_deposit_limit` default to the maximum allowed.
+		fn debug_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input: Vec<u8>,
+		) -> CallSummary<AccountId, Balance, Hash>;
+
+		/// Summarize the contract call or instantiation made by `extrinsic` against the current
+		/// state.
+		///
+		/// To debug a historical extrinsic, initialize its block and apply the extrinsics
+		/// before it first. Returns `None` if `extrinsic` is not a signed `Contracts::call`,
+		/// `Contracts::instantiate` or `Contracts::instantiate_with_code`.
+		fn debug_extrinsic(
+			extrinsic: <Block as sp_runtime::traits::Block>::Extrinsic,
+		) -> Option<CallSummary<AccountId, Balance, Hash>>;
+	}
+}
@@ -0,0 +1,52 @@
+//! Ethereum `personal_sign` (EIP-191) messages.
+//!
+//! Pallets that let the key of an H160 authorize something on chain have it sign a readable
+//! message with `personal_sign`, as every Ethereum wallet can, and recover the H160 from the
+//! signature.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::H160;
+use sp_std::prelude::*;
+
+/// A `personal_sign` signature: `r`, `s` and the recovery id `v` (0, 1, 27 or 28).
+pub type EthSignature = [u8; 65];
+
+/// The hash `personal_sign` signs for `message`, as specified by EIP-191.
+pub fn eth_message_hash(message: &[u8]) -> [u8; 32] {
+	let mut prefixed = b"\x19Ethereum Signed Message:\n".to_vec();
+	prefixed.extend(decimal(message.len()));
+	prefixed.extend_from_slice(message);
+	sp_io::hashing::keccak_256(&prefixed)
+}
+
+/// The H160 whose key made the `personal_sign` `signature` of `message`.
+pub fn eth_signer(message: &[u8], signature: &EthSignature) -> Option<H160> {
+	let hash = eth_message_hash(message);
+	let public = sp_io::crypto::secp256k1_ecdsa_recover(signature, &hash).ok()?;
+	Some(H160::from_slice(&sp_io::hashing::keccak_256(&public)[12..]))
+}
+
+/// Lowercase hex of `bytes`, without a prefix.
+pub fn hex(bytes: &[u8]) -> Vec<u8> {
+	const DIGITS: &[u8; 16] = b"0123456789abcdef";
+	bytes
+		.iter()
+		.flat_map(|byte| [DIGITS[(byte >> 4) as usize], DIGITS[(byte & 0xf) as usize]])
+		.collect()
+}
+
+/// The decimal representation of `n`.
+pub fn decimal(mut n: usize) -> Vec<u8> {
+	let mut digits = Vec::new();
+	loop {
+		digits.push(b'0' + (n % 10) as u8);
+		n /= 10;
+		if n == 0 {
+			break
+		}
+	}
+	digits.reverse();
+	digits
+}
@@ -0,0 +1,44 @@
+//! Runtime API for quoting the fee of an unsigned call.
+//!
+//! Wallets only hold the call they are about to sign, so the runtime accounts for the
+//! signature, address and signed extensions on their behalf when computing the length fee.
+//! The call is passed SCALE-encoded so that the node does not need to know the runtime's
+//! `RuntimeCall` type.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_runtime::{traits::MaybeDisplay, FixedU128, RuntimeDebug};
+use sp_std::vec::Vec;
+
+/// Breakdown of the fee a call would pay if it were submitted in the current block.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, Default, RuntimeDebug, TypeInfo)]
+pub struct FeeEstimate<Balance> {
+	/// The fixed fee every signed extrinsic pays.
+	pub base_fee: Balance,
+	/// The fee for the encoded length of the signed extrinsic.
+	pub len_fee: Balance,
+	/// The weight fee after `multiplier` has been applied.
+	pub adjusted_weight_fee: Balance,
+	/// The fee multiplier applied to the weight fee.
+	pub multiplier: FixedU128,
+	/// The tip the estimate was computed with.
+	pub tip: Balance,
+	/// The sum of all of the above.
+	pub total: Balance,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Quote fees for calls that have not been signed yet.
+	pub trait FeeEstimationApi<Balance> where
+		Balance: Codec + MaybeDisplay,
+	{
+		/// Estimate the fee of the SCALE-encoded `call` once it is signed and submitted with
+		/// `tip`.
+		///
+		/// Returns `None` if `call` does not decode into a call of this runtime.
+		fn estimate_call_fee(call: Vec<u8>, tip: Balance) -> Option<FeeEstimate<Balance>>;
+	}
+}
@@ -0,0 +1,33 @@
+//! Runtime API for building genesis configs from presets owned by the runtime.
+//!
+//! A preset is a JSON patch over the default `GenesisConfig` of the runtime. Keeping the
+//! presets in the runtime means a genesis change only touches the runtime crate, and the node
+//! reads them from the runtime Wasm instead of keeping its own copy.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_std::vec::Vec;
+
+/// Identifier of a genesis preset, e.g. `b"dev"`.
+pub type PresetId = Vec<u8>;
+
+/// Preset used for single-authority development chains.
+pub const DEV_PRESET: &str = "dev";
+/// Preset used for multi-authority local testnets.
+pub const LOCAL_TESTNET_PRESET: &str = "local";
+/// Preset used for staging networks.
+pub const STAGING_PRESET: &str = "staging";
+
+sp_api::decl_runtime_apis! {
+	/// Expose named genesis presets of the runtime.
+	pub trait GenesisBuilder {
+		/// The identifiers of all presets the runtime knows about.
+		fn preset_names() -> Vec<PresetId>;
+
+		/// The JSON patch for preset `id`, to be merged into the default `GenesisConfig`.
+		///
+		/// Returns `None` if the preset does not exist.
+		fn get_preset(id: PresetId) -> Option<Vec<u8>>;
+	}
+}
@@ -0,0 +1,37 @@
+//! Runtime API for resolving an account from either of its addresses.
+//!
+//! An account bound with `pallet_unified_accounts` can be addressed by its native `AccountId`
+//! or by its Ethereum H160. An H160 that is not bound still maps to a native account, the one
+//! the EVM uses for it, but a native account that is not bound has no H160.
+
+#![warn(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use parity_scale_codec::{Codec, Decode, Encode};
+use scale_info::TypeInfo;
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+
+/// An account and its H160.
+#[derive(Eq, PartialEq, Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct ResolvedAccount<AccountId> {
+	/// The native account.
+	pub account: AccountId,
+	/// The H160 that maps to `account`, if there is one.
+	pub evm_address: Option<H160>,
+	/// Whether `account` and `evm_address` are bound to each other.
+	pub bound: bool,
+}
+
+sp_api::decl_runtime_apis! {
+	/// Resolve accounts through the bindings of `pallet_unified_accounts`.
+	pub trait UnifiedAccountsApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The account `evm_address` maps to.
+		fn resolve_evm_address(evm_address: H160) -> ResolvedAccount<AccountId>;
+
+		/// The H160 bound to `account`, if any.
+		fn resolve_account(account: AccountId) -> ResolvedAccount<AccountId>;
+	}
+}
@@ -40,3 +40,106 @@ pub mod time {
 	pub const HOURS: BlockNumber = MINUTES * 60;
 	pub const DAYS: BlockNumber = HOURS * 24;
 }
+
+/// Fee-related.
+pub mod fee {
+	use common_primitives::Balance;
+	use frame_support::{
+		pallet_prelude::Get,
+		weights::{
+			constants::ExtrinsicBaseWeight, Weight, WeightToFee as WeightToFeeT,
+			WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
+		},
+	};
+	use smallvec::smallvec;
+	use sp_runtime::Perbill;
+	use sp_std::marker::PhantomData;
+
+	/// Handles converting the `ref_time` component of a weight into a fee.
+	///
+	/// The polynomial is calibrated so that `ExtrinsicBaseWeight` costs exactly `BaseFee`.
+	pub struct RefTimeToFee<BaseFee>(PhantomData<BaseFee>);
+	impl<BaseFee: Get<Balance>> WeightToFeePolynomial for RefTimeToFee<BaseFee> {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			let p = BaseFee::get();
+			let q = Balance::from(ExtrinsicBaseWeight::get().ref_time());
+			linear(p, q)
+		}
+	}
+
+	/// Handles converting the `proof_size` component of a weight into a fee.
+	///
+	/// The polynomial is calibrated so that a proof of one KiB costs `FeePerKiB`.
+	pub struct ProofSizeToFee<FeePerKiB>(PhantomData<FeePerKiB>);
+	impl<FeePerKiB: Get<Balance>> WeightToFeePolynomial for ProofSizeToFee<FeePerKiB> {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			linear(FeePerKiB::get(), 1024)
+		}
+	}
+
+	/// Converts a full weight into a fee: the `ref_time` fee plus the `proof_size` fee.
+	pub struct WeightToFee<BaseFee, FeePerKiB>(PhantomData<(BaseFee, FeePerKiB)>);
+	impl<BaseFee: Get<Balance>, FeePerKiB: Get<Balance>> WeightToFeeT
+		for WeightToFee<BaseFee, FeePerKiB>
+	{
+		type Balance = Balance;
+		fn weight_to_fee(weight: &Weight) -> Self::Balance {
+			// Both polynomials only look at `ref_time`, so feed each component in that slot.
+			let ref_time_fee =
+				RefTimeToFee::<BaseFee>::weight_to_fee(&Weight::from_parts(weight.ref_time(), 0));
+			let proof_size_fee = ProofSizeToFee::<FeePerKiB>::weight_to_fee(&Weight::from_parts(
+				weight.proof_size(),
+				0,
+			));
+			ref_time_fee.saturating_add(proof_size_fee)
+		}
+	}
+
+	/// A single-term polynomial charging `p / q` per unit.
+	fn linear(p: Balance, q: Balance) -> WeightToFeeCoefficients<Balance> {
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::*;
+		use crate::constants::currency::{CENTS, MILLICENTS};
+		use frame_support::{parameter_types, weights::constants::WEIGHT_REF_TIME_PER_SECOND};
+
+		parameter_types! {
+			pub const BaseFee: Balance = 10 * CENTS;
+			pub const FeePerKiB: Balance = 10 * MILLICENTS;
+		}
+
+		type Fee = WeightToFee<BaseFee, FeePerKiB>;
+
+		#[test]
+		fn extrinsic_base_fee_is_correct() {
+			let base = Weight::from_parts(ExtrinsicBaseWeight::get().ref_time(), 0);
+			assert_eq!(Fee::weight_to_fee(&base), BaseFee::get());
+		}
+
+		#[test]
+		fn proof_size_is_charged() {
+			let proof = Weight::from_parts(0, 64 * 1024);
+			assert_eq!(Fee::weight_to_fee(&proof), 64 * FeePerKiB::get());
+		}
+
+		#[test]
+		fn full_block_fee_is_correct() {
+			let full_block = Weight::from_parts(2 * WEIGHT_REF_TIME_PER_SECOND, 0);
+			let expected = BaseFee::get() * Balance::from(full_block.ref_time()) /
+				Balance::from(ExtrinsicBaseWeight::get().ref_time());
+			// Allow for the rounding of the fractional coefficient.
+			let actual = Fee::weight_to_fee(&full_block);
+			assert!(actual.abs_diff(expected) <= expected / 1_000_000);
+		}
+	}
+}
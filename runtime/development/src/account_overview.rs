@@ -0,0 +1,157 @@
+use crate::*;
+use account_overview_runtime_api as api;
+use frame_support::traits::tokens::{
+	fungibles::metadata::Inspect as _, nonfungibles::InspectEnumerable as _,
+	nonfungibles_v2::InspectEnumerable as _,
+};
+
+/// The overview of `who` served by `AccountOverviewApi`.
+///
+/// Storage keyed by `who`, or by the collections and pools it refers to, is read by key. The
+/// assets are the exception: `Assets::account_balances` iterates every asset and reads the
+/// balance of `who` in each. `pallet_indices` indices and `pallet_multisig` deposits can only be
+/// found by iterating a whole map and are added node-side by the `nativex_accountOverview` RPC.
+pub fn account_overview(who: AccountId) -> api::AccountOverview<AccountId, Balance, BlockNumber> {
+	let (proxies, proxy_deposit) = Proxy::proxies(&who);
+	api::AccountOverview {
+		balances: balances(&who),
+		assets: Assets::account_balances(who.clone())
+			.into_iter()
+			.map(|(asset_id, balance)| api::AssetBalance {
+				asset_id,
+				balance,
+				symbol: Assets::symbol(asset_id),
+				decimals: Assets::decimals(asset_id),
+			})
+			.collect(),
+		nfts: Nfts::owned(&who).map(|(collection, item)| api::Item { collection, item }).collect(),
+		uniques: Uniques::owned(&who)
+			.map(|(collection, item)| api::Item { collection, item })
+			.collect(),
+		staking: staking(&who),
+		nomination_pool: nomination_pool(&who),
+		vesting: Vesting::vesting(&who)
+			.unwrap_or_default()
+			.iter()
+			.map(|schedule| api::VestingSchedule {
+				locked: schedule.locked(),
+				per_block: schedule.per_block(),
+				starting_block: schedule.starting_block(),
+			})
+			.collect(),
+		proxies: proxies
+			.into_iter()
+			.map(|proxy| api::Proxy {
+				delegate: proxy.delegate,
+				proxy_type: proxy_type_name(proxy.proxy_type).to_vec(),
+				delay: proxy.delay,
+			})
+			.collect(),
+		proxy_deposit,
+		identity: identity(&who),
+	}
+}
+
+fn balances(who: &AccountId) -> api::Balances<Balance> {
+	let account = System::account(who).data;
+	let named = |id: Vec<u8>, amount| api::NamedAmount { id, amount };
+	api::Balances {
+		free: account.free,
+		reserved: account.reserved,
+		frozen: account.frozen,
+		locks: Balances::locks(who)
+			.into_iter()
+			.map(|lock| api::BalanceLock {
+				id: lock.id,
+				amount: lock.amount,
+				reasons: match lock.reasons {
+					pallet_balances::Reasons::Fee => api::LockReasons::Fee,
+					pallet_balances::Reasons::Misc => api::LockReasons::Misc,
+					pallet_balances::Reasons::All => api::LockReasons::All,
+				},
+			})
+			.collect(),
+		freezes: pallet_balances::Freezes::<Runtime>::get(who)
+			.into_iter()
+			.map(|freeze| named(freeze.id.encode(), freeze.amount))
+			.collect(),
+		reserves: Balances::reserves(who)
+			.into_iter()
+			.map(|reserve| named(reserve.id.to_vec(), reserve.amount))
+			.collect(),
+		holds: pallet_balances::Holds::<Runtime>::get(who)
+			.into_iter()
+			.map(|hold| named(hold.id.encode(), hold.amount))
+			.collect(),
+	}
+}
+
+/// The ledger `who` is the stash or the controller of.
+fn staking(who: &AccountId) -> Option<api::Staking<AccountId, Balance>> {
+	let controller = Staking::bonded(who).unwrap_or_else(|| who.clone());
+	let ledger = Staking::ledger(&controller)?;
+	let stash = ledger.stash;
+	Some(api::Staking {
+		nominations: Staking::nominators(&stash).map(|nomination| nomination.targets.into_inner()),
+		validator: pallet_staking::Validators::<Runtime>::contains_key(&stash),
+		stash,
+		controller,
+		total: ledger.total,
+		active: ledger.active,
+		unlocking: ledger
+			.unlocking
+			.into_iter()
+			.map(|chunk| api::Unbonding { value: chunk.value, era: chunk.era })
+			.collect(),
+	})
+}
+
+fn nomination_pool(who: &AccountId) -> Option<api::PoolMembership<Balance>> {
+	let member = pallet_nomination_pools::PoolMembers::<Runtime>::get(who)?;
+	Some(api::PoolMembership {
+		pool_id: member.pool_id,
+		points: member.points,
+		balance: NominationPools::api_points_to_balance(member.pool_id, member.points),
+		pending_rewards: NominationPools::api_pending_rewards(who.clone()).unwrap_or_default(),
+		unbonding: member.unbonding_eras.into_iter().collect(),
+	})
+}
+
+fn identity(who: &AccountId) -> Option<api::Identity<AccountId, Balance>> {
+	use pallet_identity::{Data, Judgement};
+
+	let registration = Identity::identity(who);
+	let super_of = Identity::super_of(who);
+	if registration.is_none() && super_of.is_none() {
+		return None
+	}
+	let raw = |data: &Data| match data {
+		Data::Raw(name) => Some(name.to_vec()),
+		_ => None,
+	};
+	let display = match (&super_of, &registration) {
+		(Some((_, name)), _) => raw(name),
+		(None, Some(registration)) => raw(&registration.info.display),
+		(None, None) => None,
+	};
+	Some(api::Identity {
+		display,
+		parent: super_of.map(|(parent, _)| parent),
+		verified: registration.as_ref().map_or(false, |registration| {
+			registration.judgements.iter().any(|(_, judgement)| {
+				matches!(judgement, Judgement::KnownGood | Judgement::Reasonable)
+			})
+		}),
+		deposit: registration.map_or(0, |registration| registration.deposit),
+		subs_deposit: Identity::subs_of(who).0,
+	})
+}
+
+fn proxy_type_name(proxy_type: ProxyType) -> &'static [u8] {
+	match proxy_type {
+		ProxyType::Any => b"Any",
+		ProxyType::NonTransfer => b"NonTransfer",
+		ProxyType::Governance => b"Governance",
+		ProxyType::Staking => b"Staking",
+	}
+}
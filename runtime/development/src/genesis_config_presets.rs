@@ -0,0 +1,248 @@
+//! Genesis presets exposed through [`genesis_builder_runtime_api::GenesisBuilder`].
+//!
+//! Every preset is a JSON patch over `GenesisConfig::default()`. `GenesisConfig` only implements
+//! `serde` with `std`, so the patches are written out by hand: field names are the camelCase
+//! names of the pallet genesis configs, and keys are SS58 (prefix 42) of the well-known
+//! development seeds.
+//!
+//! `serde_json::Value` can't hold numbers above `u64::MAX`, so the sections with balances are
+//! typed and serialized straight to JSON text.
+
+use crate::*;
+use genesis_builder_runtime_api::{PresetId, DEV_PRESET, LOCAL_TESTNET_PRESET, STAGING_PRESET};
+use nativex_assets_erc20_precompile::{asset_address, ASSET_PRECOMPILE_CODE};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+const ENDOWMENT: Balance = 10_000_000 * NATIVEX;
+const STASH: Balance = ENDOWMENT / 1000;
+
+/// `//Alice`, sr25519.
+const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+/// `//Alice//stash`, sr25519.
+const ALICE_STASH: &str = "5GNJqTPyNqANBkUVMN1LPPrxXnFouWXoe2wNSmmEoLctxiZY";
+/// `//Alice`, ed25519.
+const ALICE_ED: &str = "5FA9nQDVg267DEd8m1ZypXLBnvN7SFxYwV7ndqSYGiN9TTpu";
+/// `//Bob`, sr25519.
+const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+/// `//Bob//stash`, sr25519.
+const BOB_STASH: &str = "5HpG9w8EBLe5XCrbczpwq5TSXvedjrBGCwqxK1iQ7qUsSWFc";
+/// `//Bob/stash`, sr25519: a soft derivation, endowed by the chain specs the presets replace.
+const BOB_SOFT_STASH: &str = "5FeD15uf7x3BhWEnYwUDrwqodsyW5K3ofHeqwGn1omfQavbR";
+/// `//Bob`, ed25519.
+const BOB_ED: &str = "5GoNkf6WdbxCFnPdAnYYQyCjAKPJgLNxXwPjwTh6DGg6gN3E";
+/// Account #0 of the Hardhat and Anvil default mnemonic.
+const HARDHAT_0: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+/// Id, owner, sufficiency and minimum balance of the assets created at genesis.
+///
+/// Asset 9 is used by the NIS pallet as counterpart currency.
+const GENESIS_ASSETS: [(u32, &str, bool, Balance); 1] = [(9, ALICE, true, 1)];
+
+/// EIP-155 chain id of the test networks, the one Ethereum tooling assumes for local nodes.
+const EVM_CHAIN_ID: u64 = 1337;
+
+/// Keys of a genesis authority.
+struct Authority {
+	/// Stash account, also used as the session validator id.
+	stash: &'static str,
+	/// Controller account.
+	controller: &'static str,
+	/// GRANDPA key.
+	grandpa: &'static str,
+	/// BABE, ImOnline and AuthorityDiscovery key.
+	session: &'static str,
+}
+
+const ALICE_AUTHORITY: Authority =
+	Authority { stash: ALICE_STASH, controller: ALICE, grandpa: ALICE_ED, session: ALICE };
+const BOB_AUTHORITY: Authority =
+	Authority { stash: BOB_STASH, controller: BOB, grandpa: BOB_ED, session: BOB };
+
+/// A genesis patch.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Patch {
+	balances: BalancesPatch,
+	session: Value,
+	technical_committee: Value,
+	staking: StakingPatch,
+	elections: ElectionsPatch,
+	sudo: Value,
+	babe: Value,
+	society: Value,
+	assets: Value,
+	nomination_pools: Value,
+	evm: Value,
+	evm_chain_id: Value,
+	contract_calls: Value,
+}
+
+#[derive(Serialize)]
+struct BalancesPatch {
+	balances: Vec<(&'static str, Balance)>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StakingPatch {
+	validator_count: u32,
+	minimum_validator_count: u32,
+	invulnerables: Vec<&'static str>,
+	slash_reward_fraction: u32,
+	/// Stash, controller, bond and status of each staker.
+	stakers: Vec<(&'static str, &'static str, Balance, &'static str)>,
+}
+
+#[derive(Serialize)]
+struct ElectionsPatch {
+	members: Vec<(&'static str, Balance)>,
+}
+
+/// Build the genesis patch shared by all presets.
+fn testnet_genesis(
+	initial_authorities: &[Authority],
+	root_key: &str,
+	endowed_accounts: &[&'static str],
+	evm_accounts: &[&str],
+) -> Patch {
+	let mut endowed_accounts = endowed_accounts.to_vec();
+	// endow all authorities.
+	initial_authorities.iter().map(|x| x.stash).for_each(|x| {
+		if !endowed_accounts.contains(&x) {
+			endowed_accounts.push(x)
+		}
+	});
+	let half = endowed_accounts.iter().copied().take((endowed_accounts.len() + 1) / 2);
+
+	Patch {
+		balances: BalancesPatch {
+			balances: endowed_accounts.iter().map(|x| (*x, ENDOWMENT)).collect(),
+		},
+		session: json!({
+			"keys": initial_authorities
+				.iter()
+				.map(|x| {
+					json!([
+						x.stash,
+						x.stash,
+						{
+							"grandpa": x.grandpa,
+							"babe": x.session,
+							"im_online": x.session,
+							"authority_discovery": x.session,
+						},
+					])
+				})
+				.collect::<Vec<_>>(),
+		}),
+		technical_committee: json!({
+			"members": half.clone().collect::<Vec<_>>(),
+		}),
+		staking: StakingPatch {
+			validator_count: initial_authorities.len() as u32 * 2,
+			minimum_validator_count: initial_authorities.len() as u32,
+			invulnerables: initial_authorities.iter().map(|x| x.stash).collect(),
+			slash_reward_fraction: Perbill::from_percent(10).deconstruct(),
+			stakers: initial_authorities
+				.iter()
+				.map(|x| (x.stash, x.controller, STASH, "Validator"))
+				.collect(),
+		},
+		elections: ElectionsPatch { members: half.clone().map(|x| (x, STASH)).collect() },
+		sudo: json!({
+			"key": root_key,
+		}),
+		babe: json!({
+			"epochConfig": {
+				"c": PRIMARY_PROBABILITY,
+				"allowed_slots": "PrimaryAndSecondaryPlainSlots",
+			},
+		}),
+		society: json!({
+			"members": half.collect::<Vec<_>>(),
+			"pot": 0,
+			"maxMembers": 999,
+		}),
+		assets: json!({
+			"assets": GENESIS_ASSETS,
+		}),
+		nomination_pools: json!({
+			"minCreateBond": 10 * NATIVEX,
+			"minJoinBond": 1 * NATIVEX,
+		}),
+		evm: json!({
+			"accounts": evm_accounts
+				.iter()
+				.map(|x| {
+					let account = json!({
+						"nonce": "0x0",
+						"balance": format!("{:#x}", ENDOWMENT),
+						"storage": {},
+						"code": [],
+					});
+					(x.to_string(), account)
+				})
+				// The assets pallet doesn't call its `created` callback at genesis, so the
+				// precompile code of the genesis assets is written here.
+				.chain(GENESIS_ASSETS.iter().map(|(id, ..)| {
+					let account = json!({
+						"nonce": "0x0",
+						"balance": "0x0",
+						"storage": {},
+						"code": ASSET_PRECOMPILE_CODE,
+					});
+					(format!("{:?}", asset_address(*id)), account)
+				}))
+				.collect::<serde_json::Map<_, _>>(),
+		}),
+		evm_chain_id: json!({
+			"chainId": EVM_CHAIN_ID,
+		}),
+		contract_calls: json!({
+			"allowedCalls": InitialContractCalls::get(),
+		}),
+	}
+}
+
+/// Single-authority development chain.
+fn development_genesis() -> Patch {
+	testnet_genesis(&[ALICE_AUTHORITY], ALICE, &[ALICE, ALICE_STASH, BOB_SOFT_STASH], &[HARDHAT_0])
+}
+
+/// Two-authority local testnet.
+fn local_testnet_genesis() -> Patch {
+	testnet_genesis(
+		&[ALICE_AUTHORITY, BOB_AUTHORITY],
+		ALICE,
+		&[ALICE_STASH, BOB_SOFT_STASH],
+		&[HARDHAT_0],
+	)
+}
+
+/// Staging network: same authorities as the local testnet, but only the stashes and the sudo
+/// account are funded.
+///
+/// The keys are the public development keys and MUST be rotated before any public launch.
+fn staging_genesis() -> Patch {
+	testnet_genesis(&[ALICE_AUTHORITY, BOB_AUTHORITY], ALICE, &[ALICE], &[])
+}
+
+/// All presets known to this runtime.
+pub fn preset_names() -> Vec<PresetId> {
+	[DEV_PRESET, LOCAL_TESTNET_PRESET, STAGING_PRESET]
+		.iter()
+		.map(|id| id.as_bytes().to_vec())
+		.collect()
+}
+
+/// The JSON patch for preset `id`, if any.
+pub fn get_preset(id: &PresetId) -> Option<Vec<u8>> {
+	let patch = match id.as_slice() {
+		id if id == DEV_PRESET.as_bytes() => development_genesis(),
+		id if id == LOCAL_TESTNET_PRESET.as_bytes() => local_testnet_genesis(),
+		id if id == STAGING_PRESET.as_bytes() => staging_genesis(),
+		_ => return None,
+	};
+	Some(serde_json::to_vec(&patch).expect("serialization to json is expected to work. qed."))
+}
@@ -1,19 +1,25 @@
 use crate::{
-	AccountId, AllianceMotion, Assets, Authorship, Balances, Hash, NegativeImbalance, Runtime,
-	RuntimeCall,
+	AccountId, AllianceMotion, AssetConversion, Assets, Authorship, Babe, Balance, Balances, Hash,
+	NegativeImbalance, Runtime, RuntimeCall, System, TransactionPayment,
 };
+use pallet_asset_conversion::NativeOrAssetId;
 use pallet_asset_tx_payment::HandleCredit;
 
 use frame_support::{
+	dispatch::DispatchClass,
 	pallet_prelude::*,
 	traits::{
+		fungible,
 		fungibles::{Balanced, Credit},
-		Currency, OnUnbalanced,
+		tokens::{BalanceConversion, Preservation},
+		Currency, FindAuthor, OnUnbalanced,
 	},
+	ConsensusEngineId,
 };
 use pallet_alliance::{IdentityVerifier, ProposalIndex, ProposalProvider};
 use pallet_society::Judgement;
 use pallet_treasury::Proposal;
+use sp_core::{crypto::ByteArray, H160};
 use sp_std::prelude::*;
 
 pub struct Author;
@@ -25,15 +31,91 @@ impl OnUnbalanced<NegativeImbalance> for Author {
 	}
 }
 
-/// A `HandleCredit` implementation that naively transfers the fees to the block author.
-/// Will drop and burn the assets in case the transfer fails.
-pub struct CreditToBlockAuthor;
-impl HandleCredit<AccountId, Assets> for CreditToBlockAuthor {
+/// The EVM `coinbase`: bytes 4 to 24 of the BABE key of the block author.
+pub struct BabeAuthorAsH160;
+impl FindAuthor<H160> for BabeAuthorAsH160 {
+	fn find_author<'a, I>(digests: I) -> Option<H160>
+	where
+		I: 'a + IntoIterator<Item = (ConsensusEngineId, &'a [u8])>,
+	{
+		let index = Babe::find_author(digests)?;
+		let (authority, _) = Babe::authorities().get(index as usize)?.clone();
+		Some(H160::from_slice(&authority.to_raw_vec()[4..24]))
+	}
+}
+
+/// A `HandleCredit` implementation that swaps the fees into NATIVEX through the `AssetConversion`
+/// pool of the asset and pays them to the block author.
+///
+/// The author is paid in the asset if the swap fails, and the assets are burned if that fails too.
+pub struct SwapCreditToBlockAuthor;
+impl HandleCredit<AccountId, Assets> for SwapCreditToBlockAuthor {
 	fn handle_credit(credit: Credit<AccountId, Assets>) {
 		if let Some(author) = pallet_authorship::Pallet::<Runtime>::author() {
-			// Drop the result which will trigger the `OnDrop` of the imbalance in case of error.
-			let _ = Assets::resolve(&author, credit);
+			System::register_extra_weight_unchecked(fee_swap_weight(), DispatchClass::Mandatory);
+			swap_credit_to(credit, &author);
+		}
+	}
+}
+
+/// The weight of the swap [`SwapCreditToBlockAuthor`] makes, which [`PoolPriceToAssetBalance`]
+/// charges to every transaction paying its fees in an asset.
+pub fn fee_swap_weight() -> Weight {
+	use pallet_asset_conversion::WeightInfo;
+	<Runtime as pallet_asset_conversion::Config>::WeightInfo::swap_exact_tokens_for_tokens()
+}
+
+/// Swap `credit` into NATIVEX through the pool of its asset, paying the NATIVEX to `who`.
+///
+/// The swap runs against the reserves the fee was just priced at by [`PoolPriceToAssetBalance`],
+/// within the same transaction, so `who` gets at least the native fee and no minimum has to be
+/// given. `who` is paid in the asset if the swap fails.
+pub fn swap_credit_to(credit: Credit<AccountId, Assets>, who: &AccountId) {
+	let asset = NativeOrAssetId::Asset(credit.asset());
+	let pool = AssetConversion::get_pool_account(&AssetConversion::get_pool_id(
+		NativeOrAssetId::Native,
+		asset,
+	));
+	let swapped = AssetConversion::get_reserves(&asset, &NativeOrAssetId::Native)
+		.and_then(|(reserve_in, reserve_out)| {
+			AssetConversion::get_amount_out(&credit.peek(), &reserve_in, &reserve_out)
+		})
+		.map_err(DispatchError::from)
+		.and_then(|amount_out| {
+			// The pool keeps at least the existential deposit, as `AssetConversion` swaps do.
+			<Balances as fungible::Mutate<_>>::transfer(
+				&pool,
+				who,
+				amount_out,
+				Preservation::Preserve,
+			)
+		});
+	let to = if swapped.is_ok() { &pool } else { who };
+	// Drop the result which will trigger the `OnDrop` of the imbalance in case of error.
+	let _ = Assets::resolve(to, credit);
+}
+
+/// Prices a native balance in an asset at the current `AssetConversion` pool rate, i.e. the
+/// amount of the asset needed to buy `balance` from the asset/NATIVEX pool, fees included.
+///
+/// A non-zero `balance` is a transaction fee, so the fee of the swap [`SwapCreditToBlockAuthor`]
+/// makes is added to it.
+pub struct PoolPriceToAssetBalance;
+impl BalanceConversion<Balance, u32, Balance> for PoolPriceToAssetBalance {
+	type Error = DispatchError;
+
+	fn to_asset_balance(balance: Balance, asset_id: u32) -> Result<Balance, Self::Error> {
+		if balance == 0 {
+			return Ok(0)
 		}
+		let balance = balance.saturating_add(TransactionPayment::weight_to_fee(fee_swap_weight()));
+		AssetConversion::quote_price_tokens_for_exact_tokens(
+			NativeOrAssetId::Asset(asset_id),
+			NativeOrAssetId::Native,
+			balance,
+			true,
+		)
+		.ok_or(DispatchError::Other("no asset conversion pool for the fee asset"))
 	}
 }
 
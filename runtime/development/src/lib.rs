@@ -17,7 +17,7 @@ pub use frame_support::{
 		tokens::{nonfungibles_v2::Inspect, GetSalary, PayFromAccount},
 		AsEnsureOriginWithArg, ConstBool, ConstU128, ConstU16, ConstU32, Currency, EitherOfDiverse,
 		EqualPrivilegeOnly, Everything, Imbalance, InstanceFilter, KeyOwnerProofSystem,
-		LockIdentifier, Nothing, OnUnbalanced, U128CurrencyToVote, WithdrawReasons,
+		LockIdentifier, Nothing, OnFinalize, OnUnbalanced, U128CurrencyToVote, WithdrawReasons,
 	},
 	weights::{
 		constants::{
@@ -32,6 +32,11 @@ pub use frame_system::{
 	limits::{BlockLength, BlockWeights},
 	EnsureRoot, EnsureRootWithSuccess, EnsureSigned, EnsureSignedBy, EnsureWithSuccess,
 };
+use pallet_asset_conversion::NativeOrAssetId;
+use pallet_ethereum::{
+	Call::transact, Transaction as EthereumTransaction, TransactionAction, TransactionData,
+};
+use pallet_evm::{Account as EVMAccount, FeeCalculator, Runner};
 use pallet_grandpa::AuthorityId as GrandpaId;
 use pallet_session::historical as pallet_session_historical;
 pub use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
@@ -39,16 +44,19 @@ use parity_scale_codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_api::impl_runtime_apis;
 use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
-use sp_core::{crypto::KeyTypeId, OpaqueMetadata, TryCollect};
+use sp_core::{crypto::KeyTypeId, OpaqueMetadata, TryCollect, H160, H256, U256};
 pub use sp_runtime::{
 	create_runtime_str,
 	curve::PiecewiseLinear,
 	generic, impl_opaque_keys,
 	traits::{
-		self, AccountIdConversion, BlakeTwo256, Block as BlockT, Bounded, ConvertInto, NumberFor,
-		OpaqueKeys, SaturatedConversion, StaticLookup,
+		self, AccountIdConversion, BlakeTwo256, Block as BlockT, Bounded, ConvertInto,
+		DispatchInfoOf, Dispatchable, NumberFor, OpaqueKeys, PostDispatchInfoOf,
+		SaturatedConversion, StaticLookup, UniqueSaturatedInto,
+	},
+	transaction_validity::{
+		TransactionPriority, TransactionSource, TransactionValidity, TransactionValidityError,
 	},
-	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, FixedPointNumber, FixedU128, Perbill, Percent, Permill, Perquintill,
 };
 use sp_std::prelude::*;
@@ -77,12 +85,19 @@ pub use sp_runtime::BuildStorage;
 pub mod impls;
 #[cfg(not(feature = "runtime-benchmarks"))]
 pub use impls::{AllianceIdentityVerifier, AllianceProposalProvider};
-pub use impls::{Author, CreditToBlockAuthor};
+pub use impls::{Author, BabeAuthorAsH160, PoolPriceToAssetBalance, SwapCreditToBlockAuthor};
 
 /// Define all pallets in the runtime
 mod pallets;
 pub use pallets::*;
 
+/// The precompiles of the EVM.
+pub mod precompiles;
+pub use precompiles::NativexPrecompiles;
+
+/// The state of an account across pallets, for `AccountOverviewApi`.
+mod account_overview;
+
 /// Constant values used within the runtime
 pub use runtime_common::{constants::currency::*, constants::time::*, *};
 use sp_runtime::generic::Era;
@@ -90,6 +105,12 @@ use sp_runtime::generic::Era;
 /// Generated voter bag information
 mod voter_bags;
 
+/// Named genesis presets served through the genesis builder runtime API.
+pub mod genesis_config_presets;
+
+/// Checks run around the runtime's migrations.
+pub mod migrations;
+
 /// Runtime API definition for assets.
 /// pub mod assets_api;
 
@@ -121,12 +142,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 109,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 4,
 	state_version: 1,
 };
 
@@ -228,7 +247,8 @@ impl frame_system::Config for Runtime {
 
 	/// The lookup mechanism to get account ID from whatever is passed in dispatchers.
 	//type Lookup = AccountIdLookup<AccountId, ()>;
-	type Lookup = Indices;
+	/// Indices, with `MultiAddress::Address20` resolved through `UnifiedAccounts`.
+	type Lookup = pallet_unified_accounts::UnifiedLookup<Runtime, Indices>;
 
 	/// The header type.
 	type Header = generic::Header<BlockNumber, BlakeTwo256>;
@@ -337,6 +357,62 @@ where
 	type OverarchingCall = RuntimeCall;
 }
 
+impl fp_self_contained::SelfContainedCall for RuntimeCall {
+	type SignedInfo = H160;
+
+	fn is_self_contained(&self) -> bool {
+		match self {
+			RuntimeCall::Ethereum(call) => call.is_self_contained(),
+			_ => false,
+		}
+	}
+
+	fn check_self_contained(&self) -> Option<Result<Self::SignedInfo, TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.check_self_contained(),
+			_ => None,
+		}
+	}
+
+	fn validate_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<TransactionValidity> {
+		match self {
+			RuntimeCall::Ethereum(call) => call.validate_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn pre_dispatch_self_contained(
+		&self,
+		info: &Self::SignedInfo,
+		dispatch_info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Option<Result<(), TransactionValidityError>> {
+		match self {
+			RuntimeCall::Ethereum(call) =>
+				call.pre_dispatch_self_contained(info, dispatch_info, len),
+			_ => None,
+		}
+	}
+
+	fn apply_self_contained(
+		self,
+		info: Self::SignedInfo,
+	) -> Option<sp_runtime::DispatchResultWithInfo<PostDispatchInfoOf<Self>>> {
+		match self {
+			call @ RuntimeCall::Ethereum(pallet_ethereum::Call::transact { .. }) =>
+				Some(call.dispatch(RuntimeOrigin::from(
+					pallet_ethereum::RawOrigin::EthereumTransaction(info),
+				))),
+			_ => None,
+		}
+	}
+}
+
 /// A reason for placing a hold on funds.
 #[derive(
 	Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Encode, Decode, MaxEncodedLen, Debug, TypeInfo,
@@ -425,6 +501,17 @@ construct_runtime!(
 
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		PoolAssets: pallet_assets::<Instance2>,
+		AssetConversion: pallet_asset_conversion,
+		AssetTreasury: pallet_asset_treasury,
+		EVM: pallet_evm,
+		Ethereum: pallet_ethereum,
+		BaseFee: pallet_base_fee,
+		EVMChainId: pallet_evm_chain_id,
+		UnifiedAccounts: pallet_unified_accounts,
+		Statement: pallet_statement,
+		Claims: pallet_claims,
+		ContractCalls: pallet_contract_calls,
 	}
 );
 /// The address format for describing accounts.
@@ -451,16 +538,37 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_asset_tx_payment::ChargeAssetTxPayment<Runtime>,
+	pallet_unified_accounts::PrevalidateBinding<Runtime>,
 );
 
+/// The [`SignedExtra`] of a transaction with `nonce`, valid for `era` and paying `tip` and its
+/// fees in the native currency.
+pub fn signed_extra(nonce: Index, era: Era, tip: Balance) -> SignedExtra {
+	(
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(era),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(tip, None),
+		pallet_unified_accounts::PrevalidateBinding::<Runtime>::new(),
+	)
+}
+
 /// Unchecked extrinsic type as expected by this runtime.
+///
+/// Ethereum transactions are self-contained: they carry their own signature and are unsigned
+/// from Substrate's point of view.
 pub type UncheckedExtrinsic =
-	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
+	fp_self_contained::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
 /// Extrinsic type that has already been checked.
-//pub type CheckedExtrinsic: generic::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra>;
+pub type CheckedExtrinsic =
+	fp_self_contained::CheckedExtrinsic<AccountId, RuntimeCall, SignedExtra, H160>;
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -472,11 +580,21 @@ pub type Executive = frame_executive::Executive<
 >;
 
 // All migrations executed on runtime upgrade as a nested tuple of types implementing
-// `OnRuntimeUpgrade`.
+// `OnRuntimeUpgrade`. Each one is wrapped in `migrations::Checked` so that `try-runtime`
+// verifies its storage version and idempotency; the audit must stay last.
 type Migrations = (
-	pallet_nomination_pools::migration::v2::MigrateToV2<Runtime>,
-	pallet_alliance::migration::Migration<Runtime>,
-	pallet_contracts::Migration<Runtime>,
+	migrations::Checked<
+		pallet_nomination_pools::migration::v2::MigrateToV2<Runtime>,
+		NominationPools,
+	>,
+	migrations::Checked<pallet_alliance::migration::Migration<Runtime>, Alliance>,
+	migrations::Checked<pallet_contracts::Migration<Runtime>, Contracts>,
+	migrations::Checked<
+		pallet_contract_calls::migration::v1::AllowCalls<Runtime, InitialContractCalls>,
+		ContractCalls,
+	>,
+	migrations::Checked<migrations::AssetPrecompileCodes, EVM>,
+	migrations::StorageVersionAudit,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -505,6 +623,14 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_template, TemplateModule]
+		[pallet_asset_conversion, AssetConversion]
+		[pallet_asset_treasury, AssetTreasury]
+		[pallet_unified_accounts, UnifiedAccounts]
+		[pallet_claims, Claims]
+		[pallet_contract_calls, ContractCalls]
+		[nativex_rand_extension, RandExtensionBench::<Runtime>]
+		[nativex_assets_extension, AssetsExtensionBench::<Runtime, Instance1>]
+		[nativex_nfts_extension, NftsExtensionBench::<Runtime>]
 	);
 }
 
@@ -574,6 +700,18 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl sp_statement_store::runtime_api::ValidateStatement<Block> for Runtime {
+		fn validate_statement(
+			source: sp_statement_store::runtime_api::StatementSource,
+			statement: sp_statement_store::Statement,
+		) -> Result<
+			sp_statement_store::runtime_api::ValidStatement,
+			sp_statement_store::runtime_api::InvalidStatement,
+		> {
+			Statement::validate_statement(source, statement)
+		}
+	}
+
 	impl sp_consensus_grandpa::GrandpaApi<Block> for Runtime {
 		fn grandpa_authorities() -> sp_consensus_grandpa::AuthorityList {
 			Grandpa::grandpa_authorities()
@@ -731,6 +869,349 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_asset_conversion::AssetConversionApi<
+		Block,
+		Balance,
+		u128,
+		NativeOrAssetId<u32>
+	> for Runtime
+	{
+		fn quote_price_exact_tokens_for_tokens(
+			asset1: NativeOrAssetId<u32>,
+			asset2: NativeOrAssetId<u32>,
+			amount: u128,
+			include_fee: bool,
+		) -> Option<Balance> {
+			AssetConversion::quote_price_exact_tokens_for_tokens(asset1, asset2, amount, include_fee)
+		}
+
+		fn quote_price_tokens_for_exact_tokens(
+			asset1: NativeOrAssetId<u32>,
+			asset2: NativeOrAssetId<u32>,
+			amount: u128,
+			include_fee: bool,
+		) -> Option<Balance> {
+			AssetConversion::quote_price_tokens_for_exact_tokens(asset1, asset2, amount, include_fee)
+		}
+
+		fn get_reserves(
+			asset1: NativeOrAssetId<u32>,
+			asset2: NativeOrAssetId<u32>,
+		) -> Option<(Balance, Balance)> {
+			AssetConversion::get_reserves(&asset1, &asset2).ok()
+		}
+	}
+
+	impl genesis_builder_runtime_api::GenesisBuilder<Block> for Runtime {
+		fn preset_names() -> Vec<genesis_builder_runtime_api::PresetId> {
+			genesis_config_presets::preset_names()
+		}
+
+		fn get_preset(id: genesis_builder_runtime_api::PresetId) -> Option<Vec<u8>> {
+			genesis_config_presets::get_preset(&id)
+		}
+	}
+
+	impl fee_estimation_runtime_api::FeeEstimationApi<Block, Balance> for Runtime {
+		fn estimate_call_fee(
+			call: Vec<u8>,
+			tip: Balance,
+		) -> Option<fee_estimation_runtime_api::FeeEstimate<Balance>> {
+			let call = RuntimeCall::decode(&mut &call[..]).ok()?;
+
+			// Wrap the call in a signed extrinsic of the largest realistic size so that the
+			// length fee is never underestimated.
+			let era = Era::mortal(BlockHashCount::get().into(), System::block_number().into());
+			let signature = Signature::Ecdsa(sp_core::ecdsa::Signature::from_raw([0u8; 65]));
+			let uxt = UncheckedExtrinsic::new_signed(
+				call.clone(),
+				Address::Id(AccountId::new([0u8; 32])),
+				signature,
+				signed_extra(Index::MAX, era, tip),
+			);
+			// Paying in an asset encodes its id, which only makes the extrinsic longer.
+			let len = (uxt.encoded_size() + u32::MAX.encoded_size()) as u32;
+
+			let details = TransactionPayment::query_call_fee_details(call, len);
+			let (base_fee, len_fee, adjusted_weight_fee) = details
+				.inclusion_fee
+				.map(|fee| (fee.base_fee, fee.len_fee, fee.adjusted_weight_fee))
+				.unwrap_or_default();
+			let total = base_fee
+				.saturating_add(len_fee)
+				.saturating_add(adjusted_weight_fee)
+				.saturating_add(tip);
+
+			Some(fee_estimation_runtime_api::FeeEstimate {
+				base_fee,
+				len_fee,
+				adjusted_weight_fee,
+				multiplier: TransactionPayment::next_fee_multiplier(),
+				tip,
+				total,
+			})
+		}
+	}
+
+	impl contracts_debug_runtime_api::ContractsDebugApi<Block, AccountId, Balance, Hash> for Runtime {
+		fn debug_call(
+			origin: AccountId,
+			dest: AccountId,
+			value: Balance,
+			gas_limit: Option<Weight>,
+			storage_deposit_limit: Option<Balance>,
+			input: Vec<u8>,
+		) -> contracts_debug_runtime_api::CallSummary<AccountId, Balance, Hash> {
+			pallets::contracts::debug_contract_call(
+				origin,
+				value,
+				gas_limit,
+				storage_deposit_limit,
+				pallets::contracts::DebugEntry::Call { dest, data: input },
+			)
+		}
+
+		fn debug_extrinsic(
+			extrinsic: <Block as BlockT>::Extrinsic,
+		) -> Option<contracts_debug_runtime_api::CallSummary<AccountId, Balance, Hash>> {
+			pallets::contracts::debug_contract_extrinsic(extrinsic)
+		}
+	}
+
+	impl account_overview_runtime_api::AccountOverviewApi<Block, AccountId, Balance, BlockNumber>
+		for Runtime
+	{
+		fn account_overview(
+			account: AccountId,
+		) -> account_overview_runtime_api::AccountOverview<AccountId, Balance, BlockNumber> {
+			account_overview::account_overview(account)
+		}
+	}
+
+	impl unified_accounts_runtime_api::UnifiedAccountsApi<Block, AccountId> for Runtime {
+		fn resolve_evm_address(
+			evm_address: H160,
+		) -> unified_accounts_runtime_api::ResolvedAccount<AccountId> {
+			let account = UnifiedAccounts::native_account(&evm_address);
+			let bound = UnifiedAccounts::evm_address(&account) == Some(evm_address);
+			unified_accounts_runtime_api::ResolvedAccount {
+				account,
+				evm_address: Some(evm_address),
+				bound,
+			}
+		}
+
+		fn resolve_account(
+			account: AccountId,
+		) -> unified_accounts_runtime_api::ResolvedAccount<AccountId> {
+			let evm_address = UnifiedAccounts::evm_address(&account);
+			let bound = evm_address.is_some();
+			unified_accounts_runtime_api::ResolvedAccount { account, evm_address, bound }
+		}
+	}
+
+	impl fp_rpc::EthereumRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			<Runtime as pallet_evm::Config>::ChainId::get()
+		}
+
+		fn account_basic(address: H160) -> EVMAccount {
+			let (account, _) = pallet_evm::Pallet::<Runtime>::account_basic(&address);
+			account
+		}
+
+		fn gas_price() -> U256 {
+			let (gas_price, _) = <Runtime as pallet_evm::Config>::FeeCalculator::min_gas_price();
+			gas_price
+		}
+
+		fn account_code_at(address: H160) -> Vec<u8> {
+			pallet_evm::AccountCodes::<Runtime>::get(address)
+		}
+
+		fn author() -> H160 {
+			<pallet_evm::Pallet<Runtime>>::find_author()
+		}
+
+		fn storage_at(address: H160, index: U256) -> H256 {
+			let mut tmp = [0u8; 32];
+			index.to_big_endian(&mut tmp);
+			pallet_evm::AccountStorages::<Runtime>::get(address, H256::from_slice(&tmp[..]))
+		}
+
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CallInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			let gas_limit = gas_limit.min(u64::MAX.into());
+			let transaction_data = TransactionData::new(
+				TransactionAction::Call(to),
+				data.clone(),
+				nonce.unwrap_or_default(),
+				gas_limit,
+				None,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				value,
+				Some(<Runtime as pallet_evm::Config>::ChainId::get()),
+				access_list.clone().unwrap_or_default(),
+			);
+			let (weight_limit, proof_size_base_cost) =
+				pallet_ethereum::Pallet::<Runtime>::transaction_weight(&transaction_data);
+
+			<Runtime as pallet_evm::Config>::Runner::call(
+				from,
+				to,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				false,
+				true,
+				weight_limit,
+				proof_size_base_cost,
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			)
+			.map_err(|err| err.error.into())
+		}
+
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: U256,
+			gas_limit: U256,
+			max_fee_per_gas: Option<U256>,
+			max_priority_fee_per_gas: Option<U256>,
+			nonce: Option<U256>,
+			estimate: bool,
+			access_list: Option<Vec<(H160, Vec<H256>)>>,
+		) -> Result<pallet_evm::CreateInfo, sp_runtime::DispatchError> {
+			let config = if estimate {
+				let mut config = <Runtime as pallet_evm::Config>::config().clone();
+				config.estimate = true;
+				Some(config)
+			} else {
+				None
+			};
+
+			let gas_limit = gas_limit.min(u64::MAX.into());
+			let transaction_data = TransactionData::new(
+				TransactionAction::Create,
+				data.clone(),
+				nonce.unwrap_or_default(),
+				gas_limit,
+				None,
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				value,
+				Some(<Runtime as pallet_evm::Config>::ChainId::get()),
+				access_list.clone().unwrap_or_default(),
+			);
+			let (weight_limit, proof_size_base_cost) =
+				pallet_ethereum::Pallet::<Runtime>::transaction_weight(&transaction_data);
+
+			<Runtime as pallet_evm::Config>::Runner::create(
+				from,
+				data,
+				value,
+				gas_limit.unique_saturated_into(),
+				max_fee_per_gas,
+				max_priority_fee_per_gas,
+				nonce,
+				access_list.unwrap_or_default(),
+				false,
+				true,
+				weight_limit,
+				proof_size_base_cost,
+				config.as_ref().unwrap_or(<Runtime as pallet_evm::Config>::config()),
+			)
+			.map_err(|err| err.error.into())
+		}
+
+		fn current_transaction_statuses() -> Option<Vec<fp_rpc::TransactionStatus>> {
+			pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get()
+		}
+
+		fn current_block() -> Option<pallet_ethereum::Block> {
+			pallet_ethereum::CurrentBlock::<Runtime>::get()
+		}
+
+		fn current_receipts() -> Option<Vec<pallet_ethereum::Receipt>> {
+			pallet_ethereum::CurrentReceipts::<Runtime>::get()
+		}
+
+		fn current_all() -> (
+			Option<pallet_ethereum::Block>,
+			Option<Vec<pallet_ethereum::Receipt>>,
+			Option<Vec<fp_rpc::TransactionStatus>>,
+		) {
+			(
+				pallet_ethereum::CurrentBlock::<Runtime>::get(),
+				pallet_ethereum::CurrentReceipts::<Runtime>::get(),
+				pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get(),
+			)
+		}
+
+		fn extrinsic_filter(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> Vec<EthereumTransaction> {
+			xts.into_iter()
+				.filter_map(|xt| match xt.0.function {
+					RuntimeCall::Ethereum(transact { transaction }) => Some(transaction),
+					_ => None,
+				})
+				.collect()
+		}
+
+		fn elasticity() -> Option<Permill> {
+			Some(pallet_base_fee::Elasticity::<Runtime>::get())
+		}
+
+		fn gas_limit_multiplier_support() {}
+
+		fn pending_block(
+			xts: Vec<<Block as BlockT>::Extrinsic>,
+		) -> (Option<pallet_ethereum::Block>, Option<Vec<fp_rpc::TransactionStatus>>) {
+			for ext in xts.into_iter() {
+				let _ = Executive::apply_extrinsic(ext);
+			}
+
+			Ethereum::on_finalize(System::block_number() + 1);
+
+			(
+				pallet_ethereum::CurrentBlock::<Runtime>::get(),
+				pallet_ethereum::CurrentTransactionStatuses::<Runtime>::get(),
+			)
+		}
+	}
+
+	impl fp_rpc::ConvertTransactionRuntimeApi<Block> for Runtime {
+		fn convert_transaction(transaction: EthereumTransaction) -> <Block as BlockT>::Extrinsic {
+			UncheckedExtrinsic::new_unsigned(
+				pallet_ethereum::Call::<Runtime>::transact { transaction }.into(),
+			)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -741,6 +1222,9 @@ impl_runtime_apis! {
 			use frame_support::traits::StorageInfoTrait;
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use baseline::Pallet as BaselineBench;
+			use nativex_rand_extension::benchmarking::Pallet as RandExtensionBench;
+			use nativex_assets_extension::benchmarking::Pallet as AssetsExtensionBench;
+			use nativex_nfts_extension::benchmarking::Pallet as NftsExtensionBench;
 
 			let mut list = Vec::<BenchmarkList>::new();
 			list_benchmarks!(list, extra);
@@ -757,9 +1241,15 @@ impl_runtime_apis! {
 
 			use frame_system_benchmarking::Pallet as SystemBench;
 			use baseline::Pallet as BaselineBench;
+			use nativex_rand_extension::benchmarking::Pallet as RandExtensionBench;
+			use nativex_assets_extension::benchmarking::Pallet as AssetsExtensionBench;
+			use nativex_nfts_extension::benchmarking::Pallet as NftsExtensionBench;
 
 			impl frame_system_benchmarking::Config for Runtime {}
 			impl baseline::Config for Runtime {}
+			impl nativex_rand_extension::benchmarking::Config for Runtime {}
+			impl nativex_assets_extension::benchmarking::Config<Instance1> for Runtime {}
+			impl nativex_nfts_extension::benchmarking::Config for Runtime {}
 
 			use frame_support::traits::WhitelistedStorageKeys;
 			let whitelist: Vec<TrackedStorageKey> = AllPalletsWithSystem::whitelisted_storage_keys();
@@ -798,8 +1288,19 @@ impl_runtime_apis! {
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use frame_support::traits::WhitelistedStorageKeys;
+	use frame_support::{
+		assert_ok,
+		dispatch::GetDispatchInfo,
+		traits::{
+			fungibles::Balanced,
+			tokens::{BalanceConversion, Fortitude, Precision, Preservation},
+			Contains, WhitelistedStorageKeys,
+		},
+	};
+	use account_overview_runtime_api as api;
+	use parity_scale_codec::Encode;
 	use sp_core::hexdisplay::HexDisplay;
+	use sp_runtime::{traits::SignedExtension, transaction_validity::InvalidTransaction};
 	use std::collections::HashSet;
 
 	#[test]
@@ -830,4 +1331,617 @@ mod tests {
 			whitelist.contains("26aa394eea5630e07c48ae0c9558cef780d41e5e16056765bc8461851072c9d7")
 		);
 	}
+
+	#[test]
+	fn contract_calls_keep_their_indices() {
+		let account = AccountId::from([1u8; 32]);
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: account.clone().into(),
+			value: 1,
+		});
+		let calls = vec![
+			(transfer.clone(), [10, 3]),
+			(
+				RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+					dest: account.clone().into(),
+					keep_alive: true,
+				}),
+				[10, 4],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::transfer {
+					id: 1.into(),
+					target: account.clone().into(),
+					amount: 1,
+				}),
+				[38, 8],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::transfer_keep_alive {
+					id: 1.into(),
+					target: account.clone().into(),
+					amount: 1,
+				}),
+				[38, 9],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::approve_transfer {
+					id: 1.into(),
+					delegate: account.clone().into(),
+					amount: 1,
+				}),
+				[38, 22],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::cancel_approval {
+					id: 1.into(),
+					delegate: account.clone().into(),
+				}),
+				[38, 23],
+			),
+			(
+				RuntimeCall::Assets(pallet_assets::Call::transfer_approved {
+					id: 1.into(),
+					owner: account.clone().into(),
+					destination: account.clone().into(),
+					amount: 1,
+				}),
+				[38, 25],
+			),
+			(
+				RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![transfer.clone()] }),
+				[1, 0],
+			),
+			(
+				RuntimeCall::Utility(pallet_utility::Call::batch_all {
+					calls: vec![transfer.clone()],
+				}),
+				[1, 2],
+			),
+			(
+				RuntimeCall::Utility(pallet_utility::Call::force_batch {
+					calls: vec![transfer.clone()],
+				}),
+				[1, 4],
+			),
+			(
+				RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+					real: account.clone().into(),
+					force_proxy_type: None,
+					call: Box::new(transfer.clone()),
+				}),
+				[34, 0],
+			),
+			(
+				RuntimeCall::Proxy(pallet_proxy::Call::remove_proxy {
+					delegate: account.clone().into(),
+					proxy_type: ProxyType::Any,
+					delay: 0,
+				}),
+				[34, 2],
+			),
+			(RuntimeCall::Proxy(pallet_proxy::Call::remove_proxies {}), [34, 3]),
+			(
+				RuntimeCall::Remark(pallet_remark::Call::store { remark: b"nativex".to_vec() }),
+				[60, 0],
+			),
+		];
+
+		assert_eq!(
+			calls.iter().map(|(_, [pallet, call])| (*pallet, *call)).collect::<Vec<_>>(),
+			InitialContractCalls::get()
+		);
+		new_test_ext().execute_with(|| {
+			for (call, indices) in calls {
+				assert!(
+					ContractCallFilter::contains(&call),
+					"{:?} is not allowed for contracts",
+					call
+				);
+				assert_eq!(call.encode()[..2], indices, "pallet or call index of {:?} moved", call);
+			}
+		});
+	}
+
+	#[test]
+	fn contract_call_filter_rejects_other_calls() {
+		let account = AccountId::from([1u8; 32]);
+		let remark = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+		let calls = vec![
+			remark.clone(),
+			RuntimeCall::Balances(pallet_balances::Call::transfer_all {
+				dest: account.clone().into(),
+				keep_alive: false,
+			}),
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![remark.clone()] }),
+			RuntimeCall::Proxy(pallet_proxy::Call::proxy {
+				real: account.clone().into(),
+				force_proxy_type: None,
+				call: Box::new(remark),
+			}),
+			RuntimeCall::Proxy(pallet_proxy::Call::add_proxy {
+				delegate: account.into(),
+				proxy_type: ProxyType::NonTransfer,
+				delay: 0,
+			}),
+		];
+
+		new_test_ext().execute_with(|| {
+			// Governance can't allow contracts to hand their account over.
+			assert_ok!(ContractCalls::allow_call(RuntimeOrigin::root(), (34, 1)));
+			for call in calls {
+				assert!(
+					!ContractCallFilter::contains(&call),
+					"{:?} is allowed for contracts",
+					call
+				);
+			}
+		});
+	}
+
+	#[test]
+	fn governance_changes_the_contract_calls() {
+		let transfer = RuntimeCall::Balances(pallet_balances::Call::transfer_keep_alive {
+			dest: AccountId::from([1u8; 32]).into(),
+			value: 1,
+		});
+		let batch =
+			RuntimeCall::Utility(pallet_utility::Call::batch { calls: vec![transfer.clone()] });
+
+		new_test_ext().execute_with(|| {
+			assert!(ContractCallFilter::contains(&batch));
+			assert_ok!(ContractCalls::disallow_call(RuntimeOrigin::root(), (10, 3)));
+			assert!(!ContractCallFilter::contains(&transfer));
+			assert!(!ContractCallFilter::contains(&batch));
+			assert_ok!(ContractCalls::allow_call(RuntimeOrigin::root(), (10, 3)));
+			assert!(ContractCallFilter::contains(&batch));
+		});
+	}
+
+	fn new_test_ext() -> sp_io::TestExternalities {
+		let storage = GenesisConfig {
+			babe: BabeConfig {
+				authorities: vec![],
+				epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+			},
+			contract_calls: ContractCallsConfig { allowed_calls: InitialContractCalls::get() },
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+		sp_io::TestExternalities::new(storage)
+	}
+
+	#[test]
+	fn account_overview_reports_the_state_of_every_pallet() {
+		use frame_support::traits::{
+			fungible::MutateHold, LockableCurrency, NamedReservableCurrency, WithdrawReasons,
+		};
+		use pallet_identity::{Data, IdentityInfo, Judgement};
+		use sp_runtime::traits::Hash as _;
+
+		let [alice, bob, charlie, dave, eve] =
+			[1u8, 2, 3, 4, 5].map(|byte| AccountId::from([byte; 32]));
+		let storage = GenesisConfig {
+			babe: BabeConfig {
+				authorities: vec![],
+				epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+			},
+			balances: BalancesConfig {
+				balances: [&alice, &bob, &charlie, &dave]
+					.map(|who| (who.clone(), 10_000 * NATIVEX))
+					.to_vec(),
+			},
+			staking: StakingConfig {
+				stakers: vec![
+					(bob.clone(), bob.clone(), 1_000 * NATIVEX, StakerStatus::Validator),
+					(
+						alice.clone(),
+						alice.clone(),
+						1_000 * NATIVEX,
+						StakerStatus::Nominator(vec![bob.clone()]),
+					),
+				],
+				..Default::default()
+			},
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			System::set_block_number(1);
+			let signed = |who: &AccountId| RuntimeOrigin::signed(who.clone());
+
+			// Balances.
+			assert_ok!(Balances::reserve_named(b"overview", &alice, 5));
+			assert_ok!(Balances::hold(&HoldReason::Nis, &alice, 7));
+			Balances::set_lock(*b"overview", &alice, 11, WithdrawReasons::all());
+			// Assets and items.
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				7.into(),
+				alice.clone().into(),
+				true,
+				1,
+			));
+			assert_ok!(Assets::force_set_metadata(
+				RuntimeOrigin::root(),
+				7.into(),
+				b"Seven".to_vec(),
+				b"SVN".to_vec(),
+				3,
+				false,
+			));
+			assert_ok!(Assets::mint(signed(&alice), 7.into(), alice.clone().into(), 100));
+			assert_ok!(Nfts::create(signed(&alice), alice.clone().into(), Default::default()));
+			assert_ok!(Nfts::mint(signed(&alice), 0, 1, alice.clone().into(), None));
+			assert_ok!(Uniques::create(signed(&alice), 0, alice.clone().into()));
+			assert_ok!(Uniques::mint(signed(&alice), 0, 2, alice.clone().into()));
+			// Staking, pools and vesting.
+			assert_ok!(Staking::unbond(signed(&alice), 100 * NATIVEX));
+			assert_ok!(NominationPools::create(
+				signed(&dave),
+				100 * NATIVEX,
+				dave.clone().into(),
+				dave.clone().into(),
+				dave.clone().into(),
+			));
+			let schedule = pallet_vesting::VestingInfo::new(200 * NATIVEX, NATIVEX, 10);
+			assert_ok!(Vesting::vested_transfer(signed(&charlie), alice.clone().into(), schedule));
+			// Proxies and identities.
+			assert_ok!(Proxy::add_proxy(signed(&alice), bob.clone().into(), ProxyType::Any, 3));
+			let info = IdentityInfo {
+				display: Data::Raw(b"alice".to_vec().try_into().unwrap()),
+				..Default::default()
+			};
+			assert_ok!(Identity::set_identity(signed(&alice), Box::new(info.clone())));
+			let eve_name = Data::Raw(b"eve".to_vec().try_into().unwrap());
+			assert_ok!(Identity::set_subs(signed(&alice), vec![(eve.clone(), eve_name)]));
+			assert_ok!(Identity::add_registrar(RuntimeOrigin::root(), bob.clone().into()));
+			assert_ok!(Identity::request_judgement(signed(&alice), 0, 0));
+			assert_ok!(Identity::provide_judgement(
+				signed(&bob),
+				0,
+				alice.clone().into(),
+				Judgement::Reasonable,
+				BlakeTwo256::hash_of(&info),
+			));
+
+			let overview = account_overview::account_overview(alice.clone());
+			let account = System::account(&alice).data;
+			let balances = overview.balances;
+			assert_eq!(
+				(balances.free, balances.reserved, balances.frozen),
+				(account.free, account.reserved, account.frozen)
+			);
+			assert_eq!(balances.frozen, 1_000 * NATIVEX);
+			let lock = |id: &[u8; 8]| {
+				let lock = balances.locks.iter().find(|lock| &lock.id == id)?;
+				Some((lock.amount, lock.reasons))
+			};
+			assert_eq!(lock(b"overview"), Some((11, api::LockReasons::All)));
+			assert_eq!(lock(b"staking "), Some((1_000 * NATIVEX, api::LockReasons::All)));
+			// Unvested funds can still pay fees.
+			assert_eq!(lock(b"vesting "), Some((200 * NATIVEX, api::LockReasons::Misc)));
+			assert_eq!(balances.freezes, vec![]);
+			assert_eq!(
+				balances.reserves,
+				vec![api::NamedAmount { id: b"overview".to_vec(), amount: 5 }]
+			);
+			assert_eq!(
+				balances.holds,
+				vec![api::NamedAmount { id: HoldReason::Nis.encode(), amount: 7 }]
+			);
+
+			assert_eq!(
+				overview.assets,
+				vec![api::AssetBalance {
+					asset_id: 7,
+					balance: 100,
+					symbol: b"SVN".to_vec(),
+					decimals: 3,
+				}]
+			);
+			assert_eq!(overview.nfts, vec![api::Item { collection: 0, item: 1 }]);
+			assert_eq!(overview.uniques, vec![api::Item { collection: 0, item: 2 }]);
+
+			assert_eq!(
+				overview.staking,
+				Some(api::Staking {
+					stash: alice.clone(),
+					controller: alice.clone(),
+					total: 1_000 * NATIVEX,
+					active: 900 * NATIVEX,
+					unlocking: vec![api::Unbonding {
+						value: 100 * NATIVEX,
+						era: BondingDuration::get(),
+					}],
+					nominations: Some(vec![bob.clone()]),
+					validator: false,
+				})
+			);
+			assert!(account_overview::account_overview(bob.clone()).staking.unwrap().validator);
+			assert_eq!(overview.nomination_pool, None);
+			assert_eq!(
+				account_overview::account_overview(dave.clone()).nomination_pool,
+				Some(api::PoolMembership {
+					pool_id: 1,
+					points: 100 * NATIVEX,
+					balance: 100 * NATIVEX,
+					pending_rewards: 0,
+					unbonding: vec![],
+				})
+			);
+			assert_eq!(
+				overview.vesting,
+				vec![api::VestingSchedule {
+					locked: 200 * NATIVEX,
+					per_block: NATIVEX,
+					starting_block: 10,
+				}]
+			);
+
+			let (_, proxy_deposit) = Proxy::proxies(&alice);
+			assert_eq!(
+				overview.proxies,
+				vec![api::Proxy { delegate: bob.clone(), proxy_type: b"Any".to_vec(), delay: 3 }]
+			);
+			assert_eq!(overview.proxy_deposit, proxy_deposit);
+			assert!(proxy_deposit > 0);
+
+			assert_eq!(
+				overview.identity,
+				Some(api::Identity {
+					display: Some(b"alice".to_vec()),
+					parent: None,
+					verified: true,
+					deposit: BasicDeposit::get(),
+					subs_deposit: SubAccountDeposit::get(),
+				})
+			);
+			assert_eq!(
+				account_overview::account_overview(eve).identity,
+				Some(api::Identity {
+					display: Some(b"eve".to_vec()),
+					parent: Some(alice),
+					verified: false,
+					deposit: 0,
+					subs_deposit: 0,
+				})
+			);
+			assert_eq!(account_overview::account_overview(charlie).identity, None);
+		});
+	}
+
+	#[test]
+	fn storage_versions_match_at_genesis() {
+		new_test_ext().execute_with(|| {
+			assert_eq!(migrations::mismatched_storage_versions(), vec![]);
+		});
+	}
+
+	#[test]
+	fn migration_stores_the_precompile_code_of_existing_assets() {
+		use frame_support::traits::OnRuntimeUpgrade;
+		use migrations::AssetPrecompileCodes;
+		use nativex_assets_erc20_precompile::{asset_address, ASSET_PRECOMPILE_CODE};
+
+		new_test_ext().execute_with(|| {
+			let owner = AccountId::from([1u8; 32]);
+			for id in [3u32, 4] {
+				assert_ok!(Assets::force_create(
+					RuntimeOrigin::root(),
+					id.into(),
+					owner.clone().into(),
+					true,
+					1,
+				));
+			}
+			// Asset 3 was created before the assets callback stored the code.
+			pallet_evm::AccountCodes::<Runtime>::remove(asset_address(3u32));
+
+			let weight = AssetPrecompileCodes::on_runtime_upgrade();
+			let db = <Runtime as frame_system::Config>::DbWeight::get();
+			assert_eq!(weight, db.reads_writes(4, 1));
+			for id in [3u32, 4] {
+				let code = pallet_evm::AccountCodes::<Runtime>::get(asset_address(id));
+				assert_eq!(code, ASSET_PRECOMPILE_CODE);
+			}
+
+			assert_eq!(AssetPrecompileCodes::on_runtime_upgrade(), db.reads_writes(4, 0));
+		});
+	}
+
+	#[test]
+	fn genesis_assets_of_the_presets_have_the_precompile_code() {
+		use genesis_builder_runtime_api::DEV_PRESET;
+		use nativex_assets_erc20_precompile::{asset_address, ASSET_PRECOMPILE_CODE};
+
+		let preset = genesis_config_presets::get_preset(&DEV_PRESET.as_bytes().to_vec()).unwrap();
+		let preset: serde_json::Value = serde_json::from_slice(&preset).unwrap();
+		let storage = GenesisConfig {
+			babe: BabeConfig {
+				authorities: vec![],
+				epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+			},
+			evm: serde_json::from_value(preset["evm"].clone()).unwrap(),
+			..Default::default()
+		}
+		.build_storage()
+		.unwrap();
+
+		assert_eq!(preset["assets"]["assets"][0][0], 9);
+		sp_io::TestExternalities::new(storage).execute_with(|| {
+			assert_eq!(
+				pallet_evm::AccountCodes::<Runtime>::get(asset_address(9u32)),
+				ASSET_PRECOMPILE_CODE
+			);
+		});
+	}
+
+	const FEE_ASSET: u32 = 7;
+
+	/// Run `test` with an account holding NATIVEX and `FEE_ASSET`, which also provided a 1:2
+	/// NATIVEX/`FEE_ASSET` pool.
+	fn with_fee_asset_pool(test: impl FnOnce(AccountId)) {
+		new_test_ext().execute_with(|| {
+			System::set_block_number(1);
+			let alice = AccountId::from([1u8; 32]);
+			let origin = RuntimeOrigin::signed(alice.clone());
+			Balances::make_free_balance_be(&alice, 1_000 * NATIVEX);
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				FEE_ASSET.into(),
+				alice.clone().into(),
+				true,
+				1,
+			));
+			assert_ok!(Assets::mint(
+				origin.clone(),
+				FEE_ASSET.into(),
+				alice.clone().into(),
+				1_000 * NATIVEX,
+			));
+			assert_ok!(AssetConversion::create_pool(
+				origin.clone(),
+				NativeOrAssetId::Native,
+				NativeOrAssetId::Asset(FEE_ASSET),
+			));
+			assert_ok!(AssetConversion::add_liquidity(
+				origin,
+				NativeOrAssetId::Native,
+				NativeOrAssetId::Asset(FEE_ASSET),
+				100 * NATIVEX,
+				200 * NATIVEX,
+				1,
+				1,
+				alice.clone(),
+			));
+			test(alice)
+		});
+	}
+
+	#[test]
+	fn asset_fees_are_priced_through_the_pool() {
+		with_fee_asset_pool(|_| {
+			let fee = NATIVEX / 100;
+			let price = PoolPriceToAssetBalance::to_asset_balance(fee, FEE_ASSET).unwrap();
+			// The fee pays for swapping the asset it is paid in too.
+			let fee = fee + TransactionPayment::weight_to_fee(impls::fee_swap_weight());
+			// Buying `fee` out of a 1:2 pool costs a bit more than twice as much, for the LP fee
+			// and the slippage.
+			assert!(price > 2 * fee && price < 2 * fee + fee / 50, "{price}");
+			assert_eq!(
+				Some(price),
+				AssetConversion::quote_price_tokens_for_exact_tokens(
+					NativeOrAssetId::Asset(FEE_ASSET),
+					NativeOrAssetId::Native,
+					fee,
+					true,
+				),
+			);
+			assert_eq!(PoolPriceToAssetBalance::to_asset_balance(0, FEE_ASSET), Ok(0));
+			assert!(PoolPriceToAssetBalance::to_asset_balance(fee, FEE_ASSET + 1).is_err());
+		});
+	}
+
+	#[test]
+	fn signed_extra_charges_asset_fees_at_the_pool_price() {
+		with_fee_asset_pool(|alice| {
+			let call = RuntimeCall::System(frame_system::Call::remark { remark: vec![] });
+			let info = call.get_dispatch_info();
+			let len = 100;
+			let fee = TransactionPayment::compute_fee(len as u32, &info, 0);
+			let price = PoolPriceToAssetBalance::to_asset_balance(fee, FEE_ASSET).unwrap();
+			let native = Balances::free_balance(&alice);
+			let assets = Assets::balance(FEE_ASSET, &alice);
+
+			let pre = pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
+				0,
+				Some(FEE_ASSET),
+			)
+			.pre_dispatch(&alice, &call, &info, len)
+			.unwrap();
+			assert_eq!(Assets::balance(FEE_ASSET, &alice), assets - price);
+			assert_ok!(pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::post_dispatch(
+				Some(pre),
+				&info,
+				&Default::default(),
+				len,
+				&Ok(()),
+			));
+			assert_eq!(Assets::balance(FEE_ASSET, &alice), assets - price);
+			assert_eq!(Balances::free_balance(&alice), native);
+
+			// Without a pool the fee can't be priced, so the transaction is rejected.
+			let unpriced = pallet_asset_tx_payment::ChargeAssetTxPayment::<Runtime>::from(
+				0,
+				Some(FEE_ASSET + 1),
+			)
+			.pre_dispatch(&alice, &call, &info, len);
+			assert!(matches!(
+				unpriced,
+				Err(TransactionValidityError::Invalid(InvalidTransaction::Payment))
+			));
+		});
+	}
+
+	/// Withdraw `amount` of `asset` from `who`, as the fee of a transaction.
+	fn withdraw(
+		asset: u32,
+		who: &AccountId,
+		amount: Balance,
+	) -> frame_support::traits::fungibles::Credit<AccountId, Assets> {
+		<Assets as Balanced<_>>::withdraw(
+			asset,
+			who,
+			amount,
+			Precision::Exact,
+			Preservation::Expendable,
+			Fortitude::Polite,
+		)
+		.unwrap()
+	}
+
+	#[test]
+	fn asset_fees_are_swapped_into_native_through_the_pool() {
+		with_fee_asset_pool(|alice| {
+			let author = AccountId::from([2u8; 32]);
+			Balances::make_free_balance_be(&author, NATIVEX);
+			let fee = NATIVEX / 100;
+			let price = PoolPriceToAssetBalance::to_asset_balance(fee, FEE_ASSET).unwrap();
+			let pool = AssetConversion::get_pool_account(&AssetConversion::get_pool_id(
+				NativeOrAssetId::Native,
+				NativeOrAssetId::Asset(FEE_ASSET),
+			));
+			let pool_assets = Assets::balance(FEE_ASSET, &pool);
+
+			let credit = withdraw(FEE_ASSET, &alice, price);
+			impls::swap_credit_to(credit, &author);
+			// The author gets at least the native fee, and the pool keeps the asset.
+			assert!(Balances::free_balance(&author) >= NATIVEX + fee);
+			assert_eq!(Assets::balance(FEE_ASSET, &author), 0);
+			assert_eq!(Assets::balance(FEE_ASSET, &pool), pool_assets + price);
+
+			// Without a pool the author is paid in the asset.
+			let other = FEE_ASSET + 1;
+			assert_ok!(Assets::force_create(
+				RuntimeOrigin::root(),
+				other.into(),
+				alice.clone().into(),
+				true,
+				1,
+			));
+			assert_ok!(Assets::mint(
+				RuntimeOrigin::signed(alice.clone()),
+				other.into(),
+				alice.clone().into(),
+				100,
+			));
+			let credit = withdraw(other, &alice, 100);
+			impls::swap_credit_to(credit, &author);
+			assert_eq!(Assets::balance(other, &author), 100);
+		});
+	}
 }
@@ -0,0 +1,236 @@
+//! Runtime upgrade checks for the [`Migrations`](crate::Migrations) tuple.
+//!
+//! Every migration is wrapped in [`Checked`], which verifies with `try-runtime` that the pallet
+//! it migrates ends up at its in-code storage version and that running the migration a second
+//! time leaves storage untouched. [`StorageVersionAudit`] runs last and fails the upgrade if any
+//! pallet's on-chain storage version still differs from its in-code version.
+
+use crate::*;
+use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion};
+use nativex_assets_erc20_precompile::{asset_address, ASSET_PRECOMPILE_CODE};
+use sp_std::marker::PhantomData;
+#[cfg(feature = "try-runtime")]
+use frame_support::{
+	ensure,
+	storage::{with_transaction, TransactionOutcome},
+};
+
+#[cfg(feature = "try-runtime")]
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// Wraps migration `M` of pallet `P` with `try-runtime` checks:
+///
+/// - `M`'s own `pre_upgrade`/`post_upgrade` hooks;
+/// - after the upgrade, the on-chain storage version of `P` equals its in-code version;
+/// - running `M` again does not change the storage root, i.e. `M` is idempotent.
+pub struct Checked<M, P>(PhantomData<(M, P)>);
+
+impl<M, P> OnRuntimeUpgrade for Checked<M, P>
+where
+	M: OnRuntimeUpgrade,
+	P: GetStorageVersion + PalletInfoAccess,
+{
+	fn on_runtime_upgrade() -> Weight {
+		M::on_runtime_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+		log::info!(
+			target: LOG_TARGET,
+			"{}: on-chain storage version {:?} before upgrade, in-code {:?}",
+			P::name(),
+			P::on_chain_storage_version(),
+			P::current_storage_version(),
+		);
+		M::pre_upgrade()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+		M::post_upgrade(state)?;
+
+		let (on_chain, current) = (P::on_chain_storage_version(), P::current_storage_version());
+		if on_chain != current {
+			log::error!(
+				target: LOG_TARGET,
+				"{}: on-chain storage version {:?} differs from in-code {:?} after upgrade",
+				P::name(),
+				on_chain,
+				current,
+			);
+			return Err("Storage version mismatch after migration")
+		}
+
+		// Run the migration again and roll it back: a migration that is safe to leave in the
+		// tuple across upgrades must not touch storage the second time.
+		let before = sp_io::storage::root(VERSION.state_version());
+		let after = with_transaction(|| -> TransactionOutcome<Result<_, &'static str>> {
+			M::on_runtime_upgrade();
+			TransactionOutcome::Rollback(Ok(sp_io::storage::root(VERSION.state_version())))
+		})?;
+		ensure!(before == after, "Migration is not idempotent");
+
+		Ok(())
+	}
+}
+
+/// Stores the ERC-20 precompile code at the address of every asset, so that assets created before
+/// [`AssetPrecompileCode`](nativex_assets_erc20_precompile::AssetPrecompileCode) was the assets
+/// callback are callable from the EVM as well. Assets that already have the code are skipped.
+pub struct AssetPrecompileCodes;
+
+impl OnRuntimeUpgrade for AssetPrecompileCodes {
+	fn on_runtime_upgrade() -> Weight {
+		let code = Vec::from(ASSET_PRECOMPILE_CODE);
+		let (mut reads, mut writes) = (0, 0);
+		for id in pallet_assets::Asset::<Runtime, Instance1>::iter_keys() {
+			let address = asset_address(id);
+			reads += 2;
+			if pallet_evm::AccountCodes::<Runtime>::get(address) != code {
+				pallet_evm::AccountCodes::<Runtime>::insert(address, code.clone());
+				writes += 1;
+			}
+		}
+		<Runtime as frame_system::Config>::DbWeight::get().reads_writes(reads, writes)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		ensure!(
+			pallet_assets::Asset::<Runtime, Instance1>::iter_keys().all(|id| {
+				pallet_evm::AccountCodes::<Runtime>::get(asset_address(id)) == ASSET_PRECOMPILE_CODE
+			}),
+			"Asset without precompile code"
+		);
+		Ok(())
+	}
+}
+
+/// Lists every pallet of the runtime together with its on-chain and in-code storage versions.
+macro_rules! storage_versions {
+	( $( $pallet:ident ),* $(,)? ) => {
+		/// `(pallet name, on-chain version, in-code version)` of every pallet in the runtime.
+		pub fn storage_versions() -> Vec<(&'static str, StorageVersion, StorageVersion)> {
+			sp_std::vec![ $(
+				(
+					<$pallet as PalletInfoAccess>::name(),
+					<$pallet as GetStorageVersion>::on_chain_storage_version(),
+					<$pallet as GetStorageVersion>::current_storage_version(),
+				),
+			)* ]
+		}
+	};
+}
+
+storage_versions!(
+	System,
+	Utility,
+	Timestamp,
+	Babe,
+	Authorship,
+	Indices,
+	Grandpa,
+	Treasury,
+	AssetRate,
+	Contracts,
+	Balances,
+	TransactionPayment,
+	AssetTxPayment,
+	ElectionProviderMultiPhase,
+	Staking,
+	Scheduler,
+	Session,
+	Democracy,
+	Council,
+	TechnicalCommittee,
+	Elections,
+	TechnicalMembership,
+	Sudo,
+	ImOnline,
+	AuthorityDiscovery,
+	Offences,
+	Historical,
+	RandomnessCollectiveFlip,
+	Identity,
+	Society,
+	Vesting,
+	Recovery,
+	Glutton,
+	Preimage,
+	Proxy,
+	Multisig,
+	Bounties,
+	Tips,
+	Assets,
+	Mmr,
+	Lottery,
+	Nis,
+	Uniques,
+	Nfts,
+	Salary,
+	CoreFellowship,
+	TransactionStorage,
+	VoterList,
+	StateTrieMigration,
+	ChildBounties,
+	Referenda,
+	ConvictionVoting,
+	Whitelist,
+	AllianceMotion,
+	Alliance,
+	RankedPolls,
+	RankedCollective,
+	NominationPools,
+	FastUnstake,
+	MessageQueue,
+	Remark,
+	RootTesting,
+	TemplateModule,
+	PoolAssets,
+	AssetConversion,
+	AssetTreasury,
+	EVM,
+	Ethereum,
+	BaseFee,
+	EVMChainId,
+	UnifiedAccounts,
+	Statement,
+	Claims,
+	ContractCalls,
+);
+
+/// Pallets whose on-chain storage version differs from the in-code version.
+pub fn mismatched_storage_versions() -> Vec<(&'static str, StorageVersion, StorageVersion)> {
+	storage_versions()
+		.into_iter()
+		.filter(|(_, on_chain, current)| on_chain != current)
+		.collect()
+}
+
+/// Fails the upgrade under `try-runtime` if any pallet's on-chain storage version differs from
+/// its in-code version once all other migrations have run.
+pub struct StorageVersionAudit;
+
+impl OnRuntimeUpgrade for StorageVersionAudit {
+	fn on_runtime_upgrade() -> Weight {
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), &'static str> {
+		let mismatched = mismatched_storage_versions();
+		for (pallet, on_chain, current) in &mismatched {
+			log::error!(
+				target: LOG_TARGET,
+				"{}: on-chain storage version {:?}, in-code {:?}",
+				pallet,
+				on_chain,
+				current,
+			);
+		}
+		ensure!(mismatched.is_empty(), "Pallets with mismatched storage versions");
+
+		Ok(())
+	}
+}
@@ -0,0 +1,38 @@
+/// Treasury spends denominated in `pallet_assets` tokens, limited by their `AssetRate` value.
+use crate::*;
+
+parameter_types! {
+	pub const AssetSpendPayoutPeriod: BlockNumber = 30 * DAYS;
+}
+
+impl pallet_asset_treasury::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetKind = u32;
+	type Assets = Assets;
+	type NativeBalance = Balance;
+	type BalanceConverter = AssetRate;
+	type SpendOrigin = TreasurySpender;
+	type RejectOrigin = EnsureRootOrHalfCouncil;
+	// The assets are held by the native treasury's account.
+	type PalletId = TreasuryPalletId;
+	type PayoutPeriod = AssetSpendPayoutPeriod;
+	type WeightInfo = pallet_asset_treasury::weights::SubstrateWeight<Runtime>;
+	#[cfg(feature = "runtime-benchmarks")]
+	type BenchmarkHelper = AssetTreasuryBenchmarkHelper;
+}
+
+#[cfg(feature = "runtime-benchmarks")]
+pub struct AssetTreasuryBenchmarkHelper;
+#[cfg(feature = "runtime-benchmarks")]
+impl pallet_asset_treasury::BenchmarkHelper<u32, AccountId, Balance>
+	for AssetTreasuryBenchmarkHelper
+{
+	fn create_asset_kind(who: &AccountId, amount: Balance) -> u32 {
+		use frame_support::traits::tokens::fungibles::{Create, Mutate};
+		let asset_kind = 1_000;
+		<Assets as Create<AccountId>>::create(asset_kind, who.clone(), true, 1).unwrap();
+		<Assets as Mutate<AccountId>>::mint_into(asset_kind, who, amount).unwrap();
+		pallet_asset_rate::ConversionRateToNative::<Runtime>::insert(asset_kind, FixedU128::from(1));
+		asset_kind
+	}
+}
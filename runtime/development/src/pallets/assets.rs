@@ -25,7 +25,7 @@ impl pallet_assets::Config<Instance1> for Runtime {
 	type StringLimit = StringLimit;
 	type Freezer = ();
 	type Extra = ();
-	type CallbackHandle = ();
+	type CallbackHandle = nativex_assets_erc20_precompile::AssetPrecompileCode<Runtime, Instance1>;
 	type WeightInfo = pallet_assets::weights::SubstrateWeight<Runtime>;
 	type RemoveItemsLimit = ConstU32<1000>;
 	#[cfg(feature = "runtime-benchmarks")]
@@ -0,0 +1,17 @@
+/// Genesis allocations paid out to the holders of Ethereum or sr25519 keys, with a signature of
+/// the destination account.
+use crate::*;
+
+parameter_types! {
+	pub ClaimsPrefix: &'static [u8] = b"Pay NATIVEX to the NativeX account 0x";
+	pub const MaxClaimStatementLength: u32 = 1024;
+}
+
+impl pallet_claims::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type VestingSchedule = Vesting;
+	type Prefix = ClaimsPrefix;
+	type MaxStatementLength = MaxClaimStatementLength;
+	type WeightInfo = pallet_claims::weights::SubstrateWeight<Runtime>;
+}
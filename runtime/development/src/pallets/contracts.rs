@@ -1,4 +1,13 @@
 use crate::*;
+use contracts_debug_runtime_api::{CallSummary, ContractEvent};
+use frame_support::traits::Contains;
+use nativex_assets_extension::{weights::SubstrateWeight as AssetsExtensionWeight, AssetsExtension};
+use nativex_chain_extensions::Registry;
+use nativex_nfts_extension::{weights::SubstrateWeight as NftsExtensionWeight, NftsExtension};
+use nativex_rand_extension::{weights::SubstrateWeight as RandExtensionWeight, FetchRandomExtension};
+use pallet_contracts::Determinism;
+use pallet_contracts_primitives::Code;
+use sp_runtime::traits::StaticLookup;
 
 parameter_types! {
 	pub const DepositPerItem: Balance = deposit(1, 0);
@@ -7,19 +16,83 @@ parameter_types! {
 	pub Schedule: pallet_contracts::Schedule<Runtime> = Default::default();
 }
 
+parameter_types! {
+	/// The calls contracts may dispatch at genesis, or once the allowlist is added to a running
+	/// chain: keep-alive balance transfers, asset transfers and approvals, batches, proxy calls
+	/// and removals, and stored remarks.
+	///
+	/// `contract_calls_keep_their_indices` in the runtime tests fails if one of them moves.
+	pub InitialContractCalls: Vec<pallet_contract_calls::CallIndex> = vec![
+		(10, 3),
+		(10, 4),
+		(38, 8),
+		(38, 9),
+		(38, 22),
+		(38, 23),
+		(38, 25),
+		(1, 0),
+		(1, 2),
+		(1, 4),
+		(34, 0),
+		(34, 2),
+		(34, 3),
+		(60, 0),
+	];
+}
+
+impl pallet_contract_calls::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type UpdateOrigin = EnsureRootOrHalfCouncil;
+	type WeightInfo = pallet_contract_calls::weights::SubstrateWeight<Runtime>;
+}
+
+/// Runtime calls contracts may dispatch through `call_runtime`.
+///
+/// A call must be in the allowlist of `ContractCalls`, which governance changes. Calls that
+/// dispatch other calls are only allowed if the calls they carry are, and a few calls are never
+/// allowed, whatever the allowlist says.
+pub struct ContractCallFilter;
+
+impl Contains<RuntimeCall> for ContractCallFilter {
+	fn contains(call: &RuntimeCall) -> bool {
+		if !ContractCalls::contains(call) {
+			return false
+		}
+		match call {
+			// A contract must not reap its own account.
+			RuntimeCall::Balances(pallet_balances::Call::transfer_all { keep_alive, .. }) =>
+				*keep_alive,
+			// Adding a proxy hands control of the contract's account over.
+			RuntimeCall::Proxy(pallet_proxy::Call::add_proxy { .. }) => false,
+			RuntimeCall::Utility(
+				pallet_utility::Call::batch { calls } |
+				pallet_utility::Call::batch_all { calls } |
+				pallet_utility::Call::force_batch { calls },
+			) => calls.iter().all(Self::contains),
+			RuntimeCall::Utility(pallet_utility::Call::as_derivative { call, .. }) |
+			RuntimeCall::Proxy(
+				pallet_proxy::Call::proxy { call, .. } |
+				pallet_proxy::Call::proxy_announced { call, .. },
+			) => Self::contains(call),
+			_ => true,
+		}
+	}
+}
+
+/// Chain extensions available to contracts, dispatched on the upper 16 bits of `func_id`.
+pub type ChainExtensions = (
+	FetchRandomExtension<RandExtensionWeight<Runtime>>,
+	AssetsExtension<Instance1, AssetsExtensionWeight<Runtime>>,
+	NftsExtension<(), NftsExtensionWeight<Runtime>>,
+);
+
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
 	type Randomness = RandomnessCollectiveFlip;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
-	///
-	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
-	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
-	/// change because that would break already deployed contracts. The `Call` structure itself
-	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = Nothing;
+	type CallFilter = ContractCallFilter;
 
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DataDepositPerByte;
@@ -27,7 +100,7 @@ impl pallet_contracts::Config for Runtime {
 	type CallStack = [pallet_contracts::Frame<Self>; 5];
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Runtime>;
-	type ChainExtension = ();
+	type ChainExtension = Registry<ChainExtensions>;
 	type Schedule = Schedule;
 	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
 	type MaxCodeLen = ConstU32<{ 123 * 1024 }>;
@@ -35,3 +108,168 @@ impl pallet_contracts::Config for Runtime {
 	type UnsafeUnstableInterface = ConstBool<false>;
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
 }
+
+/// What a debugged contract call executes.
+pub enum DebugEntry {
+	/// Call `dest` with `data`.
+	Call { dest: AccountId, data: Vec<u8> },
+	/// Instantiate `code` with the constructor input `data`.
+	Instantiate { code: Code<Hash>, data: Vec<u8>, salt: Vec<u8> },
+}
+
+/// Execute `entry` with debugging enabled and summarize it.
+///
+/// The events of the current block are cleared first so that only the debugged call's events are
+/// collected, so this must only run in a throwaway runtime API call.
+pub fn debug_contract_call(
+	origin: AccountId,
+	value: Balance,
+	gas_limit: Option<Weight>,
+	storage_deposit_limit: Option<Balance>,
+	entry: DebugEntry,
+) -> CallSummary<AccountId, Balance, Hash> {
+	let gas_limit = gas_limit.unwrap_or(RuntimeBlockWeights::get().max_block);
+	System::reset_events();
+
+	let (selector, gas_consumed, gas_required, storage_deposit, result, debug_message) =
+		match entry {
+			DebugEntry::Call { dest, data } => {
+				let selector = data.get(..4).and_then(|selector| selector.try_into().ok());
+				let call = Contracts::bare_call(
+					origin,
+					dest,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					data,
+					true,
+					Determinism::Enforced,
+				);
+				(
+					selector,
+					call.gas_consumed,
+					call.gas_required,
+					call.storage_deposit,
+					call.result,
+					call.debug_message,
+				)
+			},
+			DebugEntry::Instantiate { code, data, salt } => {
+				let selector = data.get(..4).and_then(|selector| selector.try_into().ok());
+				let instantiate = Contracts::bare_instantiate(
+					origin,
+					value,
+					gas_limit,
+					storage_deposit_limit,
+					code,
+					data,
+					salt,
+					true,
+				);
+				(
+					selector,
+					instantiate.gas_consumed,
+					instantiate.gas_required,
+					instantiate.storage_deposit,
+					instantiate.result.map(|instantiated| instantiated.result),
+					instantiate.debug_message,
+				)
+			},
+		};
+
+	let events = System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::Contracts(event) => contract_event(event),
+			_ => None,
+		})
+		.collect();
+
+	CallSummary {
+		selector,
+		gas_consumed,
+		gas_required,
+		storage_deposit,
+		result,
+		debug_message,
+		events,
+	}
+}
+
+/// The part of a `pallet_contracts` event a call summary reports.
+fn contract_event(
+	event: pallet_contracts::Event<Runtime>,
+) -> Option<ContractEvent<AccountId, Hash>> {
+	use pallet_contracts::Event;
+	match event {
+		Event::Called { caller, contract } => Some(ContractEvent::Called { caller, contract }),
+		Event::DelegateCalled { contract, code_hash } =>
+			Some(ContractEvent::DelegateCalled { contract, code_hash }),
+		Event::Instantiated { deployer, contract } =>
+			Some(ContractEvent::Instantiated { deployer, contract }),
+		Event::Terminated { contract, beneficiary } =>
+			Some(ContractEvent::Terminated { contract, beneficiary }),
+		Event::ContractEmitted { contract, data } =>
+			Some(ContractEvent::Emitted { contract, data }),
+		_ => None,
+	}
+}
+
+/// Summarize the contract call or instantiation `extrinsic` makes, as its signer.
+///
+/// The extrinsic itself is not applied: its nonce, fees and signed extensions are skipped.
+/// Returns `None` if it is unsigned or not a direct call into `Contracts`.
+pub fn debug_contract_extrinsic(
+	extrinsic: UncheckedExtrinsic,
+) -> Option<CallSummary<AccountId, Balance, Hash>> {
+	let (signer, _, _) = extrinsic.0.signature?;
+	let origin = <Runtime as frame_system::Config>::Lookup::lookup(signer).ok()?;
+
+	let (value, gas_limit, storage_deposit_limit, entry) = match extrinsic.0.function {
+		RuntimeCall::Contracts(pallet_contracts::Call::call {
+			dest,
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			data,
+		}) => {
+			let dest = <Runtime as frame_system::Config>::Lookup::lookup(dest).ok()?;
+			(value, gas_limit, storage_deposit_limit, DebugEntry::Call { dest, data })
+		},
+		RuntimeCall::Contracts(pallet_contracts::Call::instantiate_with_code {
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code,
+			data,
+			salt,
+		}) => (
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			DebugEntry::Instantiate { code: Code::Upload(code), data, salt },
+		),
+		RuntimeCall::Contracts(pallet_contracts::Call::instantiate {
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			code_hash,
+			data,
+			salt,
+		}) => (
+			value,
+			gas_limit,
+			storage_deposit_limit,
+			DebugEntry::Instantiate { code: Code::Existing(code_hash), data, salt },
+		),
+		_ => return None,
+	};
+
+	Some(debug_contract_call(
+		origin,
+		value,
+		Some(gas_limit),
+		storage_deposit_limit.map(Into::into),
+		entry,
+	))
+}
@@ -0,0 +1,83 @@
+/// Frontier EVM, Ethereum block emulation, EIP-1559 base fee and the EVM chain id.
+use crate::*;
+use pallet_ethereum::PostLogContent;
+use pallet_evm::EnsureAddressTruncated;
+use sp_core::U256;
+
+/// The gas limit of an Ethereum block, spread over the normal dispatch weight of a block.
+const BLOCK_GAS_LIMIT: u64 = 75_000_000;
+/// The proof size a block may use, for `GasLimitPovSizeRatio`.
+const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+
+parameter_types! {
+	pub BlockGasLimit: U256 = U256::from(BLOCK_GAS_LIMIT);
+	pub const GasLimitPovSizeRatio: u64 = BLOCK_GAS_LIMIT / MAX_POV_SIZE;
+	pub WeightPerGas: Weight = Weight::from_parts(
+		(NORMAL_DISPATCH_RATIO * MAXIMUM_BLOCK_WEIGHT).ref_time() / BLOCK_GAS_LIMIT,
+		0,
+	);
+	pub PrecompilesValue: NativexPrecompiles<Runtime> = NativexPrecompiles::new();
+}
+
+impl pallet_evm::Config for Runtime {
+	type FeeCalculator = BaseFee;
+	type GasWeightMapping = pallet_evm::FixedGasWeightMapping<Self>;
+	type WeightPerGas = WeightPerGas;
+	type BlockHashMapping = pallet_ethereum::EthereumBlockHashMapping<Self>;
+	type CallOrigin = EnsureAddressTruncated;
+	type WithdrawOrigin = EnsureAddressTruncated;
+	type AddressMapping = UnifiedAddressMapping;
+	type Currency = Balances;
+	type RuntimeEvent = RuntimeEvent;
+	type PrecompilesType = NativexPrecompiles<Self>;
+	type PrecompilesValue = PrecompilesValue;
+	type ChainId = EVMChainId;
+	type BlockGasLimit = BlockGasLimit;
+	type Runner = pallet_evm::runner::stack::Runner<Self>;
+	type OnChargeTransaction = pallet_evm::EVMCurrencyAdapter<Balances, DealWithFees>;
+	type OnCreate = ();
+	type FindAuthor = BabeAuthorAsH160;
+	type GasLimitPovSizeRatio = GasLimitPovSizeRatio;
+	type Timestamp = Timestamp;
+	type WeightInfo = pallet_evm::weights::SubstrateWeight<Self>;
+}
+
+parameter_types! {
+	pub const PostBlockAndTxnHashes: PostLogContent = PostLogContent::BlockAndTxnHashes;
+}
+
+impl pallet_ethereum::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type StateRoot = pallet_ethereum::IntermediateStateRoot<Self>;
+	type PostLogContent = PostBlockAndTxnHashes;
+	type ExtraDataLength = ConstU32<30>;
+}
+
+parameter_types! {
+	/// 1 gwei, with the 18 decimals NATIVEX shares with ether.
+	pub DefaultBaseFeePerGas: U256 = U256::from(MILLICENTS);
+	pub DefaultElasticity: Permill = Permill::from_parts(125_000);
+}
+
+/// Move the base fee towards keeping blocks half full.
+pub struct BaseFeeThreshold;
+impl pallet_base_fee::BaseFeeThreshold for BaseFeeThreshold {
+	fn lower() -> Permill {
+		Permill::zero()
+	}
+	fn ideal() -> Permill {
+		Permill::from_parts(500_000)
+	}
+	fn upper() -> Permill {
+		Permill::from_parts(1_000_000)
+	}
+}
+
+impl pallet_base_fee::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Threshold = BaseFeeThreshold;
+	type DefaultBaseFeePerGas = DefaultBaseFeePerGas;
+	type DefaultElasticity = DefaultElasticity;
+}
+
+impl pallet_evm_chain_id::Config for Runtime {}
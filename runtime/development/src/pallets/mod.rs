@@ -41,8 +41,13 @@ pub mod asset_rate;
 pub use asset_rate::*;
 
 /// FRAME asset conversion pallet.
-// pub mod asset_conversion;
-// pub use asset_conversion::*;
+pub mod asset_conversion;
+pub use asset_conversion::*;
+
+/// Treasury spends denominated in non-native assets.
+pub mod asset_treasury;
+pub use asset_treasury::*;
+
 pub mod timestamp;
 pub use timestamp::*;
 
@@ -147,6 +152,18 @@ pub use elections_phragmen::*;
 pub mod contracts;
 pub use contracts::*;
 
+/// Frontier EVM and Ethereum compatibility.
+pub mod evm;
+pub use evm::*;
+
+/// Ethereum addresses bound to native accounts.
+pub mod unified_accounts;
+pub use unified_accounts::*;
+
+/// Genesis allocations claimed with Ethereum or sr25519 signatures.
+pub mod claims;
+pub use claims::*;
+
 /// FRAME
 pub mod sudo;
 pub use sudo::*;
@@ -203,6 +220,10 @@ pub use nfts::*;
 pub mod transaction_storage;
 pub use transaction_storage::*;
 
+/// Statement store pallet.
+pub mod statement;
+pub use statement::*;
+
 /// FRAME pallet for whitelisting call, and dispatch from specific origin.
 pub mod whitelist;
 pub use whitelist::*;
@@ -3,8 +3,13 @@
 /// `inclusion_fee = base_fee + length_fee + [targeted_fee_adjustment*weight_fee`];
 /// `final_fee = inclusion_fee + tip`.
 use crate::*;
+use runtime_common::constants::fee::WeightToFee;
 
 parameter_types! {
+	/// The fee charged for an extrinsic of exactly `ExtrinsicBaseWeight`.
+	pub const ExtrinsicBaseFee: Balance = 10 * CENTS;
+	/// The fee charged for each KiB of proof size.
+	pub const ProofSizeFeePerKiB: Balance = 10 * MILLICENTS;
 	pub const TransactionByteFee: Balance = 10 * MILLICENTS;
 	pub const OperationalFeeMultiplier: u8 = 5;
 	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
@@ -17,7 +22,7 @@ impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnChargeTransaction = CurrencyAdapter<Balances, DealWithFees>;
 	type OperationalFeeMultiplier = OperationalFeeMultiplier;
-	type WeightToFee = IdentityFee<Balance>;
+	type WeightToFee = WeightToFee<ExtrinsicBaseFee, ProofSizeFeePerKiB>;
 	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
 	type FeeMultiplierUpdate = TargetedFeeAdjustment<
 		Self,
@@ -16,9 +16,36 @@ parameter_types! {
 	pub const MaximumReasonLength: u32 = 300;
 	pub const MaxApprovals: u32 = 100;
 	pub const MaxBalance: Balance = Balance::max_value();
-
+	pub const SmallSpendLimit: Balance = 1_000 * NATIVEX;
+	pub const MediumSpendLimit: Balance = 10_000 * NATIVEX;
+	pub const BigSpendLimit: Balance = 100_000 * NATIVEX;
 }
 
+/// A council motion carried by at least half of the council may spend up to `SmallSpendLimit`.
+pub type SmallSpender = EnsureWithSuccess<
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 2>,
+	AccountId,
+	SmallSpendLimit,
+>;
+/// A council motion carried by at least 3/5 of the council may spend up to `MediumSpendLimit`.
+pub type MediumSpender = EnsureWithSuccess<
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 5>,
+	AccountId,
+	MediumSpendLimit,
+>;
+/// A council motion carried by at least 3/4 of the council may spend up to `BigSpendLimit`.
+pub type BigSpender = EnsureWithSuccess<
+	pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>,
+	AccountId,
+	BigSpendLimit,
+>;
+/// Spend origins of the native and asset treasuries, with the spend limit as success value.
+/// The highest tier an origin qualifies for is tried first.
+pub type TreasurySpender = EitherOf<
+	EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxBalance>,
+	EitherOf<BigSpender, EitherOf<MediumSpender, SmallSpender>>,
+>;
+
 impl pallet_treasury::Config for Runtime {
 	type PalletId = TreasuryPalletId;
 	type Currency = Balances;
@@ -41,5 +68,5 @@ impl pallet_treasury::Config for Runtime {
 	type SpendFunds = Bounties;
 	type WeightInfo = pallet_treasury::weights::SubstrateWeight<Runtime>;
 	type MaxApprovals = MaxApprovals;
-	type SpendOrigin = EnsureWithSuccess<EnsureRoot<AccountId>, AccountId, MaxBalance>;
+	type SpendOrigin = TreasurySpender;
 }
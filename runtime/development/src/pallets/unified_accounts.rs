@@ -0,0 +1,27 @@
+/// Ethereum addresses bound to native accounts, shared by the EVM and native extrinsics.
+use crate::*;
+use pallet_evm::{AddressMapping, HashedAddressMapping};
+use sp_runtime::traits::Convert;
+
+/// The account the EVM uses for an H160 that is not bound.
+pub struct HashedEvmAccount;
+impl Convert<H160, AccountId> for HashedEvmAccount {
+	fn convert(evm_address: H160) -> AccountId {
+		HashedAddressMapping::<BlakeTwo256>::into_account_id(evm_address)
+	}
+}
+
+/// Maps EVM addresses through the bindings of `UnifiedAccounts`.
+pub struct UnifiedAddressMapping;
+impl AddressMapping<AccountId> for UnifiedAddressMapping {
+	fn into_account_id(evm_address: H160) -> AccountId {
+		UnifiedAccounts::native_account(&evm_address)
+	}
+}
+
+impl pallet_unified_accounts::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type DefaultAccount = HashedEvmAccount;
+	type WeightInfo = pallet_unified_accounts::weights::SubstrateWeight<Runtime>;
+}
@@ -0,0 +1,63 @@
+//! The EVM precompiles of the runtime.
+//!
+//! Next to the Ethereum precompiles at `0x01`-`0x05`, `0x0400` and `0x0401` hold
+//! `Sha3FIPS256` and `ECRecoverPublicKey`, and every asset of `Assets` is an ERC-20 token at
+//! `0xffffffff` followed by its id, see [`nativex_assets_erc20_precompile`].
+
+use crate::{Instance1, Runtime};
+use nativex_assets_erc20_precompile::AssetsErc20;
+use pallet_evm::{IsPrecompileResult, Precompile, PrecompileHandle, PrecompileResult, PrecompileSet};
+use pallet_evm_precompile_modexp::Modexp;
+use pallet_evm_precompile_sha3fips::Sha3FIPS256;
+use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
+use sp_core::H160;
+use sp_std::marker::PhantomData;
+
+/// The precompiles available to EVM contracts.
+pub struct NativexPrecompiles<R>(PhantomData<R>);
+
+impl<R> NativexPrecompiles<R> {
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// The addresses of the fixed precompiles.
+	pub fn used_addresses() -> [H160; 7] {
+		[hash(1), hash(2), hash(3), hash(4), hash(5), hash(1024), hash(1025)]
+	}
+}
+
+impl<R> Default for NativexPrecompiles<R> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl PrecompileSet for NativexPrecompiles<Runtime> {
+	fn execute(&self, handle: &mut impl PrecompileHandle) -> Option<PrecompileResult> {
+		match handle.code_address() {
+			// Ethereum precompiles:
+			a if a == hash(1) => Some(ECRecover::execute(handle)),
+			a if a == hash(2) => Some(Sha256::execute(handle)),
+			a if a == hash(3) => Some(Ripemd160::execute(handle)),
+			a if a == hash(4) => Some(Identity::execute(handle)),
+			a if a == hash(5) => Some(Modexp::execute(handle)),
+			// Non-Frontier specific nor Ethereum precompiles:
+			a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
+			a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
+			_ => AssetsErc20::<Runtime, Instance1>::execute_if_asset(handle),
+		}
+	}
+
+	fn is_precompile(&self, address: H160, _remaining_gas: u64) -> IsPrecompileResult {
+		IsPrecompileResult::Answer {
+			is_precompile: Self::used_addresses().contains(&address) ||
+				AssetsErc20::<Runtime, Instance1>::is_asset(address),
+			extra_cost: AssetsErc20::<Runtime, Instance1>::is_asset_cost(address),
+		}
+	}
+}
+
+fn hash(a: u64) -> H160 {
+	H160::from_low_u64_be(a)
+}